@@ -1,26 +1,24 @@
 // Constants for the CapsuleX program
 
-use anchor_lang::prelude::*;
-
-// Hardcoded app authority - this is the local wallet for testing  
-// In production, this would be the team's authority wallet
-pub const APP_AUTHORITY: Pubkey = anchor_lang::solana_program::pubkey!("FnnLFxD5jZh9dhMbPBYvon3nBWm2gmJDaQnXJhYD2G12");
-
 // Program constants based on proper monetization strategy
 pub const SOLANA_BASE_FEE: u64 = 5000; // 0.000005 SOL in lamports (network fee reference)
 
-// V2 Target Fee Structure - USD-based pricing (calculated at $180/SOL)
+// Default fees written to ProgramConfig by initialize_program. Live fees are read from
+// ProgramConfig at runtime and can be repriced with update_config (no redeploy needed).
 // Capsule creation: $0.25 service fee = 1,388,889 lamports at $180/SOL
-pub const CAPSULE_CREATION_FEE: u64 = 1_388_889; // ~$0.25 at $180/SOL
-// Guess submission: $0.01 service fee = 55,556 lamports at $180/SOL  
-pub const SERVICE_FEE: u64 = 55_556; // ~$0.01 at $180/SOL
+pub const DEFAULT_CAPSULE_CREATION_FEE: u64 = 1_388_889;
+// Guess submission: $0.01 service fee = 55,556 lamports at $180/SOL
+pub const DEFAULT_SERVICE_FEE: u64 = 55_556;
 
 // V1 Legacy (Under-priced) - for reference/rollback:
 // pub const CAPSULE_CREATION_FEE: u64 = SOLANA_BASE_FEE * 10; // 0.00005 SOL (~$0.009)
 // pub const SERVICE_FEE: u64 = SOLANA_BASE_FEE * 1; // 0.000005 SOL (~$0.0009)
 pub const PREMIUM_FEATURE_FEE: u64 = SOLANA_BASE_FEE * 5; // 0.000025 SOL
-pub const BADGE_MINT_FEE: u64 = SOLANA_BASE_FEE * 5; // 0.000025 SOL
-pub const TROPHY_MINT_FEE: u64 = SOLANA_BASE_FEE * 2; // 0.00001 SOL
+pub const DEFAULT_BADGE_MINT_FEE: u64 = SOLANA_BASE_FEE * 5; // 0.000025 SOL
+pub const DEFAULT_TROPHY_MINT_FEE: u64 = SOLANA_BASE_FEE * 2; // 0.00001 SOL
+
+// Upper bound for any configured fee (guards against fat-fingered updates)
+pub const MAX_CONFIG_FEE: u64 = 1_000_000_000; // 1 SOL
 
 // Points-based reward system (no monetary rewards)
 pub const WINNER_POINTS: u64 = 100; // Points for correct guess
@@ -31,8 +29,18 @@ pub const CREATOR_BONUS_POINTS: u64 = 50; // Points for creator when someone win
 pub const MAX_GUESSES_PER_GAME: u32 = 100;
 pub const MAX_CONTENT_HASH_LENGTH: usize = 64;
 pub const MAX_GUESS_CONTENT_LENGTH: usize = 280; // Twitter length
+// Guess commitments can be opened from reveal_date until this many seconds after it
+pub const GUESS_REVEAL_WINDOW: i64 = 3600; // 1 hour, matching the default verification window
+// Longest verify_guess deadline after reveal_date (verification_window_hours is a u8)
+pub const MAX_VERIFICATION_WINDOW: i64 = u8::MAX as i64 * 3600;
+// Winners can mint their badge until this long after the reveal; until then close_game waits for them
+pub const BADGE_CLAIM_WINDOW: i64 = 30 * 24 * 3600; // 30 days
 pub const MAX_METADATA_URI_LENGTH: usize = 200;
 pub const MAX_BADGE_TYPE_LENGTH: usize = 32;
+pub const MAX_NFT_NAME_LENGTH: usize = 32; // Token Metadata name limit
+pub const MAX_NFT_SYMBOL_LENGTH: usize = 10; // Token Metadata symbol limit
+pub const BADGE_NFT_SYMBOL: &str = "CXBADGE";
+pub const TROPHY_NFT_SYMBOL: &str = "CXTROPHY";
 
 // Content storage tiers
 pub const MAX_ONCHAIN_CONTENT_LENGTH: usize = 280; // Twitter-sized content stored on-chain
@@ -55,7 +63,11 @@ pub const CAPSULE_ACCOUNT_SIZE: usize = 8 + // discriminator
     1 + // is_revealed
     1 + // is_active
     1 + // bump
-    200; // padding (increased for complex content storage)
+    1 + 8 + // capsule_index (Option<u64>)
+    1 + 8 + // legacy_index (Option<u64>)
+    1 + // nft_minted
+    8 + // revealed_at
+    173; // padding (increased for complex content storage, reduced by 27 bytes for capsule_index, legacy_index, nft_minted and revealed_at)
 
 pub const GAME_ACCOUNT_SIZE: usize = 8 + // discriminator
     32 + // capsule_id
@@ -70,17 +82,214 @@ pub const GAME_ACCOUNT_SIZE: usize = 8 + // discriminator
     32 + // winner (optional, backward compatibility)
     4 + (32 * 10) + // winners: Vec<Pubkey> - reserve space for up to 10 winners
     1 + // bump
-    128; // padding (increased for future expansion)
+    1 + // bonus_paid
+    2 + // badged_placements
+    4 + // guesses_settled
+    4 + // guesses_verified
+    2 + // reschedule_count
+    4 + // reschedule_consents
+    8 + // vault_fees_held
+    8 + // creator_fees_held
+    95; // padding (reduced by 1 byte for bonus_paid, 2 for badged_placements, 4 for guesses_settled, 4 for guesses_verified, 6 for reschedule consents, 16 for vault_fees_held and creator_fees_held)
 
 pub const GUESS_ACCOUNT_SIZE: usize = 8 + // discriminator
     32 + // game_id
     32 + // guesser
-    280 + // guess_content
+    4 + 280 + // guess_content (String: length prefix + MAX_GUESS_CONTENT_LENGTH bytes)
     8 + // timestamp
     1 + // is_paid
     1 + // is_correct
     1 + // is_anonymous
-    31; // padding (reduced by 1 byte for is_anonymous)
+    1 + // bump
+    1 + // is_verified
+    8 + // verified_at
+    32 + // commitment
+    1 + // is_opened
+    1 + // is_refunded
+    8 + // fee_paid
+    8 + // creator_share_paid
+    2 + // reschedule_consent
+    2; // padding (was 31, which also held bump; the fields from is_verified on grew the account from 394 to 431 bytes, see migrate_legacy_guess)
+
+// Guesses created before commit-reveal; the only layout migrate_legacy_guess accepts
+pub const LEGACY_GUESS_ACCOUNT_SIZE: usize = 394;
+
+// Oracle signatures
+pub const ORACLE_SIGNATURE_MAX_AGE: i64 = 900; // 15 minutes
+pub const ORACLE_SIGNATURE_MAX_FUTURE_DRIFT: i64 = 60; // 1 minute clock drift
+
+// Oracle registry
+pub const MAX_ORACLE_KEYS: usize = 8;
+pub const MAX_ORACLE_ROTATION_GRACE: i64 = ORACLE_SIGNATURE_MAX_AGE;
+pub const MAX_ORACLE_RELAYERS: usize = 4; // Wallets allowed to submit verdicts for any game
+
+pub const ORACLE_KEY_SIZE: usize = 32 + // public_key
+    8 + // activated_at
+    1 + 8; // expires_at (Option<i64>)
+
+pub const ORACLE_CONFIG_ACCOUNT_SIZE: usize = 8 + // discriminator
+    4 + (ORACLE_KEY_SIZE * MAX_ORACLE_KEYS) + // oracles: Vec<OracleKey>
+    1 + // bump
+    4 + (32 * MAX_ORACLE_RELAYERS) + // relayers: Vec<Pubkey>
+    32; // padding
+
+pub const USED_NONCE_ACCOUNT_SIZE: usize = 8 + // discriminator
+    32 + // nonce_hash
+    32 + // oracle
+    32 + // payer
+    8 + // used_at
+    8 + // expires_at
+    1; // bump
+
+pub const PROGRAM_CONFIG_ACCOUNT_SIZE: usize = 8 + // discriminator
+    32 + // app_authority
+    8 + // capsule_creation_fee
+    8 + // service_fee
+    8 + // badge_mint_fee
+    8 + // trophy_mint_fee
+    1 + // bump
+    2 + // creator_share_bps
+    8 + // refund_grace_period
+    1 + // soulbound_nfts
+    8 + // pending_refund_liability
+    45; // padding (reduced by 19 bytes for creator_share_bps, refund_grace_period, soulbound_nfts and pending_refund_liability)
+
+// Treasury payouts
+pub const MAX_TREASURY_RECIPIENTS: usize = 5;
+pub const BASIS_POINTS_DENOMINATOR: u64 = 10_000; // 100% in basis points
+
+pub const TREASURY_RECIPIENT_SIZE: usize = 32 + // wallet
+    2; // share_bps
+
+pub const TREASURY_ACCOUNT_SIZE: usize = 8 + // discriminator
+    4 + (TREASURY_RECIPIENT_SIZE * MAX_TREASURY_RECIPIENTS) + // recipients: Vec<TreasuryRecipient>
+    8 + // total_withdrawn
+    1 + // bump
+    32; // padding
+
+pub const AUTHORITY_PROPOSAL_ACCOUNT_SIZE: usize = 8 + // discriminator
+    32 + // current_authority
+    32 + // proposed_authority
+    8 + // proposed_at
+    1; // bump
+
+// Points issuers allowed to grant leaderboard points besides the vault authority
+pub const MAX_POINTS_ISSUERS: usize = 8;
+
+pub const POINTS_ISSUER_SIZE: usize = 32 + // issuer
+    8 + // points_cap
+    8; // points_issued
+
+pub const POINTS_ISSUER_REGISTRY_ACCOUNT_SIZE: usize = 8 + // discriminator
+    4 + (POINTS_ISSUER_SIZE * MAX_POINTS_ISSUERS) + // issuers: Vec<PointsIssuer>
+    1 + // bump
+    32; // padding
+
+// Per-game prize escrow (SOL or a single SPL mint)
+pub const MAX_PRIZE_SPLITS: usize = 10; // One share per winner placement, matching Game.winners capacity
+pub const MAX_PRIZE_FUNDERS: usize = 8; // One slot is kept for the pool creator
+pub const MIN_PRIZE_CONTRIBUTION: u64 = 10_000_000; // 0.01 SOL to take a funder slot in a SOL pool
+
+pub const PRIZE_FUNDER_SIZE: usize = 32 + // funder
+    8 + // amount
+    1; // refunded
+
+pub const PRIZE_POOL_ACCOUNT_SIZE: usize = 8 + // discriminator
+    32 + // game
+    32 + // creator
+    1 + 32 + // mint (Option<Pubkey>, None for SOL)
+    4 + (2 * MAX_PRIZE_SPLITS) + // split_bps: Vec<u16>
+    8 + // total_funded
+    8 + // total_paid
+    4 + (PRIZE_FUNDER_SIZE * MAX_PRIZE_FUNDERS) + // funders: Vec<PrizeFunder>
+    4 + (32 * MAX_PRIZE_SPLITS) + // claimed: Vec<Pubkey>
+    1 + // bump
+    32; // padding
+
+// Creator revenue share of guess fees
+pub const DEFAULT_CREATOR_SHARE_BPS: u16 = 2500; // 25% of each guess fee goes to the capsule creator
+
+pub const CREATOR_EARNINGS_ACCOUNT_SIZE: usize = 8 + // discriminator
+    32 + // creator
+    8 + // total_earned
+    8 + // total_claimed
+    8 + // available_balance
+    8 + // last_claim
+    8 + // total_guesses_received
+    1 + // bump
+    8 + // locked_balance
+    24; // padding (reduced by 8 bytes for locked_balance)
+
+// Creator profiles number each creator's capsules; the index replaces reveal_date in capsule seeds
+pub const CREATOR_PROFILE_ACCOUNT_SIZE: usize = 8 + // discriminator
+    32 + // creator
+    8 + // capsule_count
+    1 + // bump
+    32; // padding
+
+pub const LEGACY_CAPSULE_LINK_ACCOUNT_SIZE: usize = 8 + // discriminator
+    32 + // creator
+    32 + // capsule
+    8 + // capsule_index
+    1 + // bump
+    16; // padding
+
+// Plaintext published after a reveal, checked on-chain against content_integrity_hash
+pub const MAX_PUBLISHED_PLAINTEXT_LENGTH: usize = 512; // Leaves room for the accounts in a single transaction
+
+pub const PUBLISHED_PLAINTEXT_ACCOUNT_SIZE: usize = 8 + // discriminator
+    32 + // capsule
+    32 + // publisher
+    4 + MAX_PUBLISHED_PLAINTEXT_LENGTH + // plaintext
+    32 + // content_hash
+    8 + // published_at
+    1 + // bump
+    16; // padding
+
+// Winner badges record the placement they were minted for
+pub const WINNER_BADGE_ACCOUNT_SIZE: usize = 8 + // discriminator
+    32 + // game
+    32 + // winner
+    32 + // badge_mint
+    1 + // placement
+    4 + MAX_BADGE_TYPE_LENGTH + // badge_type
+    8 + // minted_at
+    1 + // bump
+    16; // padding
+
+// Trophy definitions hold the criteria mint_trophy_nft evaluates
+pub const TROPHY_SERIAL_PLACEHOLDER: &str = "{serial}"; // Replaced by the trophy's serial number in metadata_uri_template
+
+pub const TROPHY_DEFINITION_ACCOUNT_SIZE: usize = 8 + // discriminator
+    4 + MAX_BADGE_TYPE_LENGTH + // trophy_type
+    4 + // criteria.min_games_won
+    4 + // criteria.min_games_played
+    4 + // criteria.min_capsules_created
+    8 + // criteria.min_total_points
+    2 + // season
+    8 + // season_start
+    1 + 8 + // season_end: Option<i64>
+    1 + 4 + // max_supply: Option<u32>
+    4 + // minted
+    4 + MAX_METADATA_URI_LENGTH + // metadata_uri_template
+    1 + // is_active
+    1 + // bump
+    32; // padding
+
+// Creator bonds for gamified capsules
+pub const DEFAULT_REFUND_GRACE_PERIOD: i64 = 7 * 24 * 3600; // Creator has 7 days after reveal_date to reveal before guessers are refunded
+pub const MAX_REFUND_GRACE_PERIOD: i64 = 90 * 24 * 3600;
+
+pub const BOND_ACCOUNT_SIZE: usize = 8 + // discriminator
+    32 + // capsule
+    32 + // creator
+    8 + // initial_deposit
+    8 + // current_balance
+    8 + // refund_per_guess
+    4 + // refunds_paid
+    8 + // created_at
+    1 + // bump
+    32; // padding
 
 pub const LEADERBOARD_ACCOUNT_SIZE: usize = 8 + // discriminator
     32 + // user
@@ -98,6 +307,25 @@ pub const GAME_SEED: &[u8] = b"game";
 pub const GUESS_SEED: &[u8] = b"guess";
 pub const LEADERBOARD_SEED: &[u8] = b"leaderboard";
 pub const VAULT_SEED: &[u8] = b"vault";
+pub const PROGRAM_CONFIG_SEED: &[u8] = b"program_config";
 pub const CAPSULE_MINT_SEED: &[u8] = b"capsule_mint";
 pub const BADGE_MINT_SEED: &[u8] = b"badge_mint";
-pub const TROPHY_MINT_SEED: &[u8] = b"trophy_mint"; 
\ No newline at end of file
+pub const TROPHY_MINT_SEED: &[u8] = b"trophy_mint";
+pub const ORACLE_CONFIG_SEED: &[u8] = b"oracle_config";
+pub const USED_NONCE_SEED: &[u8] = b"used_nonce";
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const AUTHORITY_PROPOSAL_SEED: &[u8] = b"authority_proposal";
+pub const POINTS_ISSUER_REGISTRY_SEED: &[u8] = b"points_issuers";
+pub const PRIZE_POOL_SEED: &[u8] = b"prize_pool";
+pub const PRIZE_ESCROW_SEED: &[u8] = b"prize_escrow";
+pub const CREATOR_EARNINGS_SEED: &[u8] = b"creator_earnings";
+pub const BOND_SEED: &[u8] = b"bond";
+pub const CREATOR_PROFILE_SEED: &[u8] = b"creator_profile";
+pub const PUBLISHED_PLAINTEXT_SEED: &[u8] = b"plaintext";
+pub const WINNER_BADGE_SEED: &[u8] = b"winner_badge";
+pub const COLLECTION_MINT_SEED: &[u8] = b"collection_mint";
+pub const COLLECTION_AUTHORITY_SEED: &[u8] = b"collection_authority";
+// Token Metadata program seeds for metadata and master edition accounts
+pub const METADATA_SEED: &[u8] = b"metadata";
+pub const EDITION_SEED: &[u8] = b"edition";
+pub const TROPHY_DEFINITION_SEED: &[u8] = b"trophy_definition";
\ No newline at end of file
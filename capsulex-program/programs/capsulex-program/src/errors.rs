@@ -140,4 +140,229 @@ pub enum CapsuleXError {
     
     #[msg("Invalid game account provided.")]
     InvalidGameAccount,
-} 
\ No newline at end of file
+    
+    #[msg("Oracle verdict must be accompanied by a preceding Ed25519 signature verification instruction.")]
+    MissingOracleVerification,
+    
+    #[msg("Oracle key is not registered or not currently active.")]
+    InactiveOracleKey,
+    
+    #[msg("Oracle key is already registered.")]
+    OracleAlreadyRegistered,
+    
+    #[msg("Oracle key is not registered.")]
+    OracleNotRegistered,
+    
+    #[msg("Oracle registry is full.")]
+    OracleRegistryFull,
+    
+    #[msg("Invalid oracle key validity window.")]
+    InvalidOracleValidity,
+    
+    #[msg("Oracle nonce has already been used.")]
+    OracleNonceReused,
+    
+    #[msg("Oracle nonce record has not expired yet.")]
+    OracleNonceNotExpired,
+    
+    #[msg("Configured fee exceeds the maximum allowed.")]
+    FeeTooHigh,
+    
+    #[msg("Withdrawal exceeds the vault balance available above the rent-exempt minimum.")]
+    InsufficientVaultBalance,
+    
+    #[msg("Treasury recipient shares must be non-zero and sum to 10000 basis points.")]
+    InvalidTreasuryShares,
+    
+    #[msg("Too many treasury recipients.")]
+    TooManyTreasuryRecipients,
+    
+    #[msg("Recipient accounts do not match the configured treasury recipients.")]
+    TreasuryRecipientMismatch,
+    
+    #[msg("Proposed authority must differ from the current authority.")]
+    InvalidProposedAuthority,
+    
+    #[msg("Only the proposed authority can accept the vault authority.")]
+    UnauthorizedPendingAuthority,
+    
+    #[msg("Only the vault authority or a registered points issuer can perform this action.")]
+    UnauthorizedPointsIssuer,
+    
+    #[msg("Points grant exceeds the issuer's remaining points cap.")]
+    PointsCapExceeded,
+    
+    #[msg("Points issuer is not registered.")]
+    PointsIssuerNotRegistered,
+    
+    #[msg("Points issuer registry is full.")]
+    PointsIssuerRegistryFull,
+    
+    #[msg("Only the game creator or app authority can complete this game.")]
+    UnauthorizedGameCompletion,
+    
+    #[msg("Guess has already been verified.")]
+    GuessAlreadyVerified,
+    
+    #[msg("Only the game creator, vault authority or a registered oracle relayer can verify guesses.")]
+    UnauthorizedVerifier,
+    
+    #[msg("Oracle relayer is already registered.")]
+    RelayerAlreadyRegistered,
+    
+    #[msg("Oracle relayer is not registered.")]
+    RelayerNotRegistered,
+    
+    #[msg("Oracle relayer registry is full.")]
+    RelayerRegistryFull,
+    
+    #[msg("Guess does not match its commitment.")]
+    InvalidGuessCommitment,
+    
+    #[msg("Guess commitment has already been opened.")]
+    GuessAlreadyOpened,
+    
+    #[msg("Guess commitment has not been opened.")]
+    GuessNotOpened,
+    
+    #[msg("Guess commitments can only be opened after the capsule's reveal date.")]
+    GuessRevealNotStarted,
+    
+    #[msg("Guess reveal window has closed; the commitment is forfeited.")]
+    GuessRevealWindowClosed,
+    
+    #[msg("Prize split must have one non-zero share per winner placement, totalling 100%.")]
+    InvalidPrizeSplit,
+    
+    #[msg("Prize amount must be greater than zero.")]
+    InvalidPrizeAmount,
+    
+    #[msg("Prize pool holds a different asset.")]
+    PrizeMintMismatch,
+    
+    #[msg("Prize pool cannot accept more funders.")]
+    PrizeFunderLimitReached,
+    
+    #[msg("Prize has already been claimed.")]
+    PrizeAlreadyClaimed,
+    
+    #[msg("No prize refund is available for this funder.")]
+    PrizeRefundUnavailable,
+    
+    #[msg("Creator share must not exceed 100% of the guess fee.")]
+    InvalidCreatorShare,
+    
+    #[msg("No creator earnings available to claim.")]
+    NoCreatorEarnings,
+    
+    #[msg("Capsule is still within its reveal grace period.")]
+    BondGracePeriodActive,
+    
+    #[msg("Guess fee has already been refunded.")]
+    GuessAlreadyRefunded,
+    
+    #[msg("Creator bond has no balance left.")]
+    BondExhausted,
+    
+    #[msg("Bond can only be released after reveal, or once every guesser has been refunded.")]
+    BondNotReleasable,
+    
+    #[msg("Refund grace period must be positive and at most 90 days.")]
+    InvalidRefundGracePeriod,
+    
+    #[msg("Guess was not charged a fee, so there is nothing to refund.")]
+    NothingToRefund,
+    
+    #[msg("Capsule must be revealed or cancelled before its accounts can be closed.")]
+    CapsuleLifecycleNotFinished,
+    
+    #[msg("Game is still active.")]
+    GameStillActive,
+    
+    #[msg("Prize pool still holds unclaimed prizes or refunds.")]
+    PrizePoolNotSettled,
+    
+    #[msg("Close the capsule's game and release its bond before closing the capsule.")]
+    CapsuleAccountsStillOpen,
+    
+    #[msg("Release the creator bond before closing the game.")]
+    BondNotReleased,
+    
+    #[msg("Capsule content can only be edited before its reveal date and before any guess is submitted.")]
+    CapsuleContentLocked,
+    
+    #[msg("Reveal can only be rescheduled before the current reveal date.")]
+    RescheduleNotAllowed,
+    
+    #[msg("New reveal date must be later than the current one.")]
+    RevealDateNotExtended,
+    
+    #[msg("Every guesser must consent with consent_reschedule before the reveal can be rescheduled.")]
+    MissingGuesserConsent,
+    
+    #[msg("Capsule already has a creator index.")]
+    CapsuleAlreadyIndexed,
+    
+    #[msg("Decrypted content does not match the capsule's integrity hash.")]
+    ContentIntegrityMismatch,
+    
+    #[msg("Published plaintext is too long.")]
+    PlaintextTooLong,
+    
+    #[msg("A badge has already been minted for this winner.")]
+    BadgeAlreadyMinted,
+    
+    #[msg("NFT name is too long.")]
+    NftNameTooLong,
+    
+    #[msg("NFT symbol is too long.")]
+    NftSymbolTooLong,
+    
+    #[msg("The capsule NFT has already been minted.")]
+    CapsuleNftAlreadyMinted,
+    
+    #[msg("Soulbound NFTs are not enabled in the program config.")]
+    SoulboundNftsDisabled,
+    
+    #[msg("Badges and trophies must be minted as soulbound NFTs.")]
+    SoulboundNftsRequired,
+    
+    #[msg("Trophy definition is invalid.")]
+    InvalidTrophyDefinition,
+    
+    #[msg("Trophy definition is no longer active.")]
+    TrophyDefinitionInactive,
+    
+    #[msg("The trophy's season is not open.")]
+    TrophySeasonNotOpen,
+    
+    #[msg("Every trophy of this type has already been minted.")]
+    TrophySupplyExhausted,
+    
+    #[msg("Guesses can only be committed before the capsule's reveal date.")]
+    GuessCommitPhaseClosed,
+    
+    #[msg("Guesses on this capsule are still owed refunds.")]
+    GuessRefundsOutstanding,
+    
+    #[msg("This guess has already consented to the next reschedule.")]
+    RescheduleAlreadyConsented,
+    
+    #[msg("A new prize sponsor must contribute at least the minimum amount.")]
+    PrizeContributionTooSmall,
+    
+    #[msg("Guess account already has the current layout.")]
+    GuessAlreadyMigrated,
+    
+    #[msg("Legacy capsules are addressed by their reveal date and cannot be rescheduled.")]
+    LegacyCapsuleNotReschedulable,
+    
+    #[msg("Prize pool contributions have been refunded to their funders.")]
+    PrizePoolRefunded,
+    
+    #[msg("Winners can still mint their badges from this game.")]
+    WinnerBadgesUnclaimed,
+    
+    #[msg("Only the winner, the game creator or the vault authority can mint a winner badge.")]
+    UnauthorizedBadgeMinter,
+}
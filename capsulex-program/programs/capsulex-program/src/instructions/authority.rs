@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::CapsuleXError,
+    state::{AuthorityProposal, ProgramVault}
+};
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        constraint = vault.authority == authority.key() @ CapsuleXError::InvalidProgramAuthority
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    // Re-proposing replaces the pending proposal
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuthorityProposal::LEN,
+        seeds = [AUTHORITY_PROPOSAL_SEED],
+        bump
+    )]
+    pub proposal: Account<'info, AuthorityProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    /// CHECK: Proposer, receives the proposal rent back
+    #[account(mut, address = proposal.current_authority @ CapsuleXError::InvalidAccountOwner)]
+    pub current_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    #[account(
+        mut,
+        seeds = [AUTHORITY_PROPOSAL_SEED],
+        bump = proposal.bump,
+        constraint = proposal.proposed_authority == new_authority.key() @ CapsuleXError::UnauthorizedPendingAuthority,
+        close = current_authority
+    )]
+    pub proposal: Account<'info, AuthorityProposal>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityProposal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        constraint = vault.authority == authority.key() @ CapsuleXError::InvalidProgramAuthority
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    #[account(
+        mut,
+        seeds = [AUTHORITY_PROPOSAL_SEED],
+        bump = proposal.bump,
+        close = authority
+    )]
+    pub proposal: Account<'info, AuthorityProposal>,
+}
+
+pub fn propose_authority(
+    ctx: Context<ProposeAuthority>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    let current_authority = ctx.accounts.authority.key();
+    require!(
+        new_authority != current_authority && new_authority != Pubkey::default(),
+        CapsuleXError::InvalidProposedAuthority
+    );
+
+    let proposed_at = Clock::get()?.unix_timestamp;
+    let proposal = &mut ctx.accounts.proposal;
+    **proposal = AuthorityProposal {
+        current_authority,
+        proposed_authority: new_authority,
+        proposed_at,
+        bump: ctx.bumps.proposal,
+    };
+
+    emit!(AuthorityProposed {
+        current_authority,
+        proposed_authority: new_authority,
+        proposed_at,
+    });
+
+    Ok(())
+}
+
+pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let proposal = &ctx.accounts.proposal;
+
+    let previous_authority = vault.authority;
+    vault.authority = proposal.proposed_authority;
+
+    emit!(AuthorityTransferred {
+        previous_authority,
+        new_authority: vault.authority,
+        accepted_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn cancel_authority_proposal(ctx: Context<CancelAuthorityProposal>) -> Result<()> {
+    emit!(AuthorityProposalCancelled {
+        authority: ctx.accounts.authority.key(),
+        proposed_authority: ctx.accounts.proposal.proposed_authority,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub current_authority: Pubkey,
+    pub proposed_authority: Pubkey,
+    pub proposed_at: i64,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub accepted_at: i64,
+}
+
+#[event]
+pub struct AuthorityProposalCancelled {
+    pub authority: Pubkey,
+    pub proposed_authority: Pubkey,
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::CapsuleXError,
+    instructions::refund::{claim_guess_refund, ClaimGuessRefund},
+    state::{BondAccount, Capsule, CreatorEarnings, Game, ProgramConfig}
+};
+
+#[derive(Accounts)]
+pub struct ReleaseBond<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub capsule: Account<'info, Capsule>,
+
+    #[account(mut, constraint = game.capsule_id == capsule.key() @ CapsuleXError::InvalidGameAccount)]
+    pub game: Account<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [BOND_SEED, capsule.key().as_ref()],
+        bump = bond.bump,
+        has_one = creator @ CapsuleXError::UnauthorizedCreator,
+        close = creator
+    )]
+    pub bond: Account<'info, BondAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [CREATOR_EARNINGS_SEED, creator.key().as_ref()],
+        bump = creator_earnings.bump
+    )]
+    pub creator_earnings: Account<'info, CreatorEarnings>,
+}
+
+// Permissionless: anyone can push a guesser's refund once the capsule is abandoned. The payout is
+// claim_guess_refund's, so the guesser gets back the fee they paid and the guess is closed to them;
+// this entry point only insists that the creator's bond is there to pay it first
+pub fn process_bond_refund(ctx: Context<ClaimGuessRefund>) -> Result<()> {
+    let bond_info = ctx.accounts.bond.to_account_info();
+    require!(
+        bond_info.owner == &crate::ID && !bond_info.data_is_empty(),
+        CapsuleXError::BondExhausted
+    );
+    let bond = BondAccount::try_deserialize(&mut &bond_info.data.borrow()[..])?;
+    require!(bond.current_balance > 0, CapsuleXError::BondExhausted);
+
+    claim_guess_refund(ctx)
+}
+
+pub fn release_bond(ctx: Context<ReleaseBond>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let capsule = &ctx.accounts.capsule;
+    let bond = &ctx.accounts.bond;
+
+    // After an abandonment, or a reveal that came after the grace period, the creator only gets back
+    // what is left once every guess was refunded (or closed because it owed nothing)
+    let grace_period = ctx.accounts.config.refund_grace_period;
+    let guessers_refunded = (capsule.is_revealed || capsule.is_abandoned(now, grace_period))
+        && ctx.accounts.game.all_guesses_settled();
+    require!(
+        capsule.revealed_on_time(grace_period) || guessers_refunded,
+        CapsuleXError::BondNotReleasable
+    );
+
+    // Creator shares of guesses the bond refunded stayed locked; with every guess settled they are
+    // the creator's to claim
+    let game = &mut ctx.accounts.game;
+    let unlocked = game.creator_fees_held;
+    if unlocked > 0 {
+        game.release_creator_fees(unlocked)?;
+        ctx.accounts.creator_earnings.unlock(unlocked)?;
+    }
+
+    emit!(BondReleased {
+        capsule_id: capsule.key(),
+        creator: bond.creator,
+        amount: bond.current_balance,
+        refunds_paid: bond.refunds_paid,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BondReleased {
+    pub capsule_id: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64, // Bond balance returned, excluding the account rent
+    pub refunds_paid: u32,
+}
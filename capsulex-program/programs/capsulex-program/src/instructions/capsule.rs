@@ -1,10 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token};
-use anchor_lang::solana_program::rent::Rent;
+use anchor_lang::solana_program::{hash::hashv, rent::Rent};
 use crate::{
     constants::*, 
     errors::CapsuleXError, 
-    state::{Capsule, ContentStorage, ProgramVault, Game}
+    instructions::leaderboard::update_leaderboard_capsule_created,
+    state::{BondAccount, Capsule, ContentStorage, CreatorEarnings, CreatorProfile, LeaderboardEntry, LegacyCapsuleLink, ProgramVault, PublishedPlaintext, ProgramConfig, Game, Guess}
 };
 
 // Light on-chain validation helpers (avoid expensive char iteration)
@@ -20,17 +21,72 @@ fn is_valid_url_format(url: &str) -> bool {
     url.len() >= 12 && url.len() <= 500 && url.starts_with("https://")
 }
 
+// Shared by create_capsule and update_capsule_content
+fn validate_content(
+    encrypted_content: &str,
+    content_storage: &ContentStorage,
+    content_integrity_hash: &str,
+) -> Result<()> {
+    // Validate content based on storage type
+    match content_storage {
+        ContentStorage::Text => {
+            require!(
+                encrypted_content.len() <= MAX_ONCHAIN_CONTENT_LENGTH,
+                CapsuleXError::ContentHashTooLong
+            );
+        },
+        ContentStorage::Document { cid } => {
+            require!(is_valid_ipfs_cid_format(cid), CapsuleXError::InvalidCID);
+        },
+        ContentStorage::SocialArchive { original_url, archived_cid, platform, content_hash, .. } => {
+            require!(is_valid_url_format(original_url), CapsuleXError::InvalidURL);
+            require!(is_valid_ipfs_cid_format(archived_cid), CapsuleXError::InvalidCID);
+            require!(platform.len() <= 20, CapsuleXError::UnsupportedPlatform);  // Basic length check
+            require!(is_valid_sha256_length(content_hash), CapsuleXError::InvalidContentHash);
+        },
+        ContentStorage::MediaBundle { primary_cid, attachments, manifest_cid, total_size_bytes } => {
+            require!(is_valid_ipfs_cid_format(primary_cid), CapsuleXError::InvalidCID);
+            require!(is_valid_ipfs_cid_format(manifest_cid), CapsuleXError::InvalidCID);
+            require!(attachments.len() <= 50, CapsuleXError::TooManyAttachments);
+            // Skip individual attachment validation to save compute (client validates)
+            require!(*total_size_bytes <= 1_000_000_000, CapsuleXError::ContentTooLarge);
+        },
+        ContentStorage::ExternalWithBackup { original_url, backup_cid, verification_hash } => {
+            require!(is_valid_url_format(original_url), CapsuleXError::InvalidURL);
+            require!(is_valid_ipfs_cid_format(backup_cid), CapsuleXError::InvalidCID);
+            require!(is_valid_sha256_length(verification_hash), CapsuleXError::InvalidContentHash);
+        }
+    }
+    
+    // Validate content integrity hash (length only - client validates hex format)
+    require!(
+        is_valid_sha256_length(content_integrity_hash),
+        CapsuleXError::InvalidContentHash
+    );
+    
+    Ok(())
+}
+
 #[derive(Accounts)]
-#[instruction(encrypted_content: String, content_storage: ContentStorage, content_integrity_hash: String, reveal_date: i64, is_gamified: bool)]
 pub struct CreateCapsule<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
     
+    // Numbers the creator's capsules; created with their first capsule
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = CreatorProfile::LEN,
+        seeds = [CREATOR_PROFILE_SEED, creator.key().as_ref()],
+        bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+    
     #[account(
         init,
         payer = creator,
         space = Capsule::LEN,
-        seeds = [CAPSULE_SEED, creator.key().as_ref(), &reveal_date.to_le_bytes()],
+        seeds = [CAPSULE_SEED, creator.key().as_ref(), &creator_profile.capsule_count.to_le_bytes()],
         bump
     )]
     pub capsule: Account<'info, Capsule>,
@@ -52,30 +108,235 @@ pub struct CreateCapsule<'info> {
     )]
     pub vault: Account<'info, ProgramVault>,
     
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    // Receives the creator's share of guess fees across all of their capsules
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = CreatorEarnings::LEN,
+        seeds = [CREATOR_EARNINGS_SEED, creator.key().as_ref()],
+        bump
+    )]
+    pub creator_earnings: Account<'info, CreatorEarnings>,
+    
+    // Counts the creator's capsules for trophy criteria; created with their first capsule
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = LeaderboardEntry::LEN,
+        seeds = [LEADERBOARD_SEED, creator.key().as_ref()],
+        bump
+    )]
+    pub creator_leaderboard: Account<'info, LeaderboardEntry>,
+    
     // Game account - only initialized when is_gamified = true
     /// CHECK: This account is only used when is_gamified = true. We validate this in the instruction logic.
     #[account(mut)]
     pub game: UncheckedAccount<'info>,
     
+    // Creator bond - only created when is_gamified = true
+    /// CHECK: PDA is enforced by seeds; the account is created in the instruction logic for gamified capsules.
+    #[account(
+        mut,
+        seeds = [BOND_SEED, capsule.key().as_ref()],
+        bump
+    )]
+    pub bond: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-#[instruction(reveal_date: i64)]
 pub struct RevealCapsule<'info> {
     #[account(mut)]
     pub revealer: Signer<'info>,
     
     #[account(
         mut,
-        seeds = [CAPSULE_SEED, capsule.creator.as_ref(), &reveal_date.to_le_bytes()],
+        seeds = [CAPSULE_SEED, capsule.creator.as_ref(), &capsule.seed_bytes()],
         bump = capsule.bump,
         constraint = capsule.creator == revealer.key() || 
-                     revealer.key() == crate::constants::APP_AUTHORITY @ CapsuleXError::UnauthorizedRevealer
+                     revealer.key() == config.app_authority @ CapsuleXError::UnauthorizedRevealer
+    )]
+    pub capsule: Account<'info, Capsule>,
+    
+    /// CHECK: Game for the capsule; only gamified capsules have one
+    #[account(
+        mut,
+        seeds = [GAME_SEED, capsule.key().as_ref()],
+        bump
+    )]
+    pub game: UncheckedAccount<'info>,
+    
+    /// CHECK: Creator's earnings; only exists once one of their capsules received a guess
+    #[account(
+        mut,
+        seeds = [CREATOR_EARNINGS_SEED, capsule.creator.as_ref()],
+        bump
+    )]
+    pub creator_earnings: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+// Gives a capsule created before CreatorProfile existed an index, linked from the address that index would have
+#[derive(Accounts)]
+pub struct RegisterLegacyCapsule<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = CreatorProfile::LEN,
+        seeds = [CREATOR_PROFILE_SEED, creator.key().as_ref()],
+        bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+    
+    #[account(
+        mut,
+        seeds = [CAPSULE_SEED, capsule.creator.as_ref(), &capsule.seed_bytes()],
+        bump = capsule.bump,
+        has_one = creator @ CapsuleXError::UnauthorizedCreator,
+        constraint = capsule.is_legacy() && capsule.legacy_index.is_none() @ CapsuleXError::CapsuleAlreadyIndexed
+    )]
+    pub capsule: Account<'info, Capsule>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = LegacyCapsuleLink::LEN,
+        seeds = [CAPSULE_SEED, creator.key().as_ref(), &creator_profile.capsule_count.to_le_bytes()],
+        bump
+    )]
+    pub capsule_link: Account<'info, LegacyCapsuleLink>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelCapsule<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        has_one = creator @ CapsuleXError::UnauthorizedCreator,
+        constraint = capsule.is_active @ CapsuleXError::CapsuleNotActive,
+        constraint = !capsule.is_revealed @ CapsuleXError::CapsuleAlreadyRevealed
+    )]
+    pub capsule: Account<'info, Capsule>,
+    
+    /// CHECK: Game PDA for the capsule; only gamified capsules have one
+    #[account(
+        mut,
+        seeds = [GAME_SEED, capsule.key().as_ref()],
+        bump
+    )]
+    pub game: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCapsuleContent<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        has_one = creator @ CapsuleXError::UnauthorizedCreator,
+        constraint = capsule.is_active @ CapsuleXError::CapsuleNotActive,
+        constraint = !capsule.is_revealed @ CapsuleXError::CapsuleAlreadyRevealed
+    )]
+    pub capsule: Account<'info, Capsule>,
+    
+    /// CHECK: Game PDA for the capsule; only gamified capsules have one
+    #[account(
+        seeds = [GAME_SEED, capsule.key().as_ref()],
+        bump
+    )]
+    pub game: UncheckedAccount<'info>,
+}
+
+// Once guesses exist, every guess must have consented through consent_reschedule
+#[derive(Accounts)]
+pub struct RescheduleReveal<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [CAPSULE_SEED, capsule.creator.as_ref(), &capsule.seed_bytes()],
+        bump = capsule.bump,
+        has_one = creator @ CapsuleXError::UnauthorizedCreator,
+        constraint = capsule.is_active @ CapsuleXError::CapsuleNotActive,
+        constraint = !capsule.is_revealed @ CapsuleXError::CapsuleAlreadyRevealed,
+        constraint = !capsule.is_legacy() @ CapsuleXError::LegacyCapsuleNotReschedulable
     )]
     pub capsule: Account<'info, Capsule>,
+    
+    /// CHECK: Game PDA for the capsule; only gamified capsules have one
+    #[account(
+        mut,
+        seeds = [GAME_SEED, capsule.key().as_ref()],
+        bump
+    )]
+    pub game: UncheckedAccount<'info>,
+}
+
+// Each guesser signs their own consent, so a reschedule never needs every guesser in one transaction
+#[derive(Accounts)]
+pub struct ConsentReschedule<'info> {
+    pub guesser: Signer<'info>,
+    
+    #[account(
+        constraint = capsule.key() == game.capsule_id @ CapsuleXError::InvalidGameAccount,
+        constraint = capsule.is_active @ CapsuleXError::CapsuleNotActive,
+        constraint = !capsule.is_revealed @ CapsuleXError::CapsuleAlreadyRevealed
+    )]
+    pub capsule: Account<'info, Capsule>,
+    
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    
+    #[account(
+        mut,
+        has_one = guesser @ CapsuleXError::InvalidAccountOwner,
+        constraint = guess.game_id == game.key() @ CapsuleXError::InvalidGuessId
+    )]
+    pub guess: Account<'info, Guess>,
+}
+
+// Anyone can publish the plaintext of a revealed capsule; the integrity hash proves it is genuine
+#[derive(Accounts)]
+pub struct PublishPlaintext<'info> {
+    #[account(mut)]
+    pub publisher: Signer<'info>,
+    
+    #[account(
+        constraint = capsule.is_revealed @ CapsuleXError::CapsuleNotReady
+    )]
+    pub capsule: Account<'info, Capsule>,
+    
+    #[account(
+        init,
+        payer = publisher,
+        space = PublishedPlaintext::LEN,
+        seeds = [PUBLISHED_PLAINTEXT_SEED, capsule.key().as_ref()],
+        bump
+    )]
+    pub published_plaintext: Account<'info, PublishedPlaintext>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 pub fn create_capsule(
@@ -89,42 +350,7 @@ pub fn create_capsule(
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp;
     
-    // Validate content based on storage type
-    match &content_storage {
-        ContentStorage::Text => {
-            require!(
-                encrypted_content.len() <= MAX_ONCHAIN_CONTENT_LENGTH,
-                CapsuleXError::ContentHashTooLong
-            );
-        },
-        ContentStorage::Document { cid } => {
-            require!(is_valid_ipfs_cid_format(cid), CapsuleXError::InvalidCID);
-        },
-        ContentStorage::SocialArchive { original_url, archived_cid, platform, content_hash, .. } => {
-            require!(is_valid_url_format(original_url), CapsuleXError::InvalidURL);
-            require!(is_valid_ipfs_cid_format(archived_cid), CapsuleXError::InvalidCID);
-            require!(platform.len() <= 20, CapsuleXError::UnsupportedPlatform);  // Basic length check
-            require!(is_valid_sha256_length(content_hash), CapsuleXError::InvalidContentHash);
-        },
-        ContentStorage::MediaBundle { primary_cid, attachments, manifest_cid, total_size_bytes } => {
-            require!(is_valid_ipfs_cid_format(primary_cid), CapsuleXError::InvalidCID);
-            require!(is_valid_ipfs_cid_format(manifest_cid), CapsuleXError::InvalidCID);
-            require!(attachments.len() <= 50, CapsuleXError::TooManyAttachments);
-            // Skip individual attachment validation to save compute (client validates)
-            require!(*total_size_bytes <= 1_000_000_000, CapsuleXError::ContentTooLarge);
-        },
-        ContentStorage::ExternalWithBackup { original_url, backup_cid, verification_hash } => {
-            require!(is_valid_url_format(original_url), CapsuleXError::InvalidURL);
-            require!(is_valid_ipfs_cid_format(backup_cid), CapsuleXError::InvalidCID);
-            require!(is_valid_sha256_length(verification_hash), CapsuleXError::InvalidContentHash);
-        }
-    }
-    
-    // Validate content integrity hash (length only - client validates hex format)
-    require!(
-        is_valid_sha256_length(&content_integrity_hash),
-        CapsuleXError::InvalidContentHash
-    );
+    validate_content(&encrypted_content, &content_storage, &content_integrity_hash)?;
     
     // Validate reveal date
     require!(
@@ -134,7 +360,7 @@ pub fn create_capsule(
     );
     
     // Collect capsule creation fee
-    let fee_amount = CAPSULE_CREATION_FEE;
+    let fee_amount = ctx.accounts.config.capsule_creation_fee;
     
     let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
         &ctx.accounts.creator.key(),
@@ -153,6 +379,25 @@ pub fn create_capsule(
     
     ctx.accounts.vault.add_fees(fee_amount);
     
+    let creator_earnings = &mut ctx.accounts.creator_earnings;
+    if creator_earnings.creator == Pubkey::default() {
+        creator_earnings.creator = ctx.accounts.creator.key();
+        creator_earnings.bump = ctx.bumps.creator_earnings;
+    }
+    
+    let creator_profile = &mut ctx.accounts.creator_profile;
+    if creator_profile.creator == Pubkey::default() {
+        creator_profile.creator = ctx.accounts.creator.key();
+        creator_profile.bump = ctx.bumps.creator_profile;
+    }
+    let capsule_index = creator_profile.next_index();
+    
+    let creator_leaderboard = &mut ctx.accounts.creator_leaderboard;
+    if creator_leaderboard.user == Pubkey::default() {
+        **creator_leaderboard = LeaderboardEntry::new(ctx.accounts.creator.key(), ctx.bumps.creator_leaderboard);
+    }
+    update_leaderboard_capsule_created(creator_leaderboard)?;
+    
     // Initialize capsule (content is already encrypted on device)
     let capsule = &mut ctx.accounts.capsule;
     **capsule = Capsule::new(
@@ -164,6 +409,7 @@ pub fn create_capsule(
         reveal_date,
         is_gamified,
         ctx.bumps.capsule,
+        capsule_index,
     );
     
     // If gamified, initialize the game account
@@ -221,6 +467,47 @@ pub fn create_capsule(
         
         // Serialize the game data
         game.try_serialize(&mut game_data.as_mut())?;
+        
+        // Post the creator bond: one service fee per possible guess, refunded to guessers if the capsule is abandoned
+        let bond_account_info = ctx.accounts.bond.to_account_info();
+        let bond_bump = ctx.bumps.bond;
+        let bond = BondAccount::new(
+            capsule.key(),
+            ctx.accounts.creator.key(),
+            ctx.accounts.config.service_fee,
+            game.max_guesses,
+            current_time,
+            bond_bump,
+        );
+        
+        anchor_lang::solana_program::program::invoke_signed(
+            &anchor_lang::solana_program::system_instruction::create_account(
+                &ctx.accounts.creator.key(),
+                &bond_account_info.key(),
+                rent.minimum_balance(BondAccount::LEN) + bond.initial_deposit,
+                BondAccount::LEN as u64,
+                ctx.program_id,
+            ),
+            &[
+                ctx.accounts.creator.to_account_info(),
+                bond_account_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&[
+                BOND_SEED,
+                &capsule.key().to_bytes(),
+                &[bond_bump],
+            ]],
+        )?;
+        
+        bond.try_serialize(&mut bond_account_info.try_borrow_mut_data()?.as_mut())?;
+        
+        emit!(BondPosted {
+            capsule_id: capsule.key(),
+            creator: ctx.accounts.creator.key(),
+            amount: bond.initial_deposit,
+            max_guesses: game.max_guesses,
+        });
     }
     
     emit!(CapsuleCreated {
@@ -231,12 +518,14 @@ pub fn create_capsule(
         is_gamified,
         content_storage: capsule.content_storage.clone(),
         fee_amount, // Actual fee collected
+        capsule_index,
     });
     
     Ok(())
 }
 
-pub fn reveal_capsule(ctx: Context<RevealCapsule>, _reveal_date: i64) -> Result<()> {
+pub fn reveal_capsule(ctx: Context<RevealCapsule>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
     let capsule = &mut ctx.accounts.capsule;
     
     // Check if already revealed
@@ -245,14 +534,226 @@ pub fn reveal_capsule(ctx: Context<RevealCapsule>, _reveal_date: i64) -> Result<
     // Check if capsule can be revealed (time check)
     require!(capsule.can_reveal(), CapsuleXError::CapsuleNotReady);
     
+    // Guess fees can no longer be refunded once the capsule is revealed, so the vault may pay them out.
+    // Past the grace period guessers are owed refunds, and a late reveal must not take that away.
+    let game_info = ctx.accounts.game.to_account_info();
+    if game_info.owner == &crate::ID && !game_info.data_is_empty() {
+        let mut game = Game::try_deserialize(&mut &game_info.data.borrow()[..])?;
+        require!(
+            !capsule.is_abandoned(now, ctx.accounts.config.refund_grace_period) || game.all_guesses_settled(),
+            CapsuleXError::GuessRefundsOutstanding
+        );
+        let released = game.vault_fees_held;
+        game.release_vault_fees(released)?;
+        ctx.accounts.config.release_refundable(released)?;
+        
+        // Likewise the creator's share of those fees becomes claimable
+        let earnings_info = ctx.accounts.creator_earnings.to_account_info();
+        let unlocked = game.creator_fees_held;
+        game.release_creator_fees(unlocked)?;
+        if unlocked > 0 && earnings_info.owner == &crate::ID && !earnings_info.data_is_empty() {
+            let mut creator_earnings = CreatorEarnings::try_deserialize(&mut &earnings_info.data.borrow()[..])?;
+            creator_earnings.unlock(unlocked)?;
+            creator_earnings.try_serialize(&mut &mut earnings_info.data.borrow_mut()[..])?;
+        }
+        game.try_serialize(&mut &mut game_info.data.borrow_mut()[..])?;
+    }
+    
     // Reveal the capsule (no key management needed - done on device)
-    capsule.reveal();
+    capsule.reveal(now);
     
     emit!(CapsuleRevealed {
         capsule_id: capsule.key(),
         creator: capsule.creator,
         revealer: ctx.accounts.revealer.key(),
-        reveal_time: Clock::get()?.unix_timestamp,
+        reveal_time: now,
+    });
+    
+    Ok(())
+}
+
+
+pub fn register_legacy_capsule(ctx: Context<RegisterLegacyCapsule>) -> Result<()> {
+    let creator_profile = &mut ctx.accounts.creator_profile;
+    if creator_profile.creator == Pubkey::default() {
+        creator_profile.creator = ctx.accounts.creator.key();
+        creator_profile.bump = ctx.bumps.creator_profile;
+    }
+    let capsule_index = creator_profile.next_index();
+    
+    // The capsule keeps its reveal-date address; only the link lives at the index address
+    let capsule = &mut ctx.accounts.capsule;
+    capsule.legacy_index = Some(capsule_index);
+    
+    let capsule_link = &mut ctx.accounts.capsule_link;
+    capsule_link.creator = capsule.creator;
+    capsule_link.capsule = capsule.key();
+    capsule_link.capsule_index = capsule_index;
+    capsule_link.bump = ctx.bumps.capsule_link;
+    
+    emit!(LegacyCapsuleRegistered {
+        capsule_id: capsule.key(),
+        creator: capsule.creator,
+        capsule_index,
+        link: capsule_link.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn cancel_capsule(ctx: Context<CancelCapsule>) -> Result<()> {
+    let capsule = &mut ctx.accounts.capsule;
+    capsule.is_active = false;
+    
+    // End the game so no more guesses come in; existing guesses become refundable
+    let game_info = ctx.accounts.game.to_account_info();
+    let mut guesses_to_refund = 0;
+    if !game_info.data_is_empty() {
+        let mut game = Game::try_deserialize(&mut &game_info.data.borrow()[..])?;
+        game.end_game();
+        guesses_to_refund = game.current_guesses;
+        game.try_serialize(&mut &mut game_info.data.borrow_mut()[..])?;
+    }
+    
+    emit!(CapsuleCancelled {
+        capsule_id: capsule.key(),
+        creator: capsule.creator,
+        cancelled_at: Clock::get()?.unix_timestamp,
+        guesses_to_refund,
+    });
+    
+    Ok(())
+}
+
+pub fn update_capsule_content(
+    ctx: Context<UpdateCapsuleContent>,
+    encrypted_content: String,
+    content_storage: ContentStorage,
+    content_integrity_hash: String,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let capsule = &mut ctx.accounts.capsule;
+    require!(now < capsule.reveal_date, CapsuleXError::CapsuleContentLocked);
+    
+    // Guessers must all play against the same content
+    let game_info = ctx.accounts.game.to_account_info();
+    if !game_info.data_is_empty() {
+        let game = Game::try_deserialize(&mut &game_info.data.borrow()[..])?;
+        require!(game.current_guesses == 0, CapsuleXError::CapsuleContentLocked);
+    }
+    
+    validate_content(&encrypted_content, &content_storage, &content_integrity_hash)?;
+    
+    capsule.encrypted_content = encrypted_content;
+    capsule.content_storage = content_storage;
+    capsule.content_integrity_hash = content_integrity_hash;
+    
+    emit!(CapsuleContentUpdated {
+        capsule_id: capsule.key(),
+        creator: capsule.creator,
+        content_storage: capsule.content_storage.clone(),
+        content_integrity_hash: capsule.content_integrity_hash.clone(),
+        updated_at: now,
+    });
+    
+    Ok(())
+}
+
+pub fn reschedule_reveal(ctx: Context<RescheduleReveal>, new_reveal_date: i64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let capsule = &ctx.accounts.capsule;
+    let old_reveal_date = capsule.reveal_date;
+    
+    // Only a pending reveal can move, and only further out
+    require!(now < old_reveal_date, CapsuleXError::RescheduleNotAllowed);
+    require!(new_reveal_date > old_reveal_date, CapsuleXError::RevealDateNotExtended);
+    require!(
+        new_reveal_date <= now + MAX_REVEAL_DELAY,
+        CapsuleXError::InvalidRevealDate
+    );
+    
+    // Once guesses exist, every one of them needs its guesser's consent; consents are used up here
+    let game_info = ctx.accounts.game.to_account_info();
+    let mut consenting_guesses = 0u32;
+    if !game_info.data_is_empty() {
+        let mut game = Game::try_deserialize(&mut &game_info.data.borrow()[..])?;
+        require!(
+            game.reschedule_consents == game.current_guesses,
+            CapsuleXError::MissingGuesserConsent
+        );
+        
+        consenting_guesses = game.reschedule_consents;
+        game.apply_reschedule();
+        game.try_serialize(&mut &mut game_info.data.borrow_mut()[..])?;
+    }
+    
+    let capsule = &mut ctx.accounts.capsule;
+    capsule.reveal_date = new_reveal_date;
+    
+    emit!(RevealRescheduled {
+        capsule_id: capsule.key(),
+        creator: capsule.creator,
+        old_reveal_date,
+        new_reveal_date,
+        consenting_guesses,
+    });
+    
+    Ok(())
+}
+
+pub fn consent_reschedule(ctx: Context<ConsentReschedule>) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp < ctx.accounts.capsule.reveal_date,
+        CapsuleXError::RescheduleNotAllowed
+    );
+    
+    let game = &mut ctx.accounts.game;
+    let guess = &mut ctx.accounts.guess;
+    let next_reschedule = game.next_reschedule();
+    require!(
+        guess.reschedule_consent != next_reschedule,
+        CapsuleXError::RescheduleAlreadyConsented
+    );
+    
+    guess.reschedule_consent = next_reschedule;
+    game.reschedule_consents += 1;
+    
+    emit!(RescheduleConsented {
+        capsule_id: ctx.accounts.capsule.key(),
+        guess_id: guess.key(),
+        guesser: guess.guesser,
+        consents: game.reschedule_consents,
+        required_consents: game.current_guesses,
+    });
+    
+    Ok(())
+}
+
+pub fn publish_plaintext(ctx: Context<PublishPlaintext>, plaintext: String) -> Result<()> {
+    require!(
+        plaintext.len() <= MAX_PUBLISHED_PLAINTEXT_LENGTH,
+        CapsuleXError::PlaintextTooLong
+    );
+    
+    let capsule = &ctx.accounts.capsule;
+    require!(
+        capsule.matches_integrity_hash(&plaintext),
+        CapsuleXError::ContentIntegrityMismatch
+    );
+    
+    let published_plaintext = &mut ctx.accounts.published_plaintext;
+    published_plaintext.capsule = capsule.key();
+    published_plaintext.publisher = ctx.accounts.publisher.key();
+    published_plaintext.content_hash = hashv(&[plaintext.as_bytes()]).to_bytes();
+    published_plaintext.plaintext = plaintext;
+    published_plaintext.published_at = Clock::get()?.unix_timestamp;
+    published_plaintext.bump = ctx.bumps.published_plaintext;
+    
+    emit!(PlaintextPublished {
+        capsule_id: capsule.key(),
+        publisher: published_plaintext.publisher,
+        plaintext: published_plaintext.plaintext.clone(),
+        published_at: published_plaintext.published_at,
     });
     
     Ok(())
@@ -268,6 +769,7 @@ pub struct CapsuleCreated {
     pub is_gamified: bool,
     pub content_storage: ContentStorage,
     pub fee_amount: u64,
+    pub capsule_index: u64,
 }
 
 #[event]
@@ -276,4 +778,63 @@ pub struct CapsuleRevealed {
     pub creator: Pubkey,
     pub revealer: Pubkey,
     pub reveal_time: i64,
-} 
\ No newline at end of file
+} 
+
+#[event]
+pub struct BondPosted {
+    pub capsule_id: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub max_guesses: u32,
+}
+
+#[event]
+pub struct CapsuleCancelled {
+    pub capsule_id: Pubkey,
+    pub creator: Pubkey,
+    pub cancelled_at: i64,
+    pub guesses_to_refund: u32,
+}
+
+#[event]
+pub struct CapsuleContentUpdated {
+    pub capsule_id: Pubkey,
+    pub creator: Pubkey,
+    pub content_storage: ContentStorage,
+    pub content_integrity_hash: String,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct RevealRescheduled {
+    pub capsule_id: Pubkey,
+    pub creator: Pubkey,
+    pub old_reveal_date: i64,
+    pub new_reveal_date: i64,
+    pub consenting_guesses: u32, // Guesses whose guessers consented to the extension
+}
+
+#[event]
+pub struct RescheduleConsented {
+    pub capsule_id: Pubkey,
+    pub guess_id: Pubkey,
+    pub guesser: Pubkey,
+    pub consents: u32,
+    pub required_consents: u32, // Guesses on the game; the reveal can move once consents reach it
+}
+
+#[event]
+pub struct LegacyCapsuleRegistered {
+    pub capsule_id: Pubkey,
+    pub creator: Pubkey,
+    pub capsule_index: u64,
+    pub link: Pubkey, // LegacyCapsuleLink at the index-derived capsule address
+}
+
+#[event]
+pub struct PlaintextPublished {
+    pub capsule_id: Pubkey,
+    pub publisher: Pubkey,
+    pub plaintext: String,
+    pub published_at: i64,
+}
@@ -0,0 +1,221 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::CapsuleXError,
+    state::{Capsule, Game, Guess, PrizePool}
+};
+
+// Accounts are closed back to whoever paid their rent: guesses to the guesser,
+// games and capsules to the creator.
+
+#[derive(Accounts)]
+pub struct CloseGuess<'info> {
+    #[account(mut)]
+    pub guesser: Signer<'info>,
+
+    #[account(
+        mut,
+        close = guesser,
+        has_one = guesser @ CapsuleXError::InvalidAccountOwner
+    )]
+    pub guess: Account<'info, Guess>,
+
+    /// CHECK: The guess's game; may already be closed, otherwise it must be inactive
+    #[account(mut, address = guess.game_id @ CapsuleXError::InvalidGuessId)]
+    pub game: UncheckedAccount<'info>,
+
+    /// CHECK: The game's capsule; checked against game.capsule_id while the game is open
+    pub capsule: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseGame<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        constraint = capsule.key() == game.capsule_id @ CapsuleXError::InvalidGameAccount,
+        constraint = capsule.is_revealed || !capsule.is_active @ CapsuleXError::CapsuleLifecycleNotFinished
+    )]
+    pub capsule: Account<'info, Capsule>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [GAME_SEED, capsule.key().as_ref()],
+        bump = game.bump,
+        has_one = creator @ CapsuleXError::UnauthorizedCreator,
+        constraint = !game.is_active @ CapsuleXError::GameStillActive
+    )]
+    pub game: Account<'info, Game>,
+
+    /// CHECK: Prize pool PDA for the game; only inspected when one was opened
+    #[account(
+        seeds = [PRIZE_POOL_SEED, game.key().as_ref()],
+        bump
+    )]
+    pub prize_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Releasing the bond reads the game, so it must be released first
+    #[account(
+        seeds = [BOND_SEED, capsule.key().as_ref()],
+        bump
+    )]
+    pub bond: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseCapsule<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ CapsuleXError::UnauthorizedCreator,
+        constraint = capsule.is_revealed || !capsule.is_active @ CapsuleXError::CapsuleLifecycleNotFinished
+    )]
+    pub capsule: Account<'info, Capsule>,
+
+    /// CHECK: Must be closed before the capsule so its winners are not orphaned
+    #[account(
+        seeds = [GAME_SEED, capsule.key().as_ref()],
+        bump
+    )]
+    pub game: UncheckedAccount<'info>,
+
+    /// CHECK: Must be released before the capsule so the bond cannot be stranded
+    #[account(
+        seeds = [BOND_SEED, capsule.key().as_ref()],
+        bump
+    )]
+    pub bond: UncheckedAccount<'info>,
+}
+
+pub fn close_guess(ctx: Context<CloseGuess>) -> Result<()> {
+    let game_info = ctx.accounts.game.to_account_info();
+    if !game_info.data_is_empty() {
+        require!(game_info.owner == &crate::ID, CapsuleXError::InvalidGameAccount);
+        let mut game = Game::try_deserialize(&mut &game_info.data.borrow()[..])?;
+        require!(!game.is_active, CapsuleXError::GameStillActive);
+
+        // Refunded guesses were already counted; any other paid guess on an unrevealed
+        // capsule would forfeit its refund and leave the game unclosable
+        let guess = &ctx.accounts.guess;
+        if !guess.is_refunded {
+            let capsule_info = ctx.accounts.capsule.to_account_info();
+            require!(capsule_info.key() == game.capsule_id, CapsuleXError::InvalidGameAccount);
+            let capsule = Capsule::try_deserialize(&mut &capsule_info.data.borrow()[..])?;
+            require!(
+                capsule.is_revealed || !guess.is_paid,
+                CapsuleXError::GuessRefundsOutstanding
+            );
+
+            game.settle_guess();
+            game.try_serialize(&mut &mut game_info.data.borrow_mut()[..])?;
+        }
+    }
+
+    let guess = &ctx.accounts.guess;
+    emit!(GuessClosed {
+        guess_id: guess.key(),
+        game_id: guess.game_id,
+        guesser: guess.guesser,
+        guess_content: guess.guess_content.clone(),
+        is_correct: guess.is_correct,
+        is_verified: guess.is_verified,
+        verified_at: guess.verified_at,
+    });
+
+    Ok(())
+}
+
+pub fn close_game(ctx: Context<CloseGame>) -> Result<()> {
+    // Refunds are claimed against the game, and the capsule can only close after it
+    require!(
+        ctx.accounts.capsule.is_revealed || ctx.accounts.game.all_guesses_settled(),
+        CapsuleXError::GuessRefundsOutstanding
+    );
+    require!(ctx.accounts.bond.data_is_empty(), CapsuleXError::BondNotReleased);
+
+    // Winners claim prizes against the game, so every prize must be paid out or refunded first
+    let prize_pool_info = ctx.accounts.prize_pool.to_account_info();
+    if !prize_pool_info.data_is_empty() {
+        let prize_pool = PrizePool::try_deserialize(&mut &prize_pool_info.data.borrow()[..])?;
+        require!(prize_pool.is_settled(), CapsuleXError::PrizePoolNotSettled);
+    }
+
+    // Badges are minted against the game, so winners get the claim window to mint theirs;
+    // the revealed_at fallback covers capsules revealed before it was recorded
+    let game = &ctx.accounts.game;
+    let unbadged_placements = game.unbadged_placements();
+    let capsule = &ctx.accounts.capsule;
+    let claim_window_end = capsule.revealed_at.max(capsule.reveal_date) + BADGE_CLAIM_WINDOW;
+    require!(
+        unbadged_placements.is_empty() || Clock::get()?.unix_timestamp > claim_window_end,
+        CapsuleXError::WinnerBadgesUnclaimed
+    );
+
+    emit!(GameClosed {
+        game_id: game.key(),
+        capsule_id: game.capsule_id,
+        creator: game.creator,
+        winners: game.winners.clone(),
+        unbadged_placements,
+        total_guesses: game.current_guesses,
+        total_participants: game.total_participants,
+    });
+
+    Ok(())
+}
+
+pub fn close_capsule(ctx: Context<CloseCapsule>) -> Result<()> {
+    require!(
+        ctx.accounts.game.data_is_empty() && ctx.accounts.bond.data_is_empty(),
+        CapsuleXError::CapsuleAccountsStillOpen
+    );
+
+    let capsule = &ctx.accounts.capsule;
+    emit!(CapsuleClosed {
+        capsule_id: capsule.key(),
+        creator: capsule.creator,
+        nft_mint: capsule.nft_mint,
+        content_integrity_hash: capsule.content_integrity_hash.clone(),
+        reveal_date: capsule.reveal_date,
+        is_revealed: capsule.is_revealed,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct GuessClosed {
+    pub guess_id: Pubkey,
+    pub game_id: Pubkey,
+    pub guesser: Pubkey,
+    pub guess_content: String,
+    pub is_correct: bool,
+    pub is_verified: bool,
+    pub verified_at: i64,
+}
+
+#[event]
+pub struct GameClosed {
+    pub game_id: Pubkey,
+    pub capsule_id: Pubkey,
+    pub creator: Pubkey,
+    pub winners: Vec<Pubkey>, // Final winner list, in placement order
+    pub unbadged_placements: Vec<u8>, // Placements whose winner never minted a badge
+    pub total_guesses: u32,
+    pub total_participants: u32,
+}
+
+#[event]
+pub struct CapsuleClosed {
+    pub capsule_id: Pubkey,
+    pub creator: Pubkey,
+    pub nft_mint: Pubkey,
+    pub content_integrity_hash: String,
+    pub reveal_date: i64,
+    pub is_revealed: bool,
+}
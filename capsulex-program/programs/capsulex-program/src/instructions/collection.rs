@@ -0,0 +1,272 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::{
+    create_master_edition_v3, create_metadata_accounts_v3,
+    mpl_token_metadata::types::{Collection, CollectionDetails, DataV2},
+    verify_sized_collection_item, CreateMasterEditionV3, CreateMetadataAccountsV3, Metadata,
+    VerifySizedCollectionItem,
+};
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+use crate::{
+    constants::*,
+    errors::CapsuleXError,
+    state::{NftKind, ProgramVault}
+};
+
+// One sized collection per NFT kind, owned by the program's collection authority PDA
+#[derive(Accounts)]
+#[instruction(kind: NftKind)]
+pub struct InitializeNftCollection<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        constraint = vault.authority == authority.key() @ CapsuleXError::InvalidProgramAuthority
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    /// CHECK: PDA with no data; update authority of every collection and signs item verification
+    #[account(
+        seeds = [COLLECTION_AUTHORITY_SEED],
+        bump
+    )]
+    pub collection_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = 0,
+        mint::authority = collection_authority,
+        mint::freeze_authority = collection_authority,
+        seeds = [COLLECTION_MINT_SEED, kind.seed()],
+        bump
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = collection_mint,
+        associated_token::authority = collection_authority
+    )]
+    pub collection_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Metadata PDA of the collection mint, created by the Token Metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), collection_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Master edition PDA of the collection mint, created by the Token Metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), collection_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Accounts used to turn a freshly minted token into a verified member of an NFT collection
+pub(crate) struct CollectionItem<'a, 'info> {
+    pub payer: &'a AccountInfo<'info>,
+    pub mint: &'a AccountInfo<'info>,
+    pub mint_authority: &'a AccountInfo<'info>,
+    pub update_authority: &'a AccountInfo<'info>,
+    pub metadata: &'a AccountInfo<'info>,
+    pub master_edition: &'a AccountInfo<'info>,
+    pub collection_mint: &'a AccountInfo<'info>,
+    pub collection_metadata: &'a AccountInfo<'info>,
+    pub collection_master_edition: &'a AccountInfo<'info>,
+    pub collection_authority: &'a AccountInfo<'info>,
+    pub token_metadata_program: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+}
+
+pub(crate) fn validate_nft_metadata(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    require!(name.len() <= MAX_NFT_NAME_LENGTH, CapsuleXError::NftNameTooLong);
+    require!(symbol.len() <= MAX_NFT_SYMBOL_LENGTH, CapsuleXError::NftSymbolTooLong);
+    require!(uri.len() <= MAX_METADATA_URI_LENGTH, CapsuleXError::MetadataUriTooLong);
+    Ok(())
+}
+
+// Must run after the single token is minted: the master edition takes over the mint authority.
+// signer_seeds covers every PDA among the mint authority, update authority and collection authority.
+pub(crate) fn create_collection_item(
+    item: CollectionItem<'_, '_>,
+    name: String,
+    symbol: String,
+    uri: String,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            item.token_metadata_program.clone(),
+            CreateMetadataAccountsV3 {
+                metadata: item.metadata.clone(),
+                mint: item.mint.clone(),
+                mint_authority: item.mint_authority.clone(),
+                payer: item.payer.clone(),
+                update_authority: item.update_authority.clone(),
+                system_program: item.system_program.clone(),
+                rent: item.rent.clone(),
+            },
+            signer_seeds,
+        ),
+        DataV2 {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: Some(Collection {
+                verified: false,
+                key: item.collection_mint.key(),
+            }),
+            uses: None,
+        },
+        true, // is_mutable
+        true, // update_authority_is_signer
+        None,
+    )?;
+
+    // max_supply of 0: no prints can ever be made from this edition
+    create_master_edition_v3(
+        CpiContext::new_with_signer(
+            item.token_metadata_program.clone(),
+            CreateMasterEditionV3 {
+                edition: item.master_edition.clone(),
+                mint: item.mint.clone(),
+                update_authority: item.update_authority.clone(),
+                mint_authority: item.mint_authority.clone(),
+                payer: item.payer.clone(),
+                metadata: item.metadata.clone(),
+                token_program: item.token_program.clone(),
+                system_program: item.system_program.clone(),
+                rent: item.rent.clone(),
+            },
+            signer_seeds,
+        ),
+        Some(0),
+    )?;
+
+    verify_sized_collection_item(
+        CpiContext::new_with_signer(
+            item.token_metadata_program.clone(),
+            VerifySizedCollectionItem {
+                payer: item.payer.clone(),
+                metadata: item.metadata.clone(),
+                collection_authority: item.collection_authority.clone(),
+                collection_mint: item.collection_mint.clone(),
+                collection_metadata: item.collection_metadata.clone(),
+                collection_master_edition: item.collection_master_edition.clone(),
+            },
+            signer_seeds,
+        ),
+        None,
+    )
+}
+
+pub fn initialize_nft_collection(
+    ctx: Context<InitializeNftCollection>,
+    kind: NftKind,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    validate_nft_metadata(&name, &symbol, &uri)?;
+
+    let authority_bump = [ctx.bumps.collection_authority];
+    let authority_seeds: &[&[u8]] = &[COLLECTION_AUTHORITY_SEED, &authority_bump];
+    let signer_seeds = &[authority_seeds];
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.collection_mint.to_account_info(),
+                to: ctx.accounts.collection_token_account.to_account_info(),
+                authority: ctx.accounts.collection_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.collection_metadata.to_account_info(),
+                mint: ctx.accounts.collection_mint.to_account_info(),
+                mint_authority: ctx.accounts.collection_authority.to_account_info(),
+                payer: ctx.accounts.authority.to_account_info(),
+                update_authority: ctx.accounts.collection_authority.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        DataV2 {
+            name: name.clone(),
+            symbol,
+            uri: uri.clone(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        true, // is_mutable
+        true, // update_authority_is_signer
+        Some(CollectionDetails::V1 { size: 0 }),
+    )?;
+
+    create_master_edition_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMasterEditionV3 {
+                edition: ctx.accounts.collection_master_edition.to_account_info(),
+                mint: ctx.accounts.collection_mint.to_account_info(),
+                update_authority: ctx.accounts.collection_authority.to_account_info(),
+                mint_authority: ctx.accounts.collection_authority.to_account_info(),
+                payer: ctx.accounts.authority.to_account_info(),
+                metadata: ctx.accounts.collection_metadata.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        Some(0),
+    )?;
+
+    emit!(NftCollectionInitialized {
+        kind,
+        collection_mint: ctx.accounts.collection_mint.key(),
+        name,
+        uri,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct NftCollectionInitialized {
+    pub kind: NftKind,
+    pub collection_mint: Pubkey,
+    pub name: String,
+    pub uri: String,
+}
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::CapsuleXError,
+    state::{ProgramConfig, ProgramVault}
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ConfigUpdate {
+    pub app_authority: Option<Pubkey>,
+    pub capsule_creation_fee: Option<u64>,
+    pub service_fee: Option<u64>,
+    pub badge_mint_fee: Option<u64>,
+    pub trophy_mint_fee: Option<u64>,
+    pub creator_share_bps: Option<u16>,
+    pub refund_grace_period: Option<i64>,
+    pub soulbound_nfts: Option<bool>,
+}
+
+// For deployments whose vault was initialized before ProgramConfig existed
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        constraint = vault.authority == authority.key() @ CapsuleXError::InvalidProgramAuthority
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ProgramConfig::LEN,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        constraint = vault.authority == authority.key() @ CapsuleXError::InvalidProgramAuthority
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+fn validated_fee(fee: Option<u64>, current: u64) -> Result<u64> {
+    match fee {
+        Some(fee) => {
+            require!(fee <= MAX_CONFIG_FEE, CapsuleXError::FeeTooHigh);
+            Ok(fee)
+        }
+        None => Ok(current),
+    }
+}
+
+pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    **config = ProgramConfig::new(ctx.accounts.authority.key(), ctx.bumps.config);
+
+    emit!(ConfigUpdated {
+        authority: ctx.accounts.authority.key(),
+        app_authority: config.app_authority,
+        capsule_creation_fee: config.capsule_creation_fee,
+        service_fee: config.service_fee,
+        badge_mint_fee: config.badge_mint_fee,
+        trophy_mint_fee: config.trophy_mint_fee,
+        creator_share_bps: config.creator_share_bps,
+        refund_grace_period: config.refund_grace_period,
+        soulbound_nfts: config.soulbound_nfts,
+    });
+
+    Ok(())
+}
+
+pub fn update_config(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.capsule_creation_fee = validated_fee(update.capsule_creation_fee, config.capsule_creation_fee)?;
+    config.service_fee = validated_fee(update.service_fee, config.service_fee)?;
+    config.badge_mint_fee = validated_fee(update.badge_mint_fee, config.badge_mint_fee)?;
+    config.trophy_mint_fee = validated_fee(update.trophy_mint_fee, config.trophy_mint_fee)?;
+
+    if let Some(creator_share_bps) = update.creator_share_bps {
+        require!(
+            creator_share_bps as u64 <= BASIS_POINTS_DENOMINATOR,
+            CapsuleXError::InvalidCreatorShare
+        );
+        config.creator_share_bps = creator_share_bps;
+    }
+    
+    if let Some(refund_grace_period) = update.refund_grace_period {
+        require!(
+            refund_grace_period > 0 && refund_grace_period <= MAX_REFUND_GRACE_PERIOD,
+            CapsuleXError::InvalidRefundGracePeriod
+        );
+        config.refund_grace_period = refund_grace_period;
+    }
+    
+    if let Some(soulbound_nfts) = update.soulbound_nfts {
+        config.soulbound_nfts = soulbound_nfts;
+    }
+    
+    if let Some(app_authority) = update.app_authority {
+        config.app_authority = app_authority;
+    }
+
+    emit!(ConfigUpdated {
+        authority: ctx.accounts.authority.key(),
+        app_authority: config.app_authority,
+        capsule_creation_fee: config.capsule_creation_fee,
+        service_fee: config.service_fee,
+        badge_mint_fee: config.badge_mint_fee,
+        trophy_mint_fee: config.trophy_mint_fee,
+        creator_share_bps: config.creator_share_bps,
+        refund_grace_period: config.refund_grace_period,
+        soulbound_nfts: config.soulbound_nfts,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub authority: Pubkey,
+    pub app_authority: Pubkey,
+    pub capsule_creation_fee: u64,
+    pub service_fee: u64,
+    pub badge_mint_fee: u64,
+    pub trophy_mint_fee: u64,
+    pub creator_share_bps: u16,
+    pub refund_grace_period: i64,
+    pub soulbound_nfts: bool,
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::CapsuleXError,
+    state::CreatorEarnings
+};
+
+#[derive(Accounts)]
+pub struct ClaimCreatorEarnings<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CREATOR_EARNINGS_SEED, creator.key().as_ref()],
+        bump = creator_earnings.bump,
+        has_one = creator @ CapsuleXError::UnauthorizedCreator
+    )]
+    pub creator_earnings: Account<'info, CreatorEarnings>,
+}
+
+pub fn claim_creator_earnings(ctx: Context<ClaimCreatorEarnings>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let creator_earnings = &mut ctx.accounts.creator_earnings;
+    // Shares from capsules that are not yet revealed stay locked in case their guessers must be refunded
+    require!(creator_earnings.claimable_balance() > 0, CapsuleXError::NoCreatorEarnings);
+
+    let amount = creator_earnings.claim(now);
+
+    // Earnings account is program owned, so lamports are moved directly; its rent stays behind
+    let earnings_info = creator_earnings.to_account_info();
+    let creator_info = ctx.accounts.creator.to_account_info();
+    **earnings_info.try_borrow_mut_lamports()? = earnings_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(CapsuleXError::ArithmeticOverflow)?;
+    **creator_info.try_borrow_mut_lamports()? = creator_info
+        .lamports()
+        .checked_add(amount)
+        .ok_or(CapsuleXError::ArithmeticOverflow)?;
+
+    emit!(CreatorEarningsClaimed {
+        creator: creator_earnings.creator,
+        amount,
+        total_claimed: creator_earnings.total_claimed,
+        claimed_at: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct CreatorEarningsClaimed {
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub claimed_at: i64,
+}
@@ -1,10 +1,93 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    hash::hashv,
+    sysvar::instructions::{self as sysvar_instructions, load_current_index_checked, load_instruction_at_checked},
+};
 use crate::{
     constants::*, 
     errors::CapsuleXError, 
-    state::{Capsule, Game, Guess, ProgramVault, LeaderboardEntry}
+    instructions::prize::require_no_prize_refunds,
+    state::{Capsule, CreatorEarnings, Game, Guess, ProgramVault, ProgramConfig, LeaderboardEntry, OracleConfig, UsedNonce},
+    utils::create_pda_account,
 };
 
+// Ed25519 native program instruction layout: [num_signatures: u8, padding: u8, offsets...]
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_SIZE: usize = 14;
+// Instruction index the Ed25519 program uses for "data lives in this same instruction"
+const ED25519_CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    let bytes = data
+        .get(offset..offset + 2)
+        .ok_or(CapsuleXError::InvalidOracleSignature)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_slice(data: &[u8], offset: u16, len: usize) -> Result<&[u8]> {
+    let start = offset as usize;
+    data.get(start..start + len)
+        .ok_or_else(|| error!(CapsuleXError::InvalidOracleSignature))
+}
+
+// Ensure the instruction right before this one is an Ed25519 program instruction that
+// verified exactly this message and signature, and return the public key it was checked
+// against. The runtime rejects the whole transaction if the Ed25519 check itself fails,
+// so matching the inputs is sufficient.
+fn verify_ed25519_instruction(
+    instructions_sysvar: &AccountInfo,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<Pubkey> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, CapsuleXError::MissingOracleVerification);
+
+    let ed25519_ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require!(
+        ed25519_ix.program_id == ed25519_program::ID,
+        CapsuleXError::MissingOracleVerification
+    );
+    require!(ed25519_ix.accounts.is_empty(), CapsuleXError::InvalidOracleSignature);
+
+    let data = &ed25519_ix.data;
+    require!(
+        data.len() >= ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE && data[0] == 1,
+        CapsuleXError::InvalidOracleSignature
+    );
+
+    let signature_offset = read_u16(data, ED25519_OFFSETS_START)?;
+    let signature_ix_index = read_u16(data, ED25519_OFFSETS_START + 2)?;
+    let public_key_offset = read_u16(data, ED25519_OFFSETS_START + 4)?;
+    let public_key_ix_index = read_u16(data, ED25519_OFFSETS_START + 6)?;
+    let message_offset = read_u16(data, ED25519_OFFSETS_START + 8)?;
+    let message_size = read_u16(data, ED25519_OFFSETS_START + 10)?;
+    let message_ix_index = read_u16(data, ED25519_OFFSETS_START + 12)?;
+
+    // All verified data must be embedded in the Ed25519 instruction itself
+    require!(
+        signature_ix_index == ED25519_CURRENT_INSTRUCTION
+            && public_key_ix_index == ED25519_CURRENT_INSTRUCTION
+            && message_ix_index == ED25519_CURRENT_INSTRUCTION,
+        CapsuleXError::InvalidOracleSignature
+    );
+
+    require!(
+        read_slice(data, signature_offset, 64)? == signature,
+        CapsuleXError::InvalidOracleSignature
+    );
+    require!(
+        message_size as usize == message.len()
+            && read_slice(data, message_offset, message.len())? == message,
+        CapsuleXError::InvalidOracleSignature
+    );
+
+    let public_key: [u8; 32] = read_slice(data, public_key_offset, 32)?
+        .try_into()
+        .map_err(|_| CapsuleXError::InvalidOracleSignature)?;
+    Ok(Pubkey::new_from_array(public_key))
+}
+
 #[derive(Accounts)]
 #[instruction(capsule_id: Pubkey, max_guesses: u32)]
 pub struct InitializeGame<'info> {
@@ -27,11 +110,21 @@ pub struct InitializeGame<'info> {
     )]
     pub game: Account<'info, Game>,
     
+    // Guesses credit the creator's share here, so the creator pays for it before any guess arrives
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = CreatorEarnings::LEN,
+        seeds = [CREATOR_EARNINGS_SEED, creator.key().as_ref()],
+        bump
+    )]
+    pub creator_earnings: Account<'info, CreatorEarnings>,
+    
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(guess_content: String, is_anonymous: bool)]
+#[instruction(commitment: [u8; 32], is_anonymous: bool)]
 pub struct SubmitGuess<'info> {
     #[account(mut)]
     pub guesser: Signer<'info>,
@@ -42,6 +135,11 @@ pub struct SubmitGuess<'info> {
     )]
     pub game: Account<'info, Game>,
     
+    #[account(
+        constraint = capsule.key() == game.capsule_id @ CapsuleXError::InvalidGameAccount
+    )]
+    pub capsule: Account<'info, Capsule>,
+    
     #[account(
         init,
         payer = guesser,
@@ -58,18 +156,74 @@ pub struct SubmitGuess<'info> {
     )]
     pub vault: Account<'info, ProgramVault>,
     
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    // Created and paid for by the creator with their first capsule or game
+    #[account(
+        mut,
+        seeds = [CREATOR_EARNINGS_SEED, game.creator.as_ref()],
+        bump = creator_earnings.bump
+    )]
+    pub creator_earnings: Account<'info, CreatorEarnings>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealGuess<'info> {
+    pub guesser: Signer<'info>,
+    
+    #[account(
+        mut,
+        has_one = guesser @ CapsuleXError::InvalidAccountOwner,
+        constraint = guess.game_id == game.key() @ CapsuleXError::InvalidGuessId,
+        constraint = !guess.is_opened @ CapsuleXError::GuessAlreadyOpened
+    )]
+    pub guess: Account<'info, Guess>,
+    
+    pub game: Account<'info, Game>,
+    
+    #[account(
+        constraint = capsule.key() == game.capsule_id @ CapsuleXError::InvalidGameAccount
+    )]
+    pub capsule: Account<'info, Capsule>,
+}
+
+// Guesses created before commit-reveal are LEGACY_GUESS_ACCOUNT_SIZE bytes and do not deserialize as
+// the current struct, so the account is grown before it is read
+#[derive(Accounts)]
+pub struct MigrateLegacyGuess<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Legacy guess; the discriminator is checked in the handler before the account is read
+    #[account(mut, owner = crate::ID @ CapsuleXError::InvalidGuessId)]
+    pub guess: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 #[instruction(decrypted_content: String, verification_window_hours: Option<u8>, semantic_result: bool, oracle_timestamp: i64, oracle_nonce: String, oracle_signature: String)]
 pub struct VerifyGuess<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = authority.key() == game.creator ||
+                     authority.key() == vault.authority ||
+                     oracle_config.is_relayer(&authority.key()) @ CapsuleXError::UnauthorizedVerifier
+    )]
     pub authority: Signer<'info>,
     
     #[account(
         mut,
-        constraint = guess.game_id == game.key() @ CapsuleXError::InvalidGuessId
+        constraint = guess.game_id == game.key() @ CapsuleXError::InvalidGuessId,
+        constraint = !guess.is_verified @ CapsuleXError::GuessAlreadyVerified,
+        constraint = guess.is_opened @ CapsuleXError::GuessNotOpened
     )]
     pub guess: Account<'info, Guess>,
     
@@ -91,14 +245,56 @@ pub struct VerifyGuess<'info> {
         bump
     )]
     pub leaderboard: Account<'info, LeaderboardEntry>,
+    
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, ProgramVault>,
+    
+    #[account(
+        seeds = [ORACLE_CONFIG_SEED],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    /// CHECK: Prize pool PDA for the game; only inspected when one was opened
+    #[account(
+        seeds = [PRIZE_POOL_SEED, game.key().as_ref()],
+        bump
+    )]
+    pub prize_pool: UncheckedAccount<'info>,
+    
+    // Replay ledger entry for this oracle nonce, created in the handler so a reused nonce
+    // fails with OracleNonceReused
+    /// CHECK: PDA of the nonce hash; must not be owned by the program yet
+    #[account(
+        mut,
+        seeds = [USED_NONCE_SEED, &UsedNonce::hash_nonce(&oracle_nonce)],
+        bump
+    )]
+    pub used_nonce: UncheckedAccount<'info>,
+    
+    /// CHECK: Instructions sysvar, used to read the preceding Ed25519 verification instruction
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct CompleteGame<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = authority.key() == game.creator ||
+                     authority.key() == config.app_authority @ CapsuleXError::UnauthorizedGameCompletion
+    )]
     pub authority: Signer<'info>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = game.is_active @ CapsuleXError::GameNotActive
+    )]
     pub game: Account<'info, Game>,
     
     #[account(
@@ -113,6 +309,19 @@ pub struct CompleteGame<'info> {
     )]
     pub creator_leaderboard: Account<'info, LeaderboardEntry>,
     
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// CHECK: Prize pool PDA for the game; only inspected when one was opened
+    #[account(
+        seeds = [PRIZE_POOL_SEED, game.key().as_ref()],
+        bump
+    )]
+    pub prize_pool: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -134,6 +343,12 @@ pub fn initialize_game(
         CapsuleXError::InvalidFeeAmount
     );
     
+    let creator_earnings = &mut ctx.accounts.creator_earnings;
+    if creator_earnings.creator == Pubkey::default() {
+        creator_earnings.creator = ctx.accounts.creator.key();
+        creator_earnings.bump = ctx.bumps.creator_earnings;
+    }
+    
     // Initialize game
     let game = &mut ctx.accounts.game;
     **game = Game::new(
@@ -154,29 +369,32 @@ pub fn initialize_game(
     Ok(())
 }
 
+// Guesses are committed as sha256(guess_content || salt) and opened with reveal_guess after reveal_date
 pub fn submit_guess(
     ctx: Context<SubmitGuess>,
-    guess_content: String,
+    commitment: [u8; 32],
     is_anonymous: bool,
 ) -> Result<()> {
-    // Validate guess content length
-    require!(
-        guess_content.len() <= MAX_GUESS_CONTENT_LENGTH,
-        CapsuleXError::GuessContentTooLong
-    );
-    
     let game = &mut ctx.accounts.game;
     
     // Check if game can accept more guesses
     require!(game.can_accept_guess(), CapsuleXError::MaxGuessesReached);
     
-    // Collect service fee for guess submission
-    let fee_amount = SERVICE_FEE;
+    // Commitments close at the reveal date; afterwards the answer is public
+    require!(
+        Clock::get()?.unix_timestamp < ctx.accounts.capsule.reveal_date,
+        CapsuleXError::GuessCommitPhaseClosed
+    );
+    
+    // Collect service fee for guess submission; the creator's share is escrowed in CreatorEarnings
+    let fee_amount = ctx.accounts.config.service_fee;
+    let creator_share = ctx.accounts.config.creator_share(fee_amount);
+    let vault_share = fee_amount - creator_share;
     
     let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
         &ctx.accounts.guesser.key(),
         &ctx.accounts.vault.key(),
-        fee_amount,
+        vault_share,
     );
     
     anchor_lang::solana_program::program::invoke(
@@ -188,18 +406,54 @@ pub fn submit_guess(
         ],
     )?;
     
-    ctx.accounts.vault.add_fees(fee_amount);
+    ctx.accounts.vault.add_fees(vault_share);
+    
+    // Held back from withdrawals until the capsule is revealed, in case the guess must be refunded
+    game.vault_fees_held += vault_share;
+    ctx.accounts.config.reserve_refundable(vault_share);
+    
+    let creator_earnings = &mut ctx.accounts.creator_earnings;
+    if creator_share > 0 {
+        let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
+            &ctx.accounts.guesser.key(),
+            &creator_earnings.key(),
+            creator_share,
+        );
+        
+        anchor_lang::solana_program::program::invoke(
+            &transfer_instruction,
+            &[
+                ctx.accounts.guesser.to_account_info(),
+                creator_earnings.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+    
+    creator_earnings.accrue(creator_share);
+    creator_earnings.lock(creator_share);
+    game.creator_fees_held += creator_share;
+    
+    emit!(CreatorEarningsAccrued {
+        creator: game.creator,
+        game_id: game.key(),
+        amount: creator_share,
+        total_earned: creator_earnings.total_earned,
+        available_balance: creator_earnings.available_balance,
+    });
     
     // Initialize guess
     let guess = &mut ctx.accounts.guess;
     **guess = Guess::new(
         game.key(),
         ctx.accounts.guesser.key(),
-        guess_content.clone(),
+        commitment,
         true, // Fee collected
         is_anonymous,
         ctx.bumps.guess,
     );
+    guess.fee_paid = fee_amount;
+    guess.creator_share_paid = creator_share;
     
     // Update game
     game.add_guess();
@@ -208,7 +462,7 @@ pub fn submit_guess(
         guess_id: guess.key(),
         game_id: game.key(),
         guesser: ctx.accounts.guesser.key(),
-        guess_content,
+        commitment,
         is_paid: true, // Fee collected
         is_anonymous,
     });
@@ -216,6 +470,88 @@ pub fn submit_guess(
     Ok(())
 }
 
+pub fn reveal_guess(
+    ctx: Context<RevealGuess>,
+    guess_content: String,
+    salt: [u8; 32],
+) -> Result<()> {
+    require!(
+        guess_content.len() <= MAX_GUESS_CONTENT_LENGTH,
+        CapsuleXError::GuessContentTooLong
+    );
+    
+    // Commitments open after the capsule's reveal date; unopened ones forfeit once the window closes
+    let clock = Clock::get()?;
+    let reveal_date = ctx.accounts.capsule.reveal_date;
+    require!(
+        clock.unix_timestamp >= reveal_date,
+        CapsuleXError::GuessRevealNotStarted
+    );
+    require!(
+        clock.unix_timestamp <= reveal_date + GUESS_REVEAL_WINDOW,
+        CapsuleXError::GuessRevealWindowClosed
+    );
+    
+    let guess = &mut ctx.accounts.guess;
+    let commitment = hashv(&[guess_content.as_bytes(), &salt]).to_bytes();
+    require!(
+        commitment == guess.commitment,
+        CapsuleXError::InvalidGuessCommitment
+    );
+    
+    guess.open(guess_content);
+    
+    emit!(GuessRevealed {
+        guess_id: guess.key(),
+        game_id: ctx.accounts.game.key(),
+        guesser: guess.guesser,
+        guess_content: guess.guess_content.clone(),
+        is_anonymous: guess.is_anonymous,
+    });
+    
+    Ok(())
+}
+
+pub fn migrate_legacy_guess(ctx: Context<MigrateLegacyGuess>) -> Result<()> {
+    let guess_info = ctx.accounts.guess.to_account_info();
+    require!(guess_info.data_len() != Guess::LEN, CapsuleXError::GuessAlreadyMigrated);
+    require!(
+        guess_info.data_len() == LEGACY_GUESS_ACCOUNT_SIZE && guess_info.data.borrow()[..8] == *Guess::DISCRIMINATOR,
+        CapsuleXError::InvalidGuessId
+    );
+    
+    // Top up rent for the larger account, then grow it; the new bytes are zeroed
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(Guess::LEN)
+        .saturating_sub(guess_info.lamports());
+    if rent_shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: guess_info.clone(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+    guess_info.resize(Guess::LEN)?;
+    
+    // Legacy guesses were submitted in the clear, so a stored answer counts as already opened
+    let mut guess = Guess::try_deserialize(&mut &guess_info.data.borrow()[..])?;
+    guess.is_opened = !guess.guess_content.is_empty();
+    guess.try_serialize(&mut &mut guess_info.data.borrow_mut()[..])?;
+    
+    emit!(LegacyGuessMigrated {
+        guess_id: guess_info.key(),
+        game_id: guess.game_id,
+        guesser: guess.guesser,
+    });
+    
+    Ok(())
+}
+
 pub fn verify_guess(
     ctx: Context<VerifyGuess>,
     decrypted_content: String,
@@ -232,8 +568,15 @@ pub fn verify_guess(
     // Ensure the capsule has been revealed
     require!(capsule.is_revealed, CapsuleXError::CapsuleNotReady);
     
+    // The caller's decrypted content must be the content the capsule committed to
+    require!(
+        capsule.matches_integrity_hash(&decrypted_content),
+        CapsuleXError::ContentIntegrityMismatch
+    );
+    
     // Ensure the game is still active and accepting verifications
     require!(game.is_active, CapsuleXError::GameNotActive);
+    require_no_prize_refunds(&ctx.accounts.prize_pool.to_account_info())?;
     
     // Check if we're within the verification window
     let clock = Clock::get()?;
@@ -245,56 +588,72 @@ pub fn verify_guess(
         CapsuleXError::GameNotEnded
     );
     
-    // Verify Oracle signature to prevent cheating (if signature provided)
-    if !oracle_signature.is_empty() {
-        // Oracle public key (from semantic service)
-        let oracle_public_key_bytes = hex::decode("8733e85cf22f932ababb1fda9f1f2ad386e26c835d0500d05f806b9d0b739159")
-            .map_err(|_| CapsuleXError::InvalidOracleSignature)?;
-        
-        // Verify signature timestamp (must be within 15 minutes)
-        let current_time = clock.unix_timestamp;
-        let signature_age = current_time - oracle_timestamp;
-        require!(
-            signature_age >= -60 && signature_age <= 900, // Allow 1 min future, 15 min past for clock drift
-            CapsuleXError::OracleSignatureExpired
-        );
-        
-        // Reconstruct the signed message (must match semantic service format)
-        let message = format!("{}:{}:{}:{}:{}", guess.guess_content, decrypted_content, semantic_result, oracle_timestamp, oracle_nonce);
-        
-        // Verify signature using Solana's built-in ed25519 verification
-        use base64::{Engine as _, engine::general_purpose::STANDARD};
-        let signature_bytes = STANDARD.decode(&oracle_signature)
-            .map_err(|_| CapsuleXError::InvalidOracleSignature)?;
-        
-        // Use ed25519 program verification
-        require!(
-            signature_bytes.len() == 64,
-            CapsuleXError::InvalidOracleSignature
-        );
-        require!(
-            oracle_public_key_bytes.len() == 32,
-            CapsuleXError::InvalidOracleSignature
-        );
-        
-        let signature: [u8; 64] = signature_bytes.try_into().map_err(|_| CapsuleXError::InvalidOracleSignature)?;
-        let public_key: [u8; 32] = oracle_public_key_bytes.try_into().map_err(|_| CapsuleXError::InvalidOracleSignature)?;
-        
-        // For now, skip signature verification in development mode
-        // TODO: Implement proper ed25519 signature verification
-        msg!("Oracle signature verification skipped in development mode");
-    }
-    // If no signature provided, we're in development/fallback mode (allows testing)
+    // Verify Oracle signature to prevent cheating
+    // Verify signature timestamp (must be within 15 minutes)
+    let signature_age = clock.unix_timestamp - oracle_timestamp;
+    require!(
+        (-ORACLE_SIGNATURE_MAX_FUTURE_DRIFT..=ORACLE_SIGNATURE_MAX_AGE).contains(&signature_age),
+        CapsuleXError::OracleSignatureExpired
+    );
+    
+    // Reconstruct the signed message (must match semantic service format)
+    let message = format!("{}:{}:{}:{}:{}", guess.guess_content, decrypted_content, semantic_result, oracle_timestamp, oracle_nonce);
+    
+    use base64::{Engine as _, engine::general_purpose::STANDARD};
+    let signature_bytes = STANDARD.decode(&oracle_signature)
+        .map_err(|_| CapsuleXError::InvalidOracleSignature)?;
+    require!(
+        signature_bytes.len() == 64,
+        CapsuleXError::InvalidOracleSignature
+    );
+    
+    // Signature itself is checked by the Ed25519 program instruction preceding this one
+    let oracle_public_key = verify_ed25519_instruction(
+        &ctx.accounts.instructions_sysvar.to_account_info(),
+        message.as_bytes(),
+        &signature_bytes,
+    )?;
+    
+    // Only keys currently active in the on-chain oracle registry are trusted
+    require!(
+        ctx.accounts.oracle_config.is_active_oracle(&oracle_public_key, clock.unix_timestamp),
+        CapsuleXError::InactiveOracleKey
+    );
+    
+    // Record the nonce so the signed verdict can only be consumed once
+    let used_nonce_info = ctx.accounts.used_nonce.to_account_info();
+    require!(used_nonce_info.owner != &crate::ID, CapsuleXError::OracleNonceReused);
+    let nonce_hash = UsedNonce::hash_nonce(&oracle_nonce);
+    create_pda_account(
+        &ctx.accounts.authority.to_account_info(),
+        &used_nonce_info,
+        &ctx.accounts.system_program.to_account_info(),
+        Rent::get()?.minimum_balance(UsedNonce::LEN),
+        UsedNonce::LEN as u64,
+        &crate::ID,
+        &[USED_NONCE_SEED, &nonce_hash, &[ctx.bumps.used_nonce]],
+    )?;
+    UsedNonce::new(
+        nonce_hash,
+        oracle_public_key,
+        ctx.accounts.authority.key(),
+        clock.unix_timestamp,
+        oracle_timestamp,
+        ctx.bumps.used_nonce,
+    ).try_serialize(&mut &mut used_nonce_info.try_borrow_mut_data()?[..])?;
     
     // Use verified semantic result
     let is_correct = semantic_result;
+    guess.mark_verified(clock.unix_timestamp);
+    game.record_verification();
     
     // Update leaderboard for participation
     let leaderboard = &mut ctx.accounts.leaderboard;
     leaderboard.add_game_played();
     leaderboard.add_points(PARTICIPATION_POINTS);
     
-    if is_correct && game.winners_found < game.max_winners {
+    // A player who already won with another guess is not added to winners again
+    if is_correct && game.winners_found < game.max_winners && !game.is_winner(&guess.guesser) {
         // Mark guess as correct
         guess.mark_correct();
         
@@ -344,25 +703,28 @@ pub fn complete_game(ctx: Context<CompleteGame>) -> Result<()> {
     // 1. Max winners reached
     // 2. Max guesses reached  
     // 3. No guesses submitted yet (creator can cancel)
-    // 4. After reveal date (creator can end anytime after reveal)
+    // 4. After reveal, once every guess has been verified
     // 5. After reveal + 1 hour verification window (auto-complete allowed)
+    // Completing right after reveal would cut off guesses still waiting for verification
     
     let max_conditions_met = game.winners_found >= game.max_winners || game.current_guesses >= game.max_guesses;
     let no_guesses_yet = game.current_guesses == 0;
-    let after_reveal = capsule.is_revealed && clock.unix_timestamp >= capsule.reveal_date;
+    let all_guesses_verified = capsule.is_revealed && game.all_guesses_verified();
     let verification_window_expired = capsule.is_revealed && clock.unix_timestamp >= (capsule.reveal_date + 3600); // +1 hour
     
     require!(
-        max_conditions_met || no_guesses_yet || after_reveal || verification_window_expired,
+        max_conditions_met || no_guesses_yet || all_guesses_verified || verification_window_expired,
         CapsuleXError::GameNotEnded
     );
+    require_no_prize_refunds(&ctx.accounts.prize_pool.to_account_info())?;
     
-    // Award bonus points to capsule creator for engagement
-    if game.total_participants > 0 {
+    // Award bonus points to capsule creator for engagement (exactly once per game)
+    if game.total_participants > 0 && !game.bonus_paid {
         // Creator gets bonus points based on participation
         let creator_leaderboard = &mut ctx.accounts.creator_leaderboard;
         let bonus_points = CREATOR_BONUS_POINTS * (game.total_participants as u64);
         creator_leaderboard.add_points(bonus_points);
+        game.bonus_paid = true;
         
         emit!(PointsAwarded {
             user: game.creator,
@@ -399,11 +761,36 @@ pub struct GuessSubmitted {
     pub guess_id: Pubkey,
     pub game_id: Pubkey,
     pub guesser: Pubkey,
-    pub guess_content: String,
+    pub commitment: [u8; 32],
     pub is_paid: bool,
     pub is_anonymous: bool,
 }
 
+#[event]
+pub struct CreatorEarningsAccrued {
+    pub creator: Pubkey,
+    pub game_id: Pubkey,
+    pub amount: u64,
+    pub total_earned: u64,
+    pub available_balance: u64,
+}
+
+#[event]
+pub struct GuessRevealed {
+    pub guess_id: Pubkey,
+    pub game_id: Pubkey,
+    pub guesser: Pubkey,
+    pub guess_content: String,
+    pub is_anonymous: bool,
+}
+
+#[event]
+pub struct LegacyGuessMigrated {
+    pub guess_id: Pubkey,
+    pub game_id: Pubkey,
+    pub guesser: Pubkey,
+}
+
 #[event]
 pub struct WinnerFound {
     pub game_id: Pubkey,
@@ -2,9 +2,10 @@ use anchor_lang::prelude::*;
 use crate::{
     constants::*, 
     errors::CapsuleXError, 
-    state::{LeaderboardEntry, ProgramVault}
+    state::{LeaderboardEntry, PointsIssuer, PointsIssuerRegistry, ProgramVault, ProgramConfig}
 };
 
+// Points can only be granted by the vault authority or a registered points issuer
 #[derive(Accounts)]
 #[instruction(user: Pubkey, points: u64)]
 pub struct UpdateLeaderboard<'info> {
@@ -14,6 +15,21 @@ pub struct UpdateLeaderboard<'info> {
     /// CHECK: This is the user whose leaderboard entry is being updated
     pub user: AccountInfo<'info>,
     
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, ProgramVault>,
+    
+    #[account(
+        mut,
+        seeds = [POINTS_ISSUER_REGISTRY_SEED],
+        bump = points_issuers.bump,
+        constraint = vault.authority == authority.key() ||
+                     points_issuers.is_issuer(&authority.key()) @ CapsuleXError::UnauthorizedPointsIssuer
+    )]
+    pub points_issuers: Account<'info, PointsIssuerRegistry>,
+    
     #[account(
         init_if_needed,
         payer = authority,
@@ -26,6 +42,7 @@ pub struct UpdateLeaderboard<'info> {
     pub system_program: Program<'info, System>,
 }
 
+// Entries are created by the user themselves, the vault authority or a registered points issuer
 #[derive(Accounts)]
 #[instruction(user: Pubkey)]
 pub struct InitializeLeaderboard<'info> {
@@ -35,6 +52,21 @@ pub struct InitializeLeaderboard<'info> {
     /// CHECK: This is the user for whom we're creating a leaderboard entry
     pub user: AccountInfo<'info>,
     
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, ProgramVault>,
+    
+    #[account(
+        seeds = [POINTS_ISSUER_REGISTRY_SEED],
+        bump = points_issuers.bump,
+        constraint = authority.key() == user.key() ||
+                     vault.authority == authority.key() ||
+                     points_issuers.is_issuer(&authority.key()) @ CapsuleXError::UnauthorizedPointsIssuer
+    )]
+    pub points_issuers: Account<'info, PointsIssuerRegistry>,
+    
     #[account(
         init,
         payer = authority,
@@ -73,9 +105,71 @@ pub struct InitializeProgram<'info> {
     )]
     pub vault: Account<'info, ProgramVault>,
     
+    #[account(
+        init,
+        payer = authority,
+        space = ProgramConfig::LEN,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = PointsIssuerRegistry::LEN,
+        seeds = [POINTS_ISSUER_REGISTRY_SEED],
+        bump
+    )]
+    pub points_issuers: Account<'info, PointsIssuerRegistry>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPointsIssuer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        constraint = vault.authority == authority.key() @ CapsuleXError::InvalidProgramAuthority
+    )]
+    pub vault: Account<'info, ProgramVault>,
+    
+    // Also created here for deployments initialized before the registry existed
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PointsIssuerRegistry::LEN,
+        seeds = [POINTS_ISSUER_REGISTRY_SEED],
+        bump
+    )]
+    pub points_issuers: Account<'info, PointsIssuerRegistry>,
+    
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct RemovePointsIssuer<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        constraint = vault.authority == authority.key() @ CapsuleXError::InvalidProgramAuthority
+    )]
+    pub vault: Account<'info, ProgramVault>,
+    
+    #[account(
+        mut,
+        seeds = [POINTS_ISSUER_REGISTRY_SEED],
+        bump = points_issuers.bump
+    )]
+    pub points_issuers: Account<'info, PointsIssuerRegistry>,
+}
+
 pub fn update_leaderboard(
     ctx: Context<UpdateLeaderboard>,
     user: Pubkey,
@@ -94,6 +188,21 @@ pub fn update_leaderboard(
         CapsuleXError::InvalidAccountOwner
     );
     
+    // Issuers draw down their cap; the vault authority is not capped
+    let authority = ctx.accounts.authority.key();
+    if ctx.accounts.vault.authority != authority {
+        let issuer = ctx
+            .accounts
+            .points_issuers
+            .find_mut(&authority)
+            .ok_or(CapsuleXError::UnauthorizedPointsIssuer)?;
+        require!(
+            points <= issuer.remaining_points(),
+            CapsuleXError::PointsCapExceeded
+        );
+        issuer.points_issued += points;
+    }
+    
     // Add points
     leaderboard.add_points(points);
     
@@ -131,9 +240,77 @@ pub fn initialize_program(
     // Initialize program vault
     **vault = ProgramVault::new(ctx.accounts.authority.key(), ctx.bumps.vault);
     
+    // Initialize runtime config with default fees; the initializer is the first app authority
+    let config = &mut ctx.accounts.config;
+    **config = ProgramConfig::new(ctx.accounts.authority.key(), ctx.bumps.config);
+    
+    // Start with no points issuers; only the vault authority can grant points
+    let points_issuers = &mut ctx.accounts.points_issuers;
+    points_issuers.issuers = Vec::new();
+    points_issuers.bump = ctx.bumps.points_issuers;
+    
     emit!(ProgramInitialized {
         authority: ctx.accounts.authority.key(),
         vault: vault.key(),
+        config: config.key(),
+    });
+    
+    Ok(())
+}
+
+// Registers a points issuer or updates its cap; points already issued still count against it
+pub fn set_points_issuer(
+    ctx: Context<SetPointsIssuer>,
+    issuer: Pubkey,
+    points_cap: u64,
+) -> Result<()> {
+    let points_issuers = &mut ctx.accounts.points_issuers;
+    points_issuers.bump = ctx.bumps.points_issuers;
+    
+    let points_issued = match points_issuers.find_mut(&issuer) {
+        Some(entry) => {
+            entry.points_cap = points_cap;
+            entry.points_issued
+        }
+        None => {
+            require!(
+                points_issuers.issuers.len() < MAX_POINTS_ISSUERS,
+                CapsuleXError::PointsIssuerRegistryFull
+            );
+            points_issuers.issuers.push(PointsIssuer {
+                issuer,
+                points_cap,
+                points_issued: 0,
+            });
+            0
+        }
+    };
+    
+    emit!(PointsIssuerUpdated {
+        issuer,
+        points_cap,
+        points_issued,
+        authority: ctx.accounts.authority.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn remove_points_issuer(
+    ctx: Context<RemovePointsIssuer>,
+    issuer: Pubkey,
+) -> Result<()> {
+    let points_issuers = &mut ctx.accounts.points_issuers;
+    
+    require!(
+        points_issuers.is_issuer(&issuer),
+        CapsuleXError::PointsIssuerNotRegistered
+    );
+    points_issuers.remove(&issuer);
+    
+    emit!(PointsIssuerRemoved {
+        issuer,
+        authority: ctx.accounts.authority.key(),
     });
     
     Ok(())
@@ -177,8 +354,23 @@ pub struct LeaderboardInitialized {
     pub leaderboard_id: Pubkey,
 }
 
+#[event]
+pub struct PointsIssuerUpdated {
+    pub issuer: Pubkey,
+    pub points_cap: u64,
+    pub points_issued: u64,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct PointsIssuerRemoved {
+    pub issuer: Pubkey,
+    pub authority: Pubkey,
+}
+
 #[event]
 pub struct ProgramInitialized {
     pub authority: Pubkey,
     pub vault: Pubkey,
+    pub config: Pubkey,
 } 
\ No newline at end of file
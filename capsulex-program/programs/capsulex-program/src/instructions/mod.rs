@@ -1,9 +1,33 @@
+pub mod authority;
+pub mod bond;
 pub mod capsule;
+pub mod close;
+pub mod collection;
+pub mod config;
+pub mod earnings;
 pub mod game;
 pub mod nft;
 pub mod leaderboard;
+pub mod oracle;
+pub mod prize;
+pub mod refund;
+pub mod soulbound;
+pub mod treasury;
+pub mod trophy;
 
+pub use authority::*;
+pub use bond::*;
 pub use capsule::*;
+pub use close::*;
+pub use collection::*;
+pub use config::*;
+pub use earnings::*;
 pub use game::*;
 pub use nft::*;
-pub use leaderboard::*; 
\ No newline at end of file
+pub use leaderboard::*;
+pub use oracle::*;
+pub use prize::*;
+pub use refund::*;
+pub use soulbound::*;
+pub use treasury::*;
+pub use trophy::*;
\ No newline at end of file
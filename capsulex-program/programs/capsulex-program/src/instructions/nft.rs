@@ -1,10 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, MintTo};
 use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::Metadata;
 use crate::{
     constants::*, 
     errors::CapsuleXError, 
-    state::{Capsule, Game, ProgramVault, LeaderboardEntry}
+    instructions::collection::{create_collection_item, validate_nft_metadata, CollectionItem},
+    state::{Capsule, Game, ProgramVault, ProgramConfig, LeaderboardEntry, NftKind, TrophyDefinition, WinnerBadge}
 };
 
 #[derive(Accounts)]
@@ -16,13 +18,16 @@ pub struct MintCapsuleNft<'info> {
     #[account(
         mut,
         constraint = capsule.creator == creator.key() @ CapsuleXError::UnauthorizedCreator,
-        constraint = capsule.is_active @ CapsuleXError::CapsuleNotActive
+        constraint = capsule.is_active @ CapsuleXError::CapsuleNotActive,
+        constraint = !capsule.nft_minted @ CapsuleXError::CapsuleNftAlreadyMinted
     )]
     pub capsule: Account<'info, Capsule>,
     
+    // Supply check also covers capsules that minted before nft_minted was tracked
     #[account(
         mut,
-        constraint = nft_mint.key() == capsule.nft_mint
+        constraint = nft_mint.key() == capsule.nft_mint,
+        constraint = nft_mint.supply == 0 @ CapsuleXError::CapsuleNftAlreadyMinted
     )]
     pub nft_mint: Account<'info, Mint>,
     
@@ -41,6 +46,56 @@ pub struct MintCapsuleNft<'info> {
     )]
     pub vault: Account<'info, ProgramVault>,
     
+    /// CHECK: Metadata PDA of the mint, created by the Token Metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA of the mint, created by the Token Metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+    
+    /// CHECK: Capsule collection mint, created by initialize_nft_collection
+    #[account(
+        seeds = [COLLECTION_MINT_SEED, NftKind::Capsule.seed()],
+        bump
+    )]
+    pub collection_mint: UncheckedAccount<'info>,
+    
+    /// CHECK: Metadata PDA of the collection mint; its size is bumped on verification
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), collection_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA of the collection mint
+    #[account(
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), collection_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub collection_master_edition: UncheckedAccount<'info>,
+    
+    /// CHECK: PDA with no data that owns the collections and signs item verification
+    #[account(
+        seeds = [COLLECTION_AUTHORITY_SEED],
+        bump
+    )]
+    pub collection_authority: UncheckedAccount<'info>,
+    
+    pub token_metadata_program: Program<'info, Metadata>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -48,18 +103,25 @@ pub struct MintCapsuleNft<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(badge_type: String, metadata_uri: String)]
 pub struct MintWinnerBadge<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = authority.key() == winner.key() ||
+                     authority.key() == game.creator ||
+                     authority.key() == vault.authority @ CapsuleXError::UnauthorizedBadgeMinter
+    )]
     pub authority: Signer<'info>,
     
     /// CHECK: This is the winner who will receive the badge
     #[account(mut)]
     pub winner: AccountInfo<'info>,
     
+    // Every entry in game.winners can claim one badge for its placement
     #[account(
+        mut,
         constraint = game.winner_found @ CapsuleXError::GameNotEnded,
-        constraint = game.winner == Some(winner.key()) @ CapsuleXError::NotEligibleForReward
+        constraint = game.is_winner(&winner.key()) @ CapsuleXError::NotEligibleForReward,
+        constraint = !game.placement_of(&winner.key()).is_some_and(|placement| game.is_badged(placement)) @ CapsuleXError::BadgeAlreadyMinted
     )]
     pub game: Account<'info, Game>,
     
@@ -81,6 +143,15 @@ pub struct MintWinnerBadge<'info> {
     )]
     pub winner_token_account: Account<'info, TokenAccount>,
     
+    #[account(
+        init,
+        payer = authority,
+        space = WinnerBadge::LEN,
+        seeds = [WINNER_BADGE_SEED, game.key().as_ref(), winner.key().as_ref()],
+        bump
+    )]
+    pub winner_badge: Account<'info, WinnerBadge>,
+    
     #[account(
         mut,
         seeds = [VAULT_SEED],
@@ -88,6 +159,63 @@ pub struct MintWinnerBadge<'info> {
     )]
     pub vault: Account<'info, ProgramVault>,
     
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.soulbound_nfts @ CapsuleXError::SoulboundNftsRequired
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// CHECK: Metadata PDA of the mint, created by the Token Metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), badge_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA of the mint, created by the Token Metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), badge_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+    
+    /// CHECK: Badge collection mint, created by initialize_nft_collection
+    #[account(
+        seeds = [COLLECTION_MINT_SEED, NftKind::Badge.seed()],
+        bump
+    )]
+    pub collection_mint: UncheckedAccount<'info>,
+    
+    /// CHECK: Metadata PDA of the collection mint; its size is bumped on verification
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), collection_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA of the collection mint
+    #[account(
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), collection_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub collection_master_edition: UncheckedAccount<'info>,
+    
+    /// CHECK: PDA with no data that owns the collections and signs item verification
+    #[account(
+        seeds = [COLLECTION_AUTHORITY_SEED],
+        bump
+    )]
+    pub collection_authority: UncheckedAccount<'info>,
+    
+    pub token_metadata_program: Program<'info, Metadata>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -95,7 +223,7 @@ pub struct MintWinnerBadge<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(trophy_type: String, metadata_uri: String)]
+#[instruction(trophy_type: String)]
 pub struct MintTrophyNft<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -109,12 +237,24 @@ pub struct MintTrophyNft<'info> {
     )]
     pub leaderboard: Account<'info, LeaderboardEntry>,
     
+    #[account(
+        mut,
+        seeds = [TROPHY_DEFINITION_SEED, trophy_type.as_bytes()],
+        bump = trophy_definition.bump
+    )]
+    pub trophy_definition: Account<'info, TrophyDefinition>,
+    
     #[account(
         init,
         payer = authority,
         mint::decimals = 0,
         mint::authority = authority,
-        seeds = [TROPHY_MINT_SEED, user.key().as_ref(), trophy_type.as_bytes()],
+        seeds = [
+            TROPHY_MINT_SEED,
+            user.key().as_ref(),
+            trophy_definition.key().as_ref(),
+            &trophy_definition.season.to_le_bytes()
+        ],
         bump
     )]
     pub trophy_mint: Account<'info, Mint>,
@@ -134,34 +274,147 @@ pub struct MintTrophyNft<'info> {
     )]
     pub vault: Account<'info, ProgramVault>,
     
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.soulbound_nfts @ CapsuleXError::SoulboundNftsRequired
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// CHECK: Metadata PDA of the mint, created by the Token Metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), trophy_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA of the mint, created by the Token Metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), trophy_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+    
+    /// CHECK: Trophy collection mint, created by initialize_nft_collection
+    #[account(
+        seeds = [COLLECTION_MINT_SEED, NftKind::Trophy.seed()],
+        bump
+    )]
+    pub collection_mint: UncheckedAccount<'info>,
+    
+    /// CHECK: Metadata PDA of the collection mint; its size is bumped on verification
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), collection_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA of the collection mint
+    #[account(
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), collection_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub collection_master_edition: UncheckedAccount<'info>,
+    
+    /// CHECK: PDA with no data that owns the collections and signs item verification
+    #[account(
+        seeds = [COLLECTION_AUTHORITY_SEED],
+        bump
+    )]
+    pub collection_authority: UncheckedAccount<'info>,
+    
+    pub token_metadata_program: Program<'info, Metadata>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+pub(crate) fn validate_reward_nft(reward_type: &str, metadata_uri: &str) -> Result<()> {
+    // Validate badge or trophy type length
+    require!(
+        reward_type.len() <= MAX_BADGE_TYPE_LENGTH,
+        CapsuleXError::BadgeTypeTooLong
+    );
+    
+    // Validate metadata URI length
+    require!(
+        metadata_uri.len() <= MAX_METADATA_URI_LENGTH,
+        CapsuleXError::MetadataUriTooLong
+    );
+    
+    Ok(())
+}
+
+// The definition's state and criteria decide eligibility
+pub(crate) fn check_trophy_eligibility(
+    trophy_definition: &TrophyDefinition,
+    leaderboard: &LeaderboardEntry,
+    now: i64,
+) -> Result<()> {
+    require!(trophy_definition.is_active, CapsuleXError::TrophyDefinitionInactive);
+    require!(trophy_definition.is_in_season(now), CapsuleXError::TrophySeasonNotOpen);
+    require!(!trophy_definition.is_sold_out(), CapsuleXError::TrophySupplyExhausted);
+    require!(
+        trophy_definition.criteria.is_met_by(leaderboard),
+        CapsuleXError::NotEligibleForReward
+    );
+    Ok(())
+}
+
+pub(crate) fn collect_mint_fee<'info>(
+    payer: &AccountInfo<'info>,
+    vault: &mut Account<'info, ProgramVault>,
+    system_program: &AccountInfo<'info>,
+    fee_amount: u64,
+) -> Result<()> {
+    let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
+        &payer.key(),
+        &vault.key(),
+        fee_amount,
+    );
+    
+    anchor_lang::solana_program::program::invoke(
+        &transfer_instruction,
+        &[
+            payer.clone(),
+            vault.to_account_info(),
+            system_program.clone(),
+        ],
+    )?;
+    
+    vault.add_fees(fee_amount);
+    Ok(())
+}
+
 pub fn mint_capsule_nft(
     ctx: Context<MintCapsuleNft>,
     name: String,
     symbol: String,
     uri: String,
 ) -> Result<()> {
-    // Validate metadata URI length
-    require!(
-        uri.len() <= MAX_METADATA_URI_LENGTH,
-        CapsuleXError::MetadataUriTooLong
-    );
+    validate_nft_metadata(&name, &symbol, &uri)?;
     
     // Mint 1 NFT to the creator
     let capsule = &ctx.accounts.capsule;
     let bump_seed = [capsule.bump];
+    let seed_bytes = capsule.seed_bytes(); // Same seeds as CreateCapsule
     let seeds = &[
         CAPSULE_SEED,
         capsule.creator.as_ref(),
-        &capsule.reveal_date.to_le_bytes(), // ✅ Fixed: use reveal_date like CreateCapsule
+        &seed_bytes,
         &bump_seed,
     ];
-    let signer_seeds = &[&seeds[..]];
+    let authority_bump = [ctx.bumps.collection_authority];
+    let authority_seeds = &[COLLECTION_AUTHORITY_SEED, &authority_bump];
+    let signer_seeds = &[&seeds[..], &authority_seeds[..]];
     
     let mint_to_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
@@ -175,6 +428,31 @@ pub fn mint_capsule_nft(
     
     token::mint_to(mint_to_ctx, 1)?;
     
+    // The capsule PDA is mint and update authority; the collection authority verifies
+    let capsule_info = ctx.accounts.capsule.to_account_info();
+    create_collection_item(
+        CollectionItem {
+            payer: &ctx.accounts.creator.to_account_info(),
+            mint: &ctx.accounts.nft_mint.to_account_info(),
+            mint_authority: &capsule_info,
+            update_authority: &capsule_info,
+            metadata: &ctx.accounts.metadata.to_account_info(),
+            master_edition: &ctx.accounts.master_edition.to_account_info(),
+            collection_mint: &ctx.accounts.collection_mint.to_account_info(),
+            collection_metadata: &ctx.accounts.collection_metadata.to_account_info(),
+            collection_master_edition: &ctx.accounts.collection_master_edition.to_account_info(),
+            collection_authority: &ctx.accounts.collection_authority.to_account_info(),
+            token_metadata_program: &ctx.accounts.token_metadata_program.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            rent: &ctx.accounts.rent.to_account_info(),
+        },
+        name.clone(),
+        symbol.clone(),
+        uri.clone(),
+        signer_seeds,
+    )?;
+    
     emit!(CapsuleNftMinted {
         capsule_id: capsule.key(),
         nft_mint: ctx.accounts.nft_mint.key(),
@@ -184,46 +462,33 @@ pub fn mint_capsule_nft(
         uri,
     });
     
+    // The master edition now holds the mint authority; the flag rejects repeat calls up front
+    ctx.accounts.capsule.nft_minted = true;
+    
     Ok(())
 }
 
 pub fn mint_winner_badge(
     ctx: Context<MintWinnerBadge>,
-    badge_type: String,
     metadata_uri: String,
 ) -> Result<()> {
-    // Validate badge type length
-    require!(
-        badge_type.len() <= MAX_BADGE_TYPE_LENGTH,
-        CapsuleXError::BadgeTypeTooLong
-    );
-    
-    // Validate metadata URI length
-    require!(
-        metadata_uri.len() <= MAX_METADATA_URI_LENGTH,
-        CapsuleXError::MetadataUriTooLong
-    );
+    let winner = ctx.accounts.winner.key();
+    let game = &mut ctx.accounts.game;
+    let placement = game
+        .placement_of(&winner)
+        .ok_or(CapsuleXError::NotEligibleForReward)?;
+    let badge_type = WinnerBadge::badge_type_for(placement);
+    validate_reward_nft(&badge_type, &metadata_uri)?;
+    game.mark_badged(placement);
     
     // Collect badge minting fee
-    let fee_amount = BADGE_MINT_FEE;
-    
-    let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
-        &ctx.accounts.authority.key(),
-        &ctx.accounts.vault.key(),
-        fee_amount,
-    );
-    
-    anchor_lang::solana_program::program::invoke(
-        &transfer_instruction,
-        &[
-            ctx.accounts.authority.to_account_info(),
-            ctx.accounts.vault.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
+    collect_mint_fee(
+        &ctx.accounts.authority.to_account_info(),
+        &mut ctx.accounts.vault,
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.config.badge_mint_fee,
     )?;
     
-    ctx.accounts.vault.add_fees(fee_amount);
-    
     // Mint 1 badge NFT to the winner
     let mint_to_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
@@ -236,12 +501,50 @@ pub fn mint_winner_badge(
     
     token::mint_to(mint_to_ctx, 1)?;
     
+    // The caller holds the mint authority until the master edition takes it over
+    let authority_bump = [ctx.bumps.collection_authority];
+    let authority_seeds = &[COLLECTION_AUTHORITY_SEED, &authority_bump];
+    let collection_authority = ctx.accounts.collection_authority.to_account_info();
+    create_collection_item(
+        CollectionItem {
+            payer: &ctx.accounts.authority.to_account_info(),
+            mint: &ctx.accounts.badge_mint.to_account_info(),
+            mint_authority: &ctx.accounts.authority.to_account_info(),
+            update_authority: &collection_authority,
+            metadata: &ctx.accounts.metadata.to_account_info(),
+            master_edition: &ctx.accounts.master_edition.to_account_info(),
+            collection_mint: &ctx.accounts.collection_mint.to_account_info(),
+            collection_metadata: &ctx.accounts.collection_metadata.to_account_info(),
+            collection_master_edition: &ctx.accounts.collection_master_edition.to_account_info(),
+            collection_authority: &collection_authority,
+            token_metadata_program: &ctx.accounts.token_metadata_program.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            rent: &ctx.accounts.rent.to_account_info(),
+        },
+        badge_type.clone(),
+        BADGE_NFT_SYMBOL.to_string(),
+        metadata_uri.clone(),
+        &[&authority_seeds[..]],
+    )?;
+    
+    let winner_badge = &mut ctx.accounts.winner_badge;
+    winner_badge.game = ctx.accounts.game.key();
+    winner_badge.winner = winner;
+    winner_badge.badge_mint = ctx.accounts.badge_mint.key();
+    winner_badge.placement = placement;
+    winner_badge.badge_type = badge_type.clone();
+    winner_badge.minted_at = Clock::get()?.unix_timestamp;
+    winner_badge.bump = ctx.bumps.winner_badge;
+    
     emit!(WinnerBadgeMinted {
         game_id: ctx.accounts.game.key(),
-        winner: ctx.accounts.winner.key(),
+        winner,
         badge_mint: ctx.accounts.badge_mint.key(),
         badge_type,
         metadata_uri,
+        placement,
+        soulbound: false,
     });
     
     Ok(())
@@ -250,53 +553,25 @@ pub fn mint_winner_badge(
 pub fn mint_trophy_nft(
     ctx: Context<MintTrophyNft>,
     trophy_type: String,
-    metadata_uri: String,
 ) -> Result<()> {
-    // Validate trophy type length
-    require!(
-        trophy_type.len() <= MAX_BADGE_TYPE_LENGTH,
-        CapsuleXError::BadgeTypeTooLong
-    );
-    
-    // Validate metadata URI length
-    require!(
-        metadata_uri.len() <= MAX_METADATA_URI_LENGTH,
-        CapsuleXError::MetadataUriTooLong
-    );
-    
-    let leaderboard = &ctx.accounts.leaderboard;
-    
-    // Check eligibility based on trophy type
-    let eligible = match trophy_type.as_str() {
-        "winner" => leaderboard.games_won >= 1,
-        "veteran" => leaderboard.games_played >= 10,
-        "creator" => leaderboard.capsules_created >= 5,
-        "champion" => leaderboard.games_won >= 10,
-        _ => false,
-    };
-    
-    require!(eligible, CapsuleXError::NotEligibleForReward);
+    let trophy_definition = &mut ctx.accounts.trophy_definition;
+    check_trophy_eligibility(
+        trophy_definition,
+        &ctx.accounts.leaderboard,
+        Clock::get()?.unix_timestamp,
+    )?;
+    let serial = trophy_definition.record_mint();
+    let metadata_uri = trophy_definition.metadata_uri(serial);
+    validate_reward_nft(&trophy_type, &metadata_uri)?;
     
     // Collect trophy minting fee
-    let fee_amount = TROPHY_MINT_FEE;
-    
-    let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
-        &ctx.accounts.authority.key(),
-        &ctx.accounts.vault.key(),
-        fee_amount,
-    );
-    
-    anchor_lang::solana_program::program::invoke(
-        &transfer_instruction,
-        &[
-            ctx.accounts.authority.to_account_info(),
-            ctx.accounts.vault.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
+    collect_mint_fee(
+        &ctx.accounts.authority.to_account_info(),
+        &mut ctx.accounts.vault,
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.config.trophy_mint_fee,
     )?;
     
-    ctx.accounts.vault.add_fees(fee_amount);
-    
     // Mint 1 trophy NFT to the user
     let mint_to_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
@@ -309,11 +584,40 @@ pub fn mint_trophy_nft(
     
     token::mint_to(mint_to_ctx, 1)?;
     
+    // The caller holds the mint authority until the master edition takes it over
+    let authority_bump = [ctx.bumps.collection_authority];
+    let authority_seeds = &[COLLECTION_AUTHORITY_SEED, &authority_bump];
+    let collection_authority = ctx.accounts.collection_authority.to_account_info();
+    create_collection_item(
+        CollectionItem {
+            payer: &ctx.accounts.authority.to_account_info(),
+            mint: &ctx.accounts.trophy_mint.to_account_info(),
+            mint_authority: &ctx.accounts.authority.to_account_info(),
+            update_authority: &collection_authority,
+            metadata: &ctx.accounts.metadata.to_account_info(),
+            master_edition: &ctx.accounts.master_edition.to_account_info(),
+            collection_mint: &ctx.accounts.collection_mint.to_account_info(),
+            collection_metadata: &ctx.accounts.collection_metadata.to_account_info(),
+            collection_master_edition: &ctx.accounts.collection_master_edition.to_account_info(),
+            collection_authority: &collection_authority,
+            token_metadata_program: &ctx.accounts.token_metadata_program.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            rent: &ctx.accounts.rent.to_account_info(),
+        },
+        trophy_type.clone(),
+        TROPHY_NFT_SYMBOL.to_string(),
+        metadata_uri.clone(),
+        &[&authority_seeds[..]],
+    )?;
+    
     emit!(TrophyNftMinted {
         user: ctx.accounts.user.key(),
         trophy_mint: ctx.accounts.trophy_mint.key(),
         trophy_type,
         metadata_uri,
+        serial,
+        soulbound: false,
     });
     
     Ok(())
@@ -336,6 +640,8 @@ pub struct WinnerBadgeMinted {
     pub badge_mint: Pubkey,
     pub badge_type: String,
     pub metadata_uri: String,
+    pub placement: u8,
+    pub soulbound: bool,
 }
 
 #[event]
@@ -344,4 +650,6 @@ pub struct TrophyNftMinted {
     pub trophy_mint: Pubkey,
     pub trophy_type: String,
     pub metadata_uri: String,
+    pub serial: u32, // Position among all trophies minted from the definition
+    pub soulbound: bool,
 } 
\ No newline at end of file
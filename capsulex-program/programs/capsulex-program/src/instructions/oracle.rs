@@ -0,0 +1,288 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::CapsuleXError,
+    state::{OracleConfig, OracleKey, ProgramVault, UsedNonce}
+};
+
+#[derive(Accounts)]
+pub struct AddOracle<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        constraint = vault.authority == authority.key() @ CapsuleXError::InvalidProgramAuthority
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = OracleConfig::LEN,
+        seeds = [ORACLE_CONFIG_SEED],
+        bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageOracle<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        constraint = vault.authority == authority.key() @ CapsuleXError::InvalidProgramAuthority
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    #[account(
+        mut,
+        seeds = [ORACLE_CONFIG_SEED],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+}
+
+#[derive(Accounts)]
+pub struct CloseUsedNonce<'info> {
+    pub closer: Signer<'info>,
+
+    /// CHECK: Original rent payer, validated against the nonce record
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [USED_NONCE_SEED, used_nonce.nonce_hash.as_ref()],
+        bump = used_nonce.bump,
+        has_one = payer @ CapsuleXError::InvalidAccountOwner,
+        close = payer
+    )]
+    pub used_nonce: Account<'info, UsedNonce>,
+}
+
+fn register_oracle(
+    oracle_config: &mut OracleConfig,
+    public_key: Pubkey,
+    activated_at: i64,
+    expires_at: Option<i64>,
+    now: i64,
+) -> Result<()> {
+    if let Some(expires_at) = expires_at {
+        require!(
+            expires_at > activated_at && expires_at > now,
+            CapsuleXError::InvalidOracleValidity
+        );
+    }
+
+    oracle_config.prune_expired(now);
+    require!(
+        oracle_config.find(&public_key).is_none(),
+        CapsuleXError::OracleAlreadyRegistered
+    );
+    require!(
+        oracle_config.oracles.len() < MAX_ORACLE_KEYS,
+        CapsuleXError::OracleRegistryFull
+    );
+
+    oracle_config.oracles.push(OracleKey {
+        public_key,
+        activated_at,
+        expires_at,
+    });
+
+    Ok(())
+}
+
+pub fn add_oracle(
+    ctx: Context<AddOracle>,
+    public_key: Pubkey,
+    activated_at: Option<i64>,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let activated_at = activated_at.unwrap_or(now);
+
+    let oracle_config = &mut ctx.accounts.oracle_config;
+    oracle_config.bump = ctx.bumps.oracle_config;
+
+    register_oracle(oracle_config, public_key, activated_at, expires_at, now)?;
+
+    emit!(OracleAdded {
+        public_key,
+        activated_at,
+        expires_at,
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+pub fn revoke_oracle(
+    ctx: Context<ManageOracle>,
+    public_key: Pubkey,
+) -> Result<()> {
+    let oracle_config = &mut ctx.accounts.oracle_config;
+
+    require!(
+        oracle_config.find(&public_key).is_some(),
+        CapsuleXError::OracleNotRegistered
+    );
+
+    // Revocation is immediate - verdicts signed by this key stop being accepted
+    oracle_config.remove(&public_key);
+
+    emit!(OracleRevoked {
+        public_key,
+        authority: ctx.accounts.authority.key(),
+        revoked_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn rotate_oracle(
+    ctx: Context<ManageOracle>,
+    old_public_key: Pubkey,
+    new_public_key: Pubkey,
+    grace_period: i64,
+) -> Result<()> {
+    // Old key stays valid for a short overlap so in-flight verdicts can still land
+    require!(
+        (0..=MAX_ORACLE_ROTATION_GRACE).contains(&grace_period),
+        CapsuleXError::InvalidOracleValidity
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let old_expires_at = now + grace_period;
+    let oracle_config = &mut ctx.accounts.oracle_config;
+
+    let old_oracle = oracle_config
+        .find_mut(&old_public_key)
+        .ok_or(CapsuleXError::OracleNotRegistered)?;
+    old_oracle.expires_at = Some(
+        old_oracle
+            .expires_at
+            .map_or(old_expires_at, |expires_at| expires_at.min(old_expires_at)),
+    );
+
+    register_oracle(oracle_config, new_public_key, now, None, now)?;
+
+    emit!(OracleRotated {
+        old_public_key,
+        new_public_key,
+        old_expires_at,
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+pub fn add_oracle_relayer(
+    ctx: Context<ManageOracle>,
+    relayer: Pubkey,
+) -> Result<()> {
+    let oracle_config = &mut ctx.accounts.oracle_config;
+
+    require!(
+        !oracle_config.is_relayer(&relayer),
+        CapsuleXError::RelayerAlreadyRegistered
+    );
+    require!(
+        oracle_config.relayers.len() < MAX_ORACLE_RELAYERS,
+        CapsuleXError::RelayerRegistryFull
+    );
+    oracle_config.relayers.push(relayer);
+
+    emit!(OracleRelayerAdded {
+        relayer,
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+pub fn remove_oracle_relayer(
+    ctx: Context<ManageOracle>,
+    relayer: Pubkey,
+) -> Result<()> {
+    let oracle_config = &mut ctx.accounts.oracle_config;
+
+    require!(
+        oracle_config.is_relayer(&relayer),
+        CapsuleXError::RelayerNotRegistered
+    );
+    oracle_config.relayers.retain(|registered| *registered != relayer);
+
+    emit!(OracleRelayerRemoved {
+        relayer,
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+pub fn close_used_nonce(ctx: Context<CloseUsedNonce>) -> Result<()> {
+    let used_nonce = &ctx.accounts.used_nonce;
+
+    // Only reclaimable once the signed verdict would fail the timestamp check anyway
+    require!(
+        used_nonce.is_expired(Clock::get()?.unix_timestamp),
+        CapsuleXError::OracleNonceNotExpired
+    );
+
+    emit!(UsedNonceClosed {
+        nonce_hash: used_nonce.nonce_hash,
+        payer: used_nonce.payer,
+        closer: ctx.accounts.closer.key(),
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OracleAdded {
+    pub public_key: Pubkey,
+    pub activated_at: i64,
+    pub expires_at: Option<i64>,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct OracleRevoked {
+    pub public_key: Pubkey,
+    pub authority: Pubkey,
+    pub revoked_at: i64,
+}
+
+#[event]
+pub struct OracleRotated {
+    pub old_public_key: Pubkey,
+    pub new_public_key: Pubkey,
+    pub old_expires_at: i64,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct OracleRelayerAdded {
+    pub relayer: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct OracleRelayerRemoved {
+    pub relayer: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct UsedNonceClosed {
+    pub nonce_hash: [u8; 32],
+    pub payer: Pubkey,
+    pub closer: Pubkey,
+}
@@ -0,0 +1,746 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, TransferChecked};
+use crate::{
+    constants::*,
+    errors::CapsuleXError,
+    state::{Capsule, Game, PrizeFunder, PrizePool, ProgramConfig}
+};
+
+#[derive(Accounts)]
+pub struct CreatePrizePool<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        constraint = game.creator == creator.key() @ CapsuleXError::UnauthorizedCreator,
+        constraint = game.is_active @ CapsuleXError::GameNotActive
+    )]
+    pub game: Account<'info, Game>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = PrizePool::LEN,
+        seeds = [PRIZE_POOL_SEED, game.key().as_ref()],
+        bump
+    )]
+    pub prize_pool: Account<'info, PrizePool>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateTokenPrizePool<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        constraint = game.creator == creator.key() @ CapsuleXError::UnauthorizedCreator,
+        constraint = game.is_active @ CapsuleXError::GameNotActive
+    )]
+    pub game: Account<'info, Game>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = PrizePool::LEN,
+        seeds = [PRIZE_POOL_SEED, game.key().as_ref()],
+        bump
+    )]
+    pub prize_pool: Account<'info, PrizePool>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = prize_pool,
+        seeds = [PRIZE_ESCROW_SEED, prize_pool.key().as_ref()],
+        bump
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Anyone can sponsor an active game
+#[derive(Accounts)]
+pub struct FundPrizePool<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(constraint = game.is_active @ CapsuleXError::GameNotActive)]
+    pub game: Account<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [PRIZE_POOL_SEED, game.key().as_ref()],
+        bump = prize_pool.bump,
+        constraint = prize_pool.mint.is_none() @ CapsuleXError::PrizeMintMismatch
+    )]
+    pub prize_pool: Account<'info, PrizePool>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundTokenPrizePool<'info> {
+    pub funder: Signer<'info>,
+
+    #[account(constraint = game.is_active @ CapsuleXError::GameNotActive)]
+    pub game: Account<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [PRIZE_POOL_SEED, game.key().as_ref()],
+        bump = prize_pool.bump,
+        constraint = prize_pool.mint == Some(mint.key()) @ CapsuleXError::PrizeMintMismatch
+    )]
+    pub prize_pool: Account<'info, PrizePool>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = funder
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [PRIZE_ESCROW_SEED, prize_pool.key().as_ref()],
+        bump
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPrize<'info> {
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    #[account(constraint = !game.is_active @ CapsuleXError::GameNotEnded)]
+    pub game: Account<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [PRIZE_POOL_SEED, game.key().as_ref()],
+        bump = prize_pool.bump,
+        constraint = prize_pool.mint.is_none() @ CapsuleXError::PrizeMintMismatch
+    )]
+    pub prize_pool: Account<'info, PrizePool>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTokenPrize<'info> {
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    #[account(constraint = !game.is_active @ CapsuleXError::GameNotEnded)]
+    pub game: Account<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [PRIZE_POOL_SEED, game.key().as_ref()],
+        bump = prize_pool.bump,
+        constraint = prize_pool.mint == Some(mint.key()) @ CapsuleXError::PrizeMintMismatch
+    )]
+    pub prize_pool: Account<'info, PrizePool>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [PRIZE_ESCROW_SEED, prize_pool.key().as_ref()],
+        bump
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = winner,
+        associated_token::mint = mint,
+        associated_token::authority = winner
+    )]
+    pub winner_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// Refunds are available once the game has ended without any winner, or once it has gone
+// uncompleted for the refund grace period after the reveal date
+#[derive(Accounts)]
+pub struct RefundPrize<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub game: Account<'info, Game>,
+
+    #[account(constraint = capsule.key() == game.capsule_id @ CapsuleXError::InvalidGameAccount)]
+    pub capsule: Account<'info, Capsule>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [PRIZE_POOL_SEED, game.key().as_ref()],
+        bump = prize_pool.bump,
+        constraint = prize_pool.mint.is_none() @ CapsuleXError::PrizeMintMismatch
+    )]
+    pub prize_pool: Account<'info, PrizePool>,
+}
+
+#[derive(Accounts)]
+pub struct RefundTokenPrize<'info> {
+    pub funder: Signer<'info>,
+
+    pub game: Account<'info, Game>,
+
+    #[account(constraint = capsule.key() == game.capsule_id @ CapsuleXError::InvalidGameAccount)]
+    pub capsule: Account<'info, Capsule>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [PRIZE_POOL_SEED, game.key().as_ref()],
+        bump = prize_pool.bump,
+        constraint = prize_pool.mint == Some(mint.key()) @ CapsuleXError::PrizeMintMismatch
+    )]
+    pub prize_pool: Account<'info, PrizePool>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [PRIZE_ESCROW_SEED, prize_pool.key().as_ref()],
+        bump
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = funder
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// Closable once every lamport or token that came in has gone out as a prize or refund
+#[derive(Accounts)]
+pub struct ClosePrizePool<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [PRIZE_POOL_SEED, prize_pool.game.as_ref()],
+        bump = prize_pool.bump,
+        has_one = creator @ CapsuleXError::UnauthorizedCreator,
+        constraint = prize_pool.mint.is_none() @ CapsuleXError::PrizeMintMismatch,
+        constraint = prize_pool.is_settled() @ CapsuleXError::PrizePoolNotSettled
+    )]
+    pub prize_pool: Account<'info, PrizePool>,
+}
+
+#[derive(Accounts)]
+pub struct CloseTokenPrizePool<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [PRIZE_POOL_SEED, prize_pool.game.as_ref()],
+        bump = prize_pool.bump,
+        has_one = creator @ CapsuleXError::UnauthorizedCreator,
+        constraint = prize_pool.mint == Some(mint.key()) @ CapsuleXError::PrizeMintMismatch,
+        constraint = prize_pool.is_settled() @ CapsuleXError::PrizePoolNotSettled
+    )]
+    pub prize_pool: Account<'info, PrizePool>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [PRIZE_ESCROW_SEED, prize_pool.key().as_ref()],
+        bump
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    // Receives tokens sent to the escrow outside fund_token_prize_pool
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = creator
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+fn validate_split(split_bps: &[u16], game: &Game) -> Result<()> {
+    require!(
+        !split_bps.is_empty()
+            && split_bps.len() <= MAX_PRIZE_SPLITS
+            && split_bps.len() <= game.max_winners as usize,
+        CapsuleXError::InvalidPrizeSplit
+    );
+    require!(
+        split_bps.iter().all(|bps| *bps > 0),
+        CapsuleXError::InvalidPrizeSplit
+    );
+
+    let total_bps: u64 = split_bps.iter().map(|bps| *bps as u64).sum();
+    require!(
+        total_bps == BASIS_POINTS_DENOMINATOR,
+        CapsuleXError::InvalidPrizeSplit
+    );
+
+    Ok(())
+}
+
+// Repeat contributions from the same funder accumulate in a single entry. Sponsors cannot take
+// the creator's reserved slot, and a new sponsor entry costs at least `min_new_entry`.
+fn record_funding(prize_pool: &mut PrizePool, funder: Pubkey, amount: u64, min_new_entry: u64) -> Result<()> {
+    require!(amount > 0, CapsuleXError::InvalidPrizeAmount);
+
+    let creator = prize_pool.creator;
+    let reserved_slots = if funder == creator || prize_pool.funders.iter().any(|entry| entry.funder == creator) {
+        0
+    } else {
+        1
+    };
+    match prize_pool.funders.iter_mut().find(|entry| entry.funder == funder) {
+        Some(entry) => {
+            entry.amount = entry.amount
+                .checked_add(amount)
+                .ok_or(CapsuleXError::ArithmeticOverflow)?;
+        }
+        None => {
+            require!(
+                prize_pool.funders.len() + reserved_slots < MAX_PRIZE_FUNDERS,
+                CapsuleXError::PrizeFunderLimitReached
+            );
+            require!(
+                funder == creator || amount >= min_new_entry,
+                CapsuleXError::PrizeContributionTooSmall
+            );
+            prize_pool.funders.push(PrizeFunder {
+                funder,
+                amount,
+                refunded: false,
+            });
+        }
+    }
+
+    prize_pool.total_funded = prize_pool.total_funded
+        .checked_add(amount)
+        .ok_or(CapsuleXError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+// Marks the winner's share as paid and returns (placement, amount)
+fn take_prize(prize_pool: &mut PrizePool, game: &Game, winner: Pubkey) -> Result<(u32, u64)> {
+    let placement = game.winners
+        .iter()
+        .position(|entry| *entry == winner)
+        .ok_or(CapsuleXError::NotEligibleForReward)?;
+    require!(!prize_pool.has_claimed(&winner), CapsuleXError::PrizeAlreadyClaimed);
+    require!(!prize_pool.has_refunds(), CapsuleXError::PrizePoolRefunded);
+
+    let amount = prize_pool.prize_for(placement, game.winners.len());
+    require!(amount > 0, CapsuleXError::NotEligibleForReward);
+
+    prize_pool.claimed.push(winner);
+    prize_pool.total_paid += amount;
+
+    Ok((placement as u32, amount))
+}
+
+// Once a winner is recorded the pool belongs to the winners, completed or not. Otherwise funders
+// can take their contributions back once no guess can still be verified as a winner: no new guess
+// can come in and every guess is settled, or the longest verification window (and the refund grace
+// period) after the reveal date has passed. Completing a game with guesses does not open refunds by itself.
+fn check_refundable(game: &Game, capsule: &Capsule, config: &ProgramConfig) -> Result<()> {
+    require!(game.winners.is_empty(), CapsuleXError::PrizeRefundUnavailable);
+
+    let now = Clock::get()?.unix_timestamp;
+    let commit_phase_closed = now >= capsule.reveal_date || !game.is_active;
+    let verification_closed =
+        now > capsule.reveal_date + MAX_VERIFICATION_WINDOW.max(config.refund_grace_period);
+    require!(
+        (commit_phase_closed && game.all_guesses_settled()) || verification_closed,
+        CapsuleXError::GameNotEnded
+    );
+
+    Ok(())
+}
+
+// Once funders have started taking their money back the pot can no longer be claimed, so
+// no new winner may be verified and the game may not be completed over it
+pub(crate) fn require_no_prize_refunds(prize_pool: &AccountInfo) -> Result<()> {
+    if !prize_pool.data_is_empty() {
+        let prize_pool = PrizePool::try_deserialize(&mut &prize_pool.data.borrow()[..])?;
+        require!(!prize_pool.has_refunds(), CapsuleXError::PrizePoolRefunded);
+    }
+
+    Ok(())
+}
+
+// Marks the funder's contribution as returned and returns the amount
+fn take_refund(prize_pool: &mut PrizePool, funder: Pubkey) -> Result<u64> {
+    let entry = prize_pool.funders
+        .iter_mut()
+        .find(|entry| entry.funder == funder && !entry.refunded)
+        .ok_or(CapsuleXError::PrizeRefundUnavailable)?;
+    entry.refunded = true;
+
+    let amount = entry.amount;
+    prize_pool.total_paid += amount;
+
+    Ok(amount)
+}
+
+// The pool is program owned, so lamports are moved directly instead of via a system transfer
+fn pay_from_pool(prize_pool: &AccountInfo, recipient: &AccountInfo, amount: u64) -> Result<()> {
+    let pool_balance = prize_pool.lamports();
+    let recipient_balance = recipient.lamports();
+
+    **prize_pool.try_borrow_mut_lamports()? = pool_balance
+        .checked_sub(amount)
+        .ok_or(CapsuleXError::ArithmeticOverflow)?;
+    **recipient.try_borrow_mut_lamports()? = recipient_balance
+        .checked_add(amount)
+        .ok_or(CapsuleXError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+fn pay_from_escrow<'info>(
+    prize_pool: &Account<'info, PrizePool>,
+    prize_escrow: &Account<'info, TokenAccount>,
+    mint: &Account<'info, Mint>,
+    recipient: AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[PRIZE_POOL_SEED, prize_pool.game.as_ref(), &[prize_pool.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: prize_escrow.to_account_info(),
+                mint: mint.to_account_info(),
+                to: recipient,
+                authority: prize_pool.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        mint.decimals,
+    )
+}
+
+pub fn create_prize_pool(ctx: Context<CreatePrizePool>, split_bps: Vec<u16>) -> Result<()> {
+    validate_split(&split_bps, &ctx.accounts.game)?;
+
+    let prize_pool = &mut ctx.accounts.prize_pool;
+    **prize_pool = PrizePool::new(
+        ctx.accounts.game.key(),
+        ctx.accounts.creator.key(),
+        None,
+        split_bps,
+        ctx.bumps.prize_pool,
+    );
+
+    emit!(PrizePoolCreated {
+        prize_pool: prize_pool.key(),
+        game: prize_pool.game,
+        creator: prize_pool.creator,
+        mint: None,
+        split_bps: prize_pool.split_bps.clone(),
+    });
+
+    Ok(())
+}
+
+pub fn create_token_prize_pool(ctx: Context<CreateTokenPrizePool>, split_bps: Vec<u16>) -> Result<()> {
+    validate_split(&split_bps, &ctx.accounts.game)?;
+
+    let mint = ctx.accounts.mint.key();
+    let prize_pool = &mut ctx.accounts.prize_pool;
+    **prize_pool = PrizePool::new(
+        ctx.accounts.game.key(),
+        ctx.accounts.creator.key(),
+        Some(mint),
+        split_bps,
+        ctx.bumps.prize_pool,
+    );
+
+    emit!(PrizePoolCreated {
+        prize_pool: prize_pool.key(),
+        game: prize_pool.game,
+        creator: prize_pool.creator,
+        mint: Some(mint),
+        split_bps: prize_pool.split_bps.clone(),
+    });
+
+    Ok(())
+}
+
+pub fn fund_prize_pool(ctx: Context<FundPrizePool>, amount: u64) -> Result<()> {
+    let funder = ctx.accounts.funder.key();
+    record_funding(&mut ctx.accounts.prize_pool, funder, amount, MIN_PRIZE_CONTRIBUTION)?;
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.prize_pool.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    emit!(PrizePoolFunded {
+        prize_pool: ctx.accounts.prize_pool.key(),
+        funder,
+        amount,
+        total_funded: ctx.accounts.prize_pool.total_funded,
+    });
+
+    Ok(())
+}
+
+pub fn fund_token_prize_pool(ctx: Context<FundTokenPrizePool>, amount: u64) -> Result<()> {
+    let funder = ctx.accounts.funder.key();
+    // Token amounts have no common scale, so token pools rely on the reserved creator slot alone
+    record_funding(&mut ctx.accounts.prize_pool, funder, amount, 0)?;
+
+    token::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.funder_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.prize_escrow.to_account_info(),
+                authority: ctx.accounts.funder.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    emit!(PrizePoolFunded {
+        prize_pool: ctx.accounts.prize_pool.key(),
+        funder,
+        amount,
+        total_funded: ctx.accounts.prize_pool.total_funded,
+    });
+
+    Ok(())
+}
+
+pub fn claim_prize(ctx: Context<ClaimPrize>) -> Result<()> {
+    let winner = ctx.accounts.winner.key();
+    let (placement, amount) = take_prize(&mut ctx.accounts.prize_pool, &ctx.accounts.game, winner)?;
+
+    pay_from_pool(
+        &ctx.accounts.prize_pool.to_account_info(),
+        &ctx.accounts.winner.to_account_info(),
+        amount,
+    )?;
+
+    emit!(PrizeClaimed {
+        prize_pool: ctx.accounts.prize_pool.key(),
+        winner,
+        placement,
+        amount,
+    });
+
+    Ok(())
+}
+
+pub fn claim_token_prize(ctx: Context<ClaimTokenPrize>) -> Result<()> {
+    let winner = ctx.accounts.winner.key();
+    let (placement, amount) = take_prize(&mut ctx.accounts.prize_pool, &ctx.accounts.game, winner)?;
+
+    pay_from_escrow(
+        &ctx.accounts.prize_pool,
+        &ctx.accounts.prize_escrow,
+        &ctx.accounts.mint,
+        ctx.accounts.winner_token_account.to_account_info(),
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    emit!(PrizeClaimed {
+        prize_pool: ctx.accounts.prize_pool.key(),
+        winner,
+        placement,
+        amount,
+    });
+
+    Ok(())
+}
+
+pub fn refund_prize(ctx: Context<RefundPrize>) -> Result<()> {
+    check_refundable(&ctx.accounts.game, &ctx.accounts.capsule, &ctx.accounts.config)?;
+
+    let funder = ctx.accounts.funder.key();
+    let amount = take_refund(&mut ctx.accounts.prize_pool, funder)?;
+
+    pay_from_pool(
+        &ctx.accounts.prize_pool.to_account_info(),
+        &ctx.accounts.funder.to_account_info(),
+        amount,
+    )?;
+
+    emit!(PrizeRefunded {
+        prize_pool: ctx.accounts.prize_pool.key(),
+        funder,
+        amount,
+    });
+
+    Ok(())
+}
+
+pub fn refund_token_prize(ctx: Context<RefundTokenPrize>) -> Result<()> {
+    check_refundable(&ctx.accounts.game, &ctx.accounts.capsule, &ctx.accounts.config)?;
+
+    let funder = ctx.accounts.funder.key();
+    let amount = take_refund(&mut ctx.accounts.prize_pool, funder)?;
+
+    pay_from_escrow(
+        &ctx.accounts.prize_pool,
+        &ctx.accounts.prize_escrow,
+        &ctx.accounts.mint,
+        ctx.accounts.funder_token_account.to_account_info(),
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    emit!(PrizeRefunded {
+        prize_pool: ctx.accounts.prize_pool.key(),
+        funder,
+        amount,
+    });
+
+    Ok(())
+}
+
+pub fn close_prize_pool(ctx: Context<ClosePrizePool>) -> Result<()> {
+    emit_prize_pool_closed(&ctx.accounts.prize_pool, 0);
+
+    Ok(())
+}
+
+pub fn close_token_prize_pool(ctx: Context<CloseTokenPrizePool>) -> Result<()> {
+    // Anything left was sent straight to the escrow, so it goes to the creator rather than blocking the close
+    let swept = ctx.accounts.prize_escrow.amount;
+    if swept > 0 {
+        pay_from_escrow(
+            &ctx.accounts.prize_pool,
+            &ctx.accounts.prize_escrow,
+            &ctx.accounts.mint,
+            ctx.accounts.creator_token_account.to_account_info(),
+            &ctx.accounts.token_program,
+            swept,
+        )?;
+    }
+
+    let prize_pool = &ctx.accounts.prize_pool;
+    let seeds = &[PRIZE_POOL_SEED, prize_pool.game.as_ref(), &[prize_pool.bump]];
+    let signer_seeds = &[&seeds[..]];
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.prize_escrow.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: prize_pool.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    emit_prize_pool_closed(prize_pool, swept);
+
+    Ok(())
+}
+
+fn emit_prize_pool_closed(prize_pool: &Account<PrizePool>, swept: u64) {
+    emit!(PrizePoolClosed {
+        prize_pool: prize_pool.key(),
+        game: prize_pool.game,
+        creator: prize_pool.creator,
+        total_funded: prize_pool.total_funded,
+        total_paid: prize_pool.total_paid,
+        swept,
+    });
+}
+
+#[event]
+pub struct PrizePoolCreated {
+    pub prize_pool: Pubkey,
+    pub game: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Option<Pubkey>, // None for SOL prizes
+    pub split_bps: Vec<u16>,
+}
+
+#[event]
+pub struct PrizePoolFunded {
+    pub prize_pool: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub total_funded: u64,
+}
+
+#[event]
+pub struct PrizeClaimed {
+    pub prize_pool: Pubkey,
+    pub winner: Pubkey,
+    pub placement: u32, // Index in Game.winners
+    pub amount: u64,
+}
+
+#[event]
+pub struct PrizeRefunded {
+    pub prize_pool: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PrizePoolClosed {
+    pub prize_pool: Pubkey,
+    pub game: Pubkey,
+    pub creator: Pubkey,
+    pub total_funded: u64,
+    pub total_paid: u64,
+    pub swept: u64, // Escrow tokens that arrived outside funding, returned to the creator
+}
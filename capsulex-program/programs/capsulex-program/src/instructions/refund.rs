@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::CapsuleXError,
+    instructions::treasury::{pay_from_vault, withdrawable_balance},
+    state::{BondAccount, Capsule, CreatorEarnings, Game, Guess, ProgramConfig, ProgramVault}
+};
+
+// Permissionless: anyone can return a guesser's fee once the capsule is abandoned
+#[derive(Accounts)]
+pub struct ClaimGuessRefund<'info> {
+    #[account(constraint = !capsule.is_revealed @ CapsuleXError::CapsuleAlreadyRevealed)]
+    pub capsule: Account<'info, Capsule>,
+
+    #[account(
+        mut,
+        constraint = game.capsule_id == capsule.key() @ CapsuleXError::InvalidGameAccount
+    )]
+    pub game: Account<'info, Game>,
+
+    #[account(
+        mut,
+        close = guesser,
+        constraint = guess.game_id == game.key() @ CapsuleXError::InvalidGuessId,
+        constraint = !guess.is_refunded @ CapsuleXError::GuessAlreadyRefunded
+    )]
+    pub guess: Account<'info, Guess>,
+
+    /// CHECK: Receives the refund and the guess rent; must be the guess owner
+    #[account(mut, address = guess.guesser @ CapsuleXError::InvalidAccountOwner)]
+    pub guesser: UncheckedAccount<'info>,
+
+    /// CHECK: Creator bond for the capsule; only gamified capsules created with a bond have one
+    #[account(
+        mut,
+        seeds = [BOND_SEED, capsule.key().as_ref()],
+        bump
+    )]
+    pub bond: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    // Holds the creator's share of the guess fee until it is claimed
+    #[account(
+        mut,
+        seeds = [CREATOR_EARNINGS_SEED, game.creator.as_ref()],
+        bump = creator_earnings.bump
+    )]
+    pub creator_earnings: Account<'info, CreatorEarnings>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn claim_guess_refund(ctx: Context<ClaimGuessRefund>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let config = &ctx.accounts.config;
+    require!(
+        ctx.accounts.capsule.is_abandoned(now, config.refund_grace_period),
+        CapsuleXError::BondGracePeriodActive
+    );
+
+    let amount = ctx.accounts.guess.refundable_fee();
+    require!(amount > 0, CapsuleXError::NothingToRefund);
+
+    let guesser_info = ctx.accounts.guesser.to_account_info();
+
+    // The creator's bond covers the refund first; the vault pays whatever it cannot
+    let bond_info = ctx.accounts.bond.to_account_info();
+    let mut from_bond = 0;
+    if bond_info.owner == &crate::ID && !bond_info.data_is_empty() {
+        let mut bond = BondAccount::try_deserialize(&mut &bond_info.data.borrow()[..])?;
+        from_bond = bond.cover(amount);
+        bond.try_serialize(&mut &mut bond_info.data.borrow_mut()[..])?;
+
+        **bond_info.try_borrow_mut_lamports()? = bond_info
+            .lamports()
+            .checked_sub(from_bond)
+            .ok_or(CapsuleXError::ArithmeticOverflow)?;
+        **guesser_info.try_borrow_mut_lamports()? = guesser_info
+            .lamports()
+            .checked_add(from_bond)
+            .ok_or(CapsuleXError::ArithmeticOverflow)?;
+    }
+
+    // The rest comes from wherever the fee went: the creator's share back out of CreatorEarnings, where
+    // it stays locked until reveal, and the remainder from the vault
+    let remaining = amount - from_bond;
+    let creator_share = ctx.accounts.guess.creator_share_paid.min(remaining);
+    let from_creator = ctx.accounts.creator_earnings.reclaim(creator_share);
+    if from_creator > 0 {
+        let earnings_info = ctx.accounts.creator_earnings.to_account_info();
+        **earnings_info.try_borrow_mut_lamports()? = earnings_info
+            .lamports()
+            .checked_sub(from_creator)
+            .ok_or(CapsuleXError::ArithmeticOverflow)?;
+        **guesser_info.try_borrow_mut_lamports()? = guesser_info
+            .lamports()
+            .checked_add(from_creator)
+            .ok_or(CapsuleXError::ArithmeticOverflow)?;
+    }
+
+    let from_vault = remaining - from_creator;
+    if from_vault > 0 {
+        let vault_info = ctx.accounts.vault.to_account_info();
+        require!(
+            from_vault <= withdrawable_balance(&vault_info)?,
+            CapsuleXError::InsufficientVaultBalance
+        );
+        pay_from_vault(&vault_info, &guesser_info, from_vault)?;
+    }
+
+    ctx.accounts.guess.is_refunded = true;
+    ctx.accounts.game.settle_guess();
+    release_refund_liability(
+        &mut ctx.accounts.game,
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.creator_earnings,
+        &ctx.accounts.guess,
+        from_creator,
+    )?;
+
+    emit!(GuessRefunded {
+        capsule_id: ctx.accounts.capsule.key(),
+        guess_id: ctx.accounts.guess.key(),
+        guesser: ctx.accounts.guesser.key(),
+        amount: from_bond + from_creator + from_vault,
+        from_bond,
+        from_creator,
+        from_vault,
+    });
+
+    Ok(())
+}
+
+// A refunded guess no longer needs the vault's share held back. Only the part of the creator's share
+// that CreatorEarnings paid back leaves its locked balance; a share the bond covered stays locked
+// until release_bond, once every guess on the game is settled.
+fn release_refund_liability(
+    game: &mut Game,
+    config: &mut ProgramConfig,
+    creator_earnings: &mut CreatorEarnings,
+    guess: &Guess,
+    from_creator: u64,
+) -> Result<()> {
+    game.release_vault_fees(guess.vault_share_paid())?;
+    config.release_refundable(guess.vault_share_paid())?;
+
+    game.release_creator_fees(from_creator)?;
+    creator_earnings.unlock(from_creator)
+}
+
+#[event]
+pub struct GuessRefunded {
+    pub capsule_id: Pubkey,
+    pub guess_id: Pubkey,
+    pub guesser: Pubkey,
+    pub amount: u64, // The fee paid, split across the sources below
+    pub from_bond: u64,
+    pub from_creator: u64,
+    pub from_vault: u64,
+}
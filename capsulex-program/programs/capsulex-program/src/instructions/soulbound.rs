@@ -0,0 +1,431 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, AssociatedToken, Create};
+use anchor_spl::token_2022::{
+    self,
+    spl_token_2022::{
+        extension::ExtensionType,
+        instruction::AuthorityType,
+        state::Mint,
+    },
+    InitializeMint2, MintTo, SetAuthority, Token2022,
+};
+use anchor_spl::token_interface::{
+    metadata_pointer_initialize, non_transferable_mint_initialize, token_metadata_initialize,
+    spl_token_metadata_interface::state::TokenMetadata, MetadataPointerInitialize,
+    NonTransferableMintInitialize, TokenMetadataInitialize,
+};
+use crate::{
+    constants::*,
+    errors::CapsuleXError,
+    instructions::nft::{check_trophy_eligibility, collect_mint_fee, validate_reward_nft, TrophyNftMinted, WinnerBadgeMinted},
+    state::{Game, LeaderboardEntry, ProgramConfig, ProgramVault, TrophyDefinition, WinnerBadge},
+    utils::create_pda_account,
+};
+
+// Same mint seeds as MintWinnerBadge, so a winner holds at most one badge per game whichever standard minted it
+#[derive(Accounts)]
+pub struct MintSoulboundBadge<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == winner.key() ||
+                     authority.key() == game.creator ||
+                     authority.key() == vault.authority @ CapsuleXError::UnauthorizedBadgeMinter
+    )]
+    pub authority: Signer<'info>,
+
+    /// CHECK: This is the winner who will receive the badge
+    pub winner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = game.winner_found @ CapsuleXError::GameNotEnded,
+        constraint = game.is_winner(&winner.key()) @ CapsuleXError::NotEligibleForReward,
+        constraint = !game.placement_of(&winner.key()).is_some_and(|placement| game.is_badged(placement)) @ CapsuleXError::BadgeAlreadyMinted
+    )]
+    pub game: Account<'info, Game>,
+
+    /// CHECK: Created in the handler as a Token-2022 mint with NonTransferable and metadata extensions
+    #[account(
+        mut,
+        seeds = [BADGE_MINT_SEED, game.key().as_ref(), winner.key().as_ref()],
+        bump
+    )]
+    pub badge_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Winner's Token-2022 associated token account, created in the handler
+    #[account(
+        mut,
+        seeds = [winner.key().as_ref(), token_program.key().as_ref(), badge_mint.key().as_ref()],
+        bump,
+        seeds::program = associated_token_program.key()
+    )]
+    pub winner_token_account: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = WinnerBadge::LEN,
+        seeds = [WINNER_BADGE_SEED, game.key().as_ref(), winner.key().as_ref()],
+        bump
+    )]
+    pub winner_badge: Account<'info, WinnerBadge>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.soulbound_nfts @ CapsuleXError::SoulboundNftsDisabled
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: PDA with no data; mint authority until the single token is minted and metadata update authority
+    #[account(
+        seeds = [COLLECTION_AUTHORITY_SEED],
+        bump
+    )]
+    pub collection_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(trophy_type: String)]
+pub struct MintSoulboundTrophy<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: This is the user who will receive the trophy
+    pub user: AccountInfo<'info>,
+
+    #[account(
+        constraint = leaderboard.user == user.key() @ CapsuleXError::InvalidAccountOwner
+    )]
+    pub leaderboard: Account<'info, LeaderboardEntry>,
+
+    #[account(
+        mut,
+        seeds = [TROPHY_DEFINITION_SEED, trophy_type.as_bytes()],
+        bump = trophy_definition.bump
+    )]
+    pub trophy_definition: Account<'info, TrophyDefinition>,
+
+    /// CHECK: Created in the handler as a Token-2022 mint with NonTransferable and metadata extensions
+    #[account(
+        mut,
+        seeds = [
+            TROPHY_MINT_SEED,
+            user.key().as_ref(),
+            trophy_definition.key().as_ref(),
+            &trophy_definition.season.to_le_bytes()
+        ],
+        bump
+    )]
+    pub trophy_mint: UncheckedAccount<'info>,
+
+    /// CHECK: User's Token-2022 associated token account, created in the handler
+    #[account(
+        mut,
+        seeds = [user.key().as_ref(), token_program.key().as_ref(), trophy_mint.key().as_ref()],
+        bump,
+        seeds::program = associated_token_program.key()
+    )]
+    pub user_token_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.soulbound_nfts @ CapsuleXError::SoulboundNftsDisabled
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: PDA with no data; mint authority until the single token is minted and metadata update authority
+    #[account(
+        seeds = [COLLECTION_AUTHORITY_SEED],
+        bump
+    )]
+    pub collection_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// Accounts used to create a non-transferable Token-2022 NFT and mint its single token
+pub(crate) struct SoulboundItem<'a, 'info> {
+    pub payer: &'a AccountInfo<'info>,
+    pub mint: &'a AccountInfo<'info>,
+    pub recipient: &'a AccountInfo<'info>,
+    pub recipient_token_account: &'a AccountInfo<'info>,
+    pub collection_authority: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub associated_token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+// The metadata lives in the mint itself (metadata pointer to self) and is updated by the collection authority.
+// Once the token is minted the mint authority is removed, so the supply stays at one.
+pub(crate) fn create_soulbound_nft(
+    item: SoulboundItem<'_, '_>,
+    name: String,
+    symbol: String,
+    uri: String,
+    mint_seeds: &[&[u8]],
+    authority_seeds: &[&[u8]],
+) -> Result<()> {
+    let collection_authority = item.collection_authority.key();
+    let mint_space = ExtensionType::try_calculate_account_len::<Mint>(&[
+        ExtensionType::NonTransferable,
+        ExtensionType::MetadataPointer,
+    ])?;
+    let token_metadata = TokenMetadata {
+        update_authority: Some(collection_authority).try_into()?,
+        mint: item.mint.key(),
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        additional_metadata: vec![],
+    };
+    let metadata_space = token_metadata.tlv_size_of()?;
+    let rent = Rent::get()?;
+
+    // Token-2022 reallocs the mint for the metadata but does not fund it
+    let lamports = rent.minimum_balance(mint_space + metadata_space);
+    create_pda_account(
+        item.payer,
+        item.mint,
+        item.system_program,
+        lamports,
+        mint_space as u64,
+        item.token_program.key,
+        mint_seeds,
+    )?;
+
+    // Extensions must be initialized before the mint itself
+    non_transferable_mint_initialize(CpiContext::new(
+        item.token_program.clone(),
+        NonTransferableMintInitialize {
+            token_program_id: item.token_program.clone(),
+            mint: item.mint.clone(),
+        },
+    ))?;
+
+    metadata_pointer_initialize(
+        CpiContext::new(
+            item.token_program.clone(),
+            MetadataPointerInitialize {
+                token_program_id: item.token_program.clone(),
+                mint: item.mint.clone(),
+            },
+        ),
+        Some(collection_authority),
+        Some(item.mint.key()),
+    )?;
+
+    token_2022::initialize_mint2(
+        CpiContext::new(
+            item.token_program.clone(),
+            InitializeMint2 {
+                mint: item.mint.clone(),
+            },
+        ),
+        0,
+        &collection_authority,
+        None,
+    )?;
+
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            item.token_program.clone(),
+            TokenMetadataInitialize {
+                program_id: item.token_program.clone(),
+                metadata: item.mint.clone(),
+                update_authority: item.collection_authority.clone(),
+                mint_authority: item.collection_authority.clone(),
+                mint: item.mint.clone(),
+            },
+            &[authority_seeds],
+        ),
+        name,
+        symbol,
+        uri,
+    )?;
+
+    // Associated token accounts of non-transferable mints get the ImmutableOwner extension
+    associated_token::create(CpiContext::new(
+        item.associated_token_program.clone(),
+        Create {
+            payer: item.payer.clone(),
+            associated_token: item.recipient_token_account.clone(),
+            authority: item.recipient.clone(),
+            mint: item.mint.clone(),
+            system_program: item.system_program.clone(),
+            token_program: item.token_program.clone(),
+        },
+    ))?;
+
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            item.token_program.clone(),
+            MintTo {
+                mint: item.mint.clone(),
+                to: item.recipient_token_account.clone(),
+                authority: item.collection_authority.clone(),
+            },
+            &[authority_seeds],
+        ),
+        1,
+    )?;
+
+    token_2022::set_authority(
+        CpiContext::new_with_signer(
+            item.token_program.clone(),
+            SetAuthority {
+                current_authority: item.collection_authority.clone(),
+                account_or_mint: item.mint.clone(),
+            },
+            &[authority_seeds],
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )
+}
+
+pub fn mint_soulbound_badge(
+    ctx: Context<MintSoulboundBadge>,
+    metadata_uri: String,
+) -> Result<()> {
+    let winner = ctx.accounts.winner.key();
+    let game_key = ctx.accounts.game.key();
+    let game = &mut ctx.accounts.game;
+    let placement = game
+        .placement_of(&winner)
+        .ok_or(CapsuleXError::NotEligibleForReward)?;
+    let badge_type = WinnerBadge::badge_type_for(placement);
+    validate_reward_nft(&badge_type, &metadata_uri)?;
+    game.mark_badged(placement);
+
+    // Collect badge minting fee
+    collect_mint_fee(
+        &ctx.accounts.authority.to_account_info(),
+        &mut ctx.accounts.vault,
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.config.badge_mint_fee,
+    )?;
+
+    let mint_bump = [ctx.bumps.badge_mint];
+    let mint_seeds: &[&[u8]] = &[BADGE_MINT_SEED, game_key.as_ref(), winner.as_ref(), &mint_bump];
+    let authority_bump = [ctx.bumps.collection_authority];
+    let authority_seeds: &[&[u8]] = &[COLLECTION_AUTHORITY_SEED, &authority_bump];
+    create_soulbound_nft(
+        SoulboundItem {
+            payer: &ctx.accounts.authority.to_account_info(),
+            mint: &ctx.accounts.badge_mint.to_account_info(),
+            recipient: &ctx.accounts.winner,
+            recipient_token_account: &ctx.accounts.winner_token_account.to_account_info(),
+            collection_authority: &ctx.accounts.collection_authority.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+        badge_type.clone(),
+        BADGE_NFT_SYMBOL.to_string(),
+        metadata_uri.clone(),
+        mint_seeds,
+        authority_seeds,
+    )?;
+
+    let winner_badge = &mut ctx.accounts.winner_badge;
+    winner_badge.game = game_key;
+    winner_badge.winner = winner;
+    winner_badge.badge_mint = ctx.accounts.badge_mint.key();
+    winner_badge.placement = placement;
+    winner_badge.badge_type = badge_type.clone();
+    winner_badge.minted_at = Clock::get()?.unix_timestamp;
+    winner_badge.bump = ctx.bumps.winner_badge;
+
+    emit!(WinnerBadgeMinted {
+        game_id: game_key,
+        winner,
+        badge_mint: ctx.accounts.badge_mint.key(),
+        badge_type,
+        metadata_uri,
+        placement,
+        soulbound: true,
+    });
+
+    Ok(())
+}
+
+pub fn mint_soulbound_trophy(
+    ctx: Context<MintSoulboundTrophy>,
+    trophy_type: String,
+) -> Result<()> {
+    let trophy_definition = &mut ctx.accounts.trophy_definition;
+    check_trophy_eligibility(
+        trophy_definition,
+        &ctx.accounts.leaderboard,
+        Clock::get()?.unix_timestamp,
+    )?;
+    let serial = trophy_definition.record_mint();
+    let metadata_uri = trophy_definition.metadata_uri(serial);
+    validate_reward_nft(&trophy_type, &metadata_uri)?;
+
+    // Collect trophy minting fee
+    collect_mint_fee(
+        &ctx.accounts.authority.to_account_info(),
+        &mut ctx.accounts.vault,
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.config.trophy_mint_fee,
+    )?;
+
+    let user = ctx.accounts.user.key();
+    let definition = ctx.accounts.trophy_definition.key();
+    let season = ctx.accounts.trophy_definition.season.to_le_bytes();
+    let mint_bump = [ctx.bumps.trophy_mint];
+    let mint_seeds: &[&[u8]] = &[TROPHY_MINT_SEED, user.as_ref(), definition.as_ref(), &season, &mint_bump];
+    let authority_bump = [ctx.bumps.collection_authority];
+    let authority_seeds: &[&[u8]] = &[COLLECTION_AUTHORITY_SEED, &authority_bump];
+    create_soulbound_nft(
+        SoulboundItem {
+            payer: &ctx.accounts.authority.to_account_info(),
+            mint: &ctx.accounts.trophy_mint.to_account_info(),
+            recipient: &ctx.accounts.user,
+            recipient_token_account: &ctx.accounts.user_token_account.to_account_info(),
+            collection_authority: &ctx.accounts.collection_authority.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+        trophy_type.clone(),
+        TROPHY_NFT_SYMBOL.to_string(),
+        metadata_uri.clone(),
+        mint_seeds,
+        authority_seeds,
+    )?;
+
+    emit!(TrophyNftMinted {
+        user,
+        trophy_mint: ctx.accounts.trophy_mint.key(),
+        trophy_type,
+        metadata_uri,
+        serial,
+        soulbound: true,
+    });
+
+    Ok(())
+}
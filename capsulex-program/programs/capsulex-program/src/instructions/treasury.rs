@@ -0,0 +1,258 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::CapsuleXError,
+    state::{ProgramConfig, ProgramVault, Treasury, TreasuryRecipient}
+};
+
+#[derive(Accounts)]
+pub struct SetTreasuryRecipients<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        constraint = vault.authority == authority.key() @ CapsuleXError::InvalidProgramAuthority
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Treasury::LEN,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        constraint = vault.authority == authority.key() @ CapsuleXError::InvalidProgramAuthority
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Treasury::LEN,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// CHECK: Any account chosen by the vault authority to receive the withdrawn lamports
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Recipient wallets are passed as writable remaining accounts, in the configured order
+#[derive(Accounts)]
+pub struct DistributeFees<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        constraint = vault.authority == authority.key() @ CapsuleXError::InvalidProgramAuthority
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+// Lamports the vault can release while staying rent exempt
+pub(crate) fn withdrawable_balance(vault: &AccountInfo) -> Result<u64> {
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(vault.data_len());
+    Ok(vault.lamports().saturating_sub(rent_exempt_minimum))
+}
+
+// Withdrawable lamports minus guess fees that may still have to be refunded
+pub(crate) fn withdrawable_fees(vault: &AccountInfo, config: &ProgramConfig) -> Result<u64> {
+    Ok(withdrawable_balance(vault)?.saturating_sub(config.pending_refund_liability))
+}
+
+// The vault is program owned, so lamports are moved directly instead of via a system transfer
+pub(crate) fn pay_from_vault(vault: &AccountInfo, recipient: &AccountInfo, amount: u64) -> Result<()> {
+    let vault_balance = vault.lamports();
+    let recipient_balance = recipient.lamports();
+
+    **vault.try_borrow_mut_lamports()? = vault_balance
+        .checked_sub(amount)
+        .ok_or(CapsuleXError::InsufficientVaultBalance)?;
+    **recipient.try_borrow_mut_lamports()? = recipient_balance
+        .checked_add(amount)
+        .ok_or(CapsuleXError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+pub fn set_treasury_recipients(
+    ctx: Context<SetTreasuryRecipients>,
+    recipients: Vec<TreasuryRecipient>,
+) -> Result<()> {
+    require!(
+        recipients.len() <= MAX_TREASURY_RECIPIENTS,
+        CapsuleXError::TooManyTreasuryRecipients
+    );
+
+    // An empty list disables split payouts; otherwise shares must cover exactly 100%
+    if !recipients.is_empty() {
+        let mut total_bps: u64 = 0;
+        for (index, recipient) in recipients.iter().enumerate() {
+            require!(recipient.share_bps > 0, CapsuleXError::InvalidTreasuryShares);
+            require!(
+                !recipients[..index].iter().any(|other| other.wallet == recipient.wallet),
+                CapsuleXError::InvalidTreasuryShares
+            );
+            total_bps += recipient.share_bps as u64;
+        }
+        require!(
+            total_bps == BASIS_POINTS_DENOMINATOR,
+            CapsuleXError::InvalidTreasuryShares
+        );
+    }
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.bump = ctx.bumps.treasury;
+    treasury.recipients = recipients;
+
+    emit!(TreasuryRecipientsUpdated {
+        authority: ctx.accounts.authority.key(),
+        recipients: treasury.recipients.clone(),
+    });
+
+    Ok(())
+}
+
+pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    require!(amount > 0, CapsuleXError::InvalidFeeAmount);
+
+    let vault_info = ctx.accounts.vault.to_account_info();
+    require!(
+        amount <= withdrawable_fees(&vault_info, &ctx.accounts.config)?,
+        CapsuleXError::InsufficientVaultBalance
+    );
+
+    pay_from_vault(&vault_info, &ctx.accounts.destination.to_account_info(), amount)?;
+    ctx.accounts.vault.add_rewards_distributed(amount);
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.bump = ctx.bumps.treasury;
+    treasury.add_withdrawn(amount);
+
+    emit!(FeesWithdrawn {
+        authority: ctx.accounts.authority.key(),
+        recipient: ctx.accounts.destination.key(),
+        amount,
+        remaining_balance: withdrawable_fees(&vault_info, &ctx.accounts.config)?,
+        total_withdrawn: treasury.total_withdrawn,
+    });
+
+    Ok(())
+}
+
+pub fn distribute_fees<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributeFees<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, CapsuleXError::InvalidFeeAmount);
+
+    let recipients = ctx.accounts.treasury.recipients.clone();
+    require!(
+        !recipients.is_empty() && ctx.remaining_accounts.len() == recipients.len(),
+        CapsuleXError::TreasuryRecipientMismatch
+    );
+
+    let vault_info = ctx.accounts.vault.to_account_info();
+    require!(
+        amount <= withdrawable_fees(&vault_info, &ctx.accounts.config)?,
+        CapsuleXError::InsufficientVaultBalance
+    );
+
+    let authority = ctx.accounts.authority.key();
+    let last_index = recipients.len() - 1;
+    let mut remaining_amount = amount;
+
+    for (index, (recipient, recipient_info)) in recipients
+        .iter()
+        .zip(ctx.remaining_accounts.iter())
+        .enumerate()
+    {
+        require!(
+            recipient_info.key() == recipient.wallet && recipient_info.is_writable,
+            CapsuleXError::TreasuryRecipientMismatch
+        );
+
+        // Last recipient takes the rounding dust so the full amount leaves the vault
+        let share = if index == last_index {
+            remaining_amount
+        } else {
+            (amount as u128 * recipient.share_bps as u128 / BASIS_POINTS_DENOMINATOR as u128) as u64
+        };
+        remaining_amount -= share;
+
+        if share == 0 {
+            continue;
+        }
+
+        pay_from_vault(&vault_info, recipient_info, share)?;
+        ctx.accounts.vault.add_rewards_distributed(share);
+        ctx.accounts.treasury.add_withdrawn(share);
+
+        emit!(FeesWithdrawn {
+            authority,
+            recipient: recipient.wallet,
+            amount: share,
+            remaining_balance: withdrawable_fees(&vault_info, &ctx.accounts.config)?,
+            total_withdrawn: ctx.accounts.treasury.total_withdrawn,
+        });
+    }
+
+    Ok(())
+}
+
+#[event]
+pub struct TreasuryRecipientsUpdated {
+    pub authority: Pubkey,
+    pub recipients: Vec<TreasuryRecipient>,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub authority: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub remaining_balance: u64, // Withdrawable lamports left above the rent-exempt minimum and refund liability
+    pub total_withdrawn: u64,
+}
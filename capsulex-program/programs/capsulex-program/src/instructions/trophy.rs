@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::CapsuleXError,
+    state::{ProgramVault, TrophyCriteria, TrophyDefinition}
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TrophyDefinitionParams {
+    pub criteria: TrophyCriteria,
+    pub season: u16,
+    pub season_start: i64,
+    pub season_end: Option<i64>,
+    pub max_supply: Option<u32>,
+    pub metadata_uri_template: String,
+}
+
+#[derive(Accounts)]
+#[instruction(trophy_type: String)]
+pub struct CreateTrophyDefinition<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        constraint = vault.authority == authority.key() @ CapsuleXError::InvalidProgramAuthority
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TrophyDefinition::LEN,
+        seeds = [TROPHY_DEFINITION_SEED, trophy_type.as_bytes()],
+        bump
+    )]
+    pub trophy_definition: Account<'info, TrophyDefinition>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(trophy_type: String)]
+pub struct UpdateTrophyDefinition<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        constraint = vault.authority == authority.key() @ CapsuleXError::InvalidProgramAuthority
+    )]
+    pub vault: Account<'info, ProgramVault>,
+
+    #[account(
+        mut,
+        seeds = [TROPHY_DEFINITION_SEED, trophy_type.as_bytes()],
+        bump = trophy_definition.bump
+    )]
+    pub trophy_definition: Account<'info, TrophyDefinition>,
+}
+
+fn validate_trophy_definition(params: &TrophyDefinitionParams, minted: u32) -> Result<()> {
+    require!(
+        params.metadata_uri_template.len() <= MAX_METADATA_URI_LENGTH,
+        CapsuleXError::MetadataUriTooLong
+    );
+    if let Some(season_end) = params.season_end {
+        require!(
+            season_end > params.season_start,
+            CapsuleXError::InvalidTrophyDefinition
+        );
+    }
+    // A cap can be lowered to what was already minted, closing the trophy, but not below it
+    if let Some(max_supply) = params.max_supply {
+        require!(
+            max_supply >= minted.max(1),
+            CapsuleXError::InvalidTrophyDefinition
+        );
+    }
+    Ok(())
+}
+
+fn apply_params(trophy_definition: &mut TrophyDefinition, params: TrophyDefinitionParams) {
+    trophy_definition.criteria = params.criteria;
+    trophy_definition.season = params.season;
+    trophy_definition.season_start = params.season_start;
+    trophy_definition.season_end = params.season_end;
+    trophy_definition.max_supply = params.max_supply;
+    trophy_definition.metadata_uri_template = params.metadata_uri_template;
+}
+
+pub fn create_trophy_definition(
+    ctx: Context<CreateTrophyDefinition>,
+    trophy_type: String,
+    params: TrophyDefinitionParams,
+) -> Result<()> {
+    require!(
+        !trophy_type.is_empty(),
+        CapsuleXError::InvalidTrophyDefinition
+    );
+    require!(
+        trophy_type.len() <= MAX_BADGE_TYPE_LENGTH,
+        CapsuleXError::BadgeTypeTooLong
+    );
+    validate_trophy_definition(&params, 0)?;
+
+    let trophy_definition = &mut ctx.accounts.trophy_definition;
+    trophy_definition.trophy_type = trophy_type;
+    apply_params(trophy_definition, params);
+    trophy_definition.minted = 0;
+    trophy_definition.is_active = true;
+    trophy_definition.bump = ctx.bumps.trophy_definition;
+
+    emit_trophy_definition_updated(ctx.accounts.authority.key(), trophy_definition);
+
+    Ok(())
+}
+
+pub fn update_trophy_definition(
+    ctx: Context<UpdateTrophyDefinition>,
+    _trophy_type: String,
+    params: TrophyDefinitionParams,
+    is_active: bool,
+) -> Result<()> {
+    let trophy_definition = &mut ctx.accounts.trophy_definition;
+    validate_trophy_definition(&params, trophy_definition.minted)?;
+
+    apply_params(trophy_definition, params);
+    trophy_definition.is_active = is_active;
+
+    emit_trophy_definition_updated(ctx.accounts.authority.key(), trophy_definition);
+
+    Ok(())
+}
+
+fn emit_trophy_definition_updated(authority: Pubkey, trophy_definition: &Account<TrophyDefinition>) {
+    emit!(TrophyDefinitionUpdated {
+        authority,
+        trophy_definition: trophy_definition.key(),
+        trophy_type: trophy_definition.trophy_type.clone(),
+        criteria: trophy_definition.criteria.clone(),
+        season: trophy_definition.season,
+        season_start: trophy_definition.season_start,
+        season_end: trophy_definition.season_end,
+        max_supply: trophy_definition.max_supply,
+        metadata_uri_template: trophy_definition.metadata_uri_template.clone(),
+        is_active: trophy_definition.is_active,
+    });
+}
+
+#[event]
+pub struct TrophyDefinitionUpdated {
+    pub authority: Pubkey,
+    pub trophy_definition: Pubkey,
+    pub trophy_type: String,
+    pub criteria: TrophyCriteria,
+    pub season: u16,
+    pub season_start: i64,
+    pub season_end: Option<i64>,
+    pub max_supply: Option<u32>,
+    pub metadata_uri_template: String,
+    pub is_active: bool,
+}
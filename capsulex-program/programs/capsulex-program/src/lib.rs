@@ -1,3 +1,7 @@
+// The #[program] macro emits its IDL handlers in a private module beside `capsulex`, out of reach
+// of an item attribute, and the resize handler there calls the deprecated AccountInfo::realloc.
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 
 // Program modules
@@ -5,8 +9,10 @@ pub mod instructions;
 pub mod state;
 pub mod errors;
 pub mod constants;
+pub mod utils;
 
-// Re-export for easier access
+// Re-export for easier access. The #[program] wrappers below share the handlers' names.
+#[allow(ambiguous_glob_reexports)]
 pub use instructions::*;
 pub use state::*;
 pub use errors::*;
@@ -33,9 +39,47 @@ pub mod capsulex {
 
     pub fn reveal_capsule(
         ctx: Context<RevealCapsule>,
-        reveal_date: i64,
     ) -> Result<()> {
-        instructions::reveal_capsule(ctx, reveal_date)
+        instructions::reveal_capsule(ctx)
+    }
+
+    pub fn register_legacy_capsule(
+        ctx: Context<RegisterLegacyCapsule>,
+    ) -> Result<()> {
+        instructions::register_legacy_capsule(ctx)
+    }
+
+    pub fn cancel_capsule(
+        ctx: Context<CancelCapsule>,
+    ) -> Result<()> {
+        instructions::cancel_capsule(ctx)
+    }
+
+    pub fn update_capsule_content(
+        ctx: Context<UpdateCapsuleContent>,
+        encrypted_content: String,
+        content_storage: ContentStorage,
+        content_integrity_hash: String,
+    ) -> Result<()> {
+        instructions::update_capsule_content(ctx, encrypted_content, content_storage, content_integrity_hash)
+    }
+
+    pub fn reschedule_reveal(
+        ctx: Context<RescheduleReveal>,
+        new_reveal_date: i64,
+    ) -> Result<()> {
+        instructions::reschedule_reveal(ctx, new_reveal_date)
+    }
+
+    pub fn consent_reschedule(ctx: Context<ConsentReschedule>) -> Result<()> {
+        instructions::consent_reschedule(ctx)
+    }
+
+    pub fn publish_plaintext(
+        ctx: Context<PublishPlaintext>,
+        plaintext: String,
+    ) -> Result<()> {
+        instructions::publish_plaintext(ctx, plaintext)
     }
 
 
@@ -60,10 +104,24 @@ pub mod capsulex {
 
     pub fn submit_guess(
         ctx: Context<SubmitGuess>,
-        guess_content: String,
+        commitment: [u8; 32],
         is_anonymous: bool,
     ) -> Result<()> {
-        instructions::submit_guess(ctx, guess_content, is_anonymous)
+        instructions::submit_guess(ctx, commitment, is_anonymous)
+    }
+
+    pub fn reveal_guess(
+        ctx: Context<RevealGuess>,
+        guess_content: String,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        instructions::reveal_guess(ctx, guess_content, salt)
+    }
+
+    pub fn migrate_legacy_guess(
+        ctx: Context<MigrateLegacyGuess>,
+    ) -> Result<()> {
+        instructions::migrate_legacy_guess(ctx)
     }
 
     pub fn verify_guess(
@@ -87,10 +145,9 @@ pub mod capsulex {
     // NFT Badge Instructions
     pub fn mint_winner_badge(
         ctx: Context<MintWinnerBadge>,
-        badge_type: String,
         metadata_uri: String,
     ) -> Result<()> {
-        instructions::mint_winner_badge(ctx, badge_type, metadata_uri)
+        instructions::mint_winner_badge(ctx, metadata_uri)
     }
 
     // Program Initialization
@@ -100,6 +157,19 @@ pub mod capsulex {
         instructions::initialize_program(ctx)
     }
 
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+    ) -> Result<()> {
+        instructions::initialize_config(ctx)
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        update: ConfigUpdate,
+    ) -> Result<()> {
+        instructions::update_config(ctx, update)
+    }
+
     // Leaderboard Instructions
     pub fn initialize_leaderboard(
         ctx: Context<InitializeLeaderboard>,
@@ -116,11 +186,268 @@ pub mod capsulex {
         instructions::update_leaderboard(ctx, user, points)
     }
 
+    pub fn set_points_issuer(
+        ctx: Context<SetPointsIssuer>,
+        issuer: Pubkey,
+        points_cap: u64,
+    ) -> Result<()> {
+        instructions::set_points_issuer(ctx, issuer, points_cap)
+    }
+
+    pub fn remove_points_issuer(
+        ctx: Context<RemovePointsIssuer>,
+        issuer: Pubkey,
+    ) -> Result<()> {
+        instructions::remove_points_issuer(ctx, issuer)
+    }
+
     pub fn mint_trophy_nft(
         ctx: Context<MintTrophyNft>,
         trophy_type: String,
+    ) -> Result<()> {
+        instructions::mint_trophy_nft(ctx, trophy_type)
+    }
+
+    // Oracle Registry Instructions
+    pub fn add_oracle(
+        ctx: Context<AddOracle>,
+        public_key: Pubkey,
+        activated_at: Option<i64>,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        instructions::add_oracle(ctx, public_key, activated_at, expires_at)
+    }
+
+    pub fn revoke_oracle(
+        ctx: Context<ManageOracle>,
+        public_key: Pubkey,
+    ) -> Result<()> {
+        instructions::revoke_oracle(ctx, public_key)
+    }
+
+    pub fn rotate_oracle(
+        ctx: Context<ManageOracle>,
+        old_public_key: Pubkey,
+        new_public_key: Pubkey,
+        grace_period: i64,
+    ) -> Result<()> {
+        instructions::rotate_oracle(ctx, old_public_key, new_public_key, grace_period)
+    }
+
+    pub fn add_oracle_relayer(
+        ctx: Context<ManageOracle>,
+        relayer: Pubkey,
+    ) -> Result<()> {
+        instructions::add_oracle_relayer(ctx, relayer)
+    }
+
+    pub fn remove_oracle_relayer(
+        ctx: Context<ManageOracle>,
+        relayer: Pubkey,
+    ) -> Result<()> {
+        instructions::remove_oracle_relayer(ctx, relayer)
+    }
+
+    pub fn close_used_nonce(
+        ctx: Context<CloseUsedNonce>,
+    ) -> Result<()> {
+        instructions::close_used_nonce(ctx)
+    }
+
+    // Treasury Instructions
+    pub fn set_treasury_recipients(
+        ctx: Context<SetTreasuryRecipients>,
+        recipients: Vec<TreasuryRecipient>,
+    ) -> Result<()> {
+        instructions::set_treasury_recipients(ctx, recipients)
+    }
+
+    pub fn withdraw_fees(
+        ctx: Context<WithdrawFees>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_fees(ctx, amount)
+    }
+
+    pub fn distribute_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeFees<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::distribute_fees(ctx, amount)
+    }
+
+    // Vault Authority Transfer Instructions
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_authority(ctx, new_authority)
+    }
+
+    pub fn accept_authority(
+        ctx: Context<AcceptAuthority>,
+    ) -> Result<()> {
+        instructions::accept_authority(ctx)
+    }
+
+    pub fn cancel_authority_proposal(
+        ctx: Context<CancelAuthorityProposal>,
+    ) -> Result<()> {
+        instructions::cancel_authority_proposal(ctx)
+    }
+
+    // Prize Pool Instructions
+    pub fn create_prize_pool(
+        ctx: Context<CreatePrizePool>,
+        split_bps: Vec<u16>,
+    ) -> Result<()> {
+        instructions::create_prize_pool(ctx, split_bps)
+    }
+
+    pub fn create_token_prize_pool(
+        ctx: Context<CreateTokenPrizePool>,
+        split_bps: Vec<u16>,
+    ) -> Result<()> {
+        instructions::create_token_prize_pool(ctx, split_bps)
+    }
+
+    pub fn fund_prize_pool(
+        ctx: Context<FundPrizePool>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::fund_prize_pool(ctx, amount)
+    }
+
+    pub fn fund_token_prize_pool(
+        ctx: Context<FundTokenPrizePool>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::fund_token_prize_pool(ctx, amount)
+    }
+
+    pub fn claim_prize(
+        ctx: Context<ClaimPrize>,
+    ) -> Result<()> {
+        instructions::claim_prize(ctx)
+    }
+
+    pub fn claim_token_prize(
+        ctx: Context<ClaimTokenPrize>,
+    ) -> Result<()> {
+        instructions::claim_token_prize(ctx)
+    }
+
+    pub fn refund_prize(
+        ctx: Context<RefundPrize>,
+    ) -> Result<()> {
+        instructions::refund_prize(ctx)
+    }
+
+    pub fn refund_token_prize(
+        ctx: Context<RefundTokenPrize>,
+    ) -> Result<()> {
+        instructions::refund_token_prize(ctx)
+    }
+
+    pub fn close_prize_pool(
+        ctx: Context<ClosePrizePool>,
+    ) -> Result<()> {
+        instructions::close_prize_pool(ctx)
+    }
+
+    pub fn close_token_prize_pool(
+        ctx: Context<CloseTokenPrizePool>,
+    ) -> Result<()> {
+        instructions::close_token_prize_pool(ctx)
+    }
+
+    // Creator Earnings Instructions
+    pub fn claim_creator_earnings(
+        ctx: Context<ClaimCreatorEarnings>,
+    ) -> Result<()> {
+        instructions::claim_creator_earnings(ctx)
+    }
+
+    // Creator Bond Instructions
+    pub fn process_bond_refund(
+        ctx: Context<ClaimGuessRefund>,
+    ) -> Result<()> {
+        instructions::process_bond_refund(ctx)
+    }
+
+    pub fn release_bond(
+        ctx: Context<ReleaseBond>,
+    ) -> Result<()> {
+        instructions::release_bond(ctx)
+    }
+
+    // Guess Refund Instructions
+    pub fn claim_guess_refund(
+        ctx: Context<ClaimGuessRefund>,
+    ) -> Result<()> {
+        instructions::claim_guess_refund(ctx)
+    }
+
+    // Account Close Instructions
+    pub fn close_guess(
+        ctx: Context<CloseGuess>,
+    ) -> Result<()> {
+        instructions::close_guess(ctx)
+    }
+
+    pub fn close_game(
+        ctx: Context<CloseGame>,
+    ) -> Result<()> {
+        instructions::close_game(ctx)
+    }
+
+    pub fn close_capsule(
+        ctx: Context<CloseCapsule>,
+    ) -> Result<()> {
+        instructions::close_capsule(ctx)
+    }
+
+    // NFT Collection Instructions
+    pub fn initialize_nft_collection(
+        ctx: Context<InitializeNftCollection>,
+        kind: NftKind,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::initialize_nft_collection(ctx, kind, name, symbol, uri)
+    }
+
+    // Soulbound NFT Instructions
+    pub fn mint_soulbound_badge(
+        ctx: Context<MintSoulboundBadge>,
         metadata_uri: String,
     ) -> Result<()> {
-        instructions::mint_trophy_nft(ctx, trophy_type, metadata_uri)
+        instructions::mint_soulbound_badge(ctx, metadata_uri)
+    }
+
+    pub fn mint_soulbound_trophy(
+        ctx: Context<MintSoulboundTrophy>,
+        trophy_type: String,
+    ) -> Result<()> {
+        instructions::mint_soulbound_trophy(ctx, trophy_type)
+    }
+
+    // Trophy Definition Instructions
+    pub fn create_trophy_definition(
+        ctx: Context<CreateTrophyDefinition>,
+        trophy_type: String,
+        params: TrophyDefinitionParams,
+    ) -> Result<()> {
+        instructions::create_trophy_definition(ctx, trophy_type, params)
+    }
+
+    pub fn update_trophy_definition(
+        ctx: Context<UpdateTrophyDefinition>,
+        trophy_type: String,
+        params: TrophyDefinitionParams,
+        is_active: bool,
+    ) -> Result<()> {
+        instructions::update_trophy_definition(ctx, trophy_type, params, is_active)
     }
-} 
\ No newline at end of file
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
 use crate::constants::*;
+use crate::errors::CapsuleXError;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum ContentStorage {
@@ -47,11 +49,16 @@ pub struct Capsule {
     pub is_revealed: bool,
     pub is_active: bool,
     pub bump: u8,
+    pub capsule_index: Option<u64>, // Position in the creator's CreatorProfile and last PDA seed; None for legacy capsules
+    pub legacy_index: Option<u64>, // Index given to a legacy capsule by register_legacy_capsule; its address does not change
+    pub nft_minted: bool, // Set once the single capsule NFT has been minted
+    pub revealed_at: i64, // 0 until revealed, and on capsules revealed before this field existed
 }
 
 impl Capsule {
     pub const LEN: usize = CAPSULE_ACCOUNT_SIZE;
     
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         creator: Pubkey,
         nft_mint: Pubkey,
@@ -61,6 +68,7 @@ impl Capsule {
         reveal_date: i64,
         is_gamified: bool,
         bump: u8,
+        capsule_index: u64,
     ) -> Self {
         let clock = Clock::get().unwrap();
         
@@ -76,6 +84,10 @@ impl Capsule {
             is_revealed: false,
             is_active: true,
             bump,
+            capsule_index: Some(capsule_index),
+            legacy_index: None,
+            nft_minted: false,
+            revealed_at: 0,
         }
     }
     
@@ -85,8 +97,39 @@ impl Capsule {
         (clock.unix_timestamp + 10) >= self.reveal_date && self.is_active
     }
     
-    pub fn reveal(&mut self) {
+    pub fn reveal(&mut self, now: i64) {
         self.is_revealed = true;
+        self.revealed_at = now;
+    }
+    
+    // Revealed before guessers became entitled to refunds; a late reveal does not end their claim on the bond
+    pub fn revealed_on_time(&self, grace_period: i64) -> bool {
+        self.is_revealed && self.revealed_at <= self.reveal_date + grace_period
+    }
+    
+    // Last seed of the capsule PDA: the creator's capsule index, or the reveal date for legacy capsules,
+    // which therefore cannot be rescheduled
+    pub fn seed_bytes(&self) -> [u8; 8] {
+        match self.capsule_index {
+            Some(index) => index.to_le_bytes(),
+            None => self.reveal_date.to_le_bytes(),
+        }
+    }
+    
+    pub fn is_legacy(&self) -> bool {
+        self.capsule_index.is_none()
+    }
+    
+    // Creator cancelled the capsule, or missed the reveal by more than the grace period
+    pub fn is_abandoned(&self, now: i64, grace_period: i64) -> bool {
+        !self.is_revealed && (!self.is_active || now > self.reveal_date + grace_period)
+    }
+    
+    // content_integrity_hash is the hex SHA-256 of the original content
+    pub fn matches_integrity_hash(&self, plaintext: &str) -> bool {
+        let digest = hashv(&[plaintext.as_bytes()]).to_bytes();
+        let digest_hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+        digest_hex.eq_ignore_ascii_case(&self.content_integrity_hash)
     }
 }
 
@@ -104,6 +147,14 @@ pub struct Game {
     pub winner: Option<Pubkey>, // Keep for backward compatibility (first winner)
     pub winners: Vec<Pubkey>, // All winners
     pub bump: u8,
+    pub bonus_paid: bool, // Creator engagement bonus awarded by complete_game
+    pub badged_placements: u16, // Bit n set once the winner at placement n + 1 has been badged
+    pub guesses_settled: u32, // Guesses refunded or closed; refunds are owed until it reaches current_guesses
+    pub guesses_verified: u32, // Guesses judged by verify_guess
+    pub reschedule_count: u16, // Reveal reschedules applied so far
+    pub reschedule_consents: u32, // Guesses consenting to the next reschedule; reset when it is applied
+    pub vault_fees_held: u64, // Vault share of guess fees still refundable; released when the capsule is revealed
+    pub creator_fees_held: u64, // Creator share of guess fees locked in CreatorEarnings until the capsule is revealed
 }
 
 impl Game {
@@ -129,6 +180,14 @@ impl Game {
             winner: None,
             winners: Vec::new(),
             bump,
+            bonus_paid: false,
+            badged_placements: 0,
+            guesses_settled: 0,
+            guesses_verified: 0,
+            reschedule_count: 0,
+            reschedule_consents: 0,
+            vault_fees_held: 0,
+            creator_fees_held: 0,
         }
     }
     
@@ -141,6 +200,10 @@ impl Game {
         self.total_participants += 1;
     }
     
+    pub fn is_winner(&self, player: &Pubkey) -> bool {
+        self.winners.contains(player)
+    }
+    
     pub fn set_winner(&mut self, winner: Pubkey) {
         // Add to winners list
         self.winners.push(winner);
@@ -153,6 +216,29 @@ impl Game {
         self.winner_found = true;
     }
     
+    // 1-based position in winners, in the order the winning guesses were verified
+    pub fn placement_of(&self, player: &Pubkey) -> Option<u8> {
+        self.winners
+            .iter()
+            .position(|winner| winner == player)
+            .map(|index| (index + 1) as u8)
+    }
+    
+    pub fn is_badged(&self, placement: u8) -> bool {
+        self.badged_placements & (1 << (placement - 1)) != 0
+    }
+    
+    pub fn mark_badged(&mut self, placement: u8) {
+        self.badged_placements |= 1 << (placement - 1);
+    }
+    
+    // Placements whose winner has not minted a badge yet
+    pub fn unbadged_placements(&self) -> Vec<u8> {
+        (1..=self.winners.len() as u8)
+            .filter(|placement| !self.is_badged(*placement))
+            .collect()
+    }
+    
     pub fn should_end_game(&self) -> bool {
         self.winners_found >= self.max_winners || self.current_guesses >= self.max_guesses
     }
@@ -160,18 +246,68 @@ impl Game {
     pub fn end_game(&mut self) {
         self.is_active = false;
     }
+    
+    pub fn settle_guess(&mut self) {
+        self.guesses_settled += 1;
+    }
+    
+    pub fn all_guesses_settled(&self) -> bool {
+        self.guesses_settled >= self.current_guesses
+    }
+    
+    pub fn record_verification(&mut self) {
+        self.guesses_verified += 1;
+    }
+    
+    pub fn all_guesses_verified(&self) -> bool {
+        self.guesses_verified >= self.current_guesses
+    }
+    
+    pub fn release_vault_fees(&mut self, amount: u64) -> Result<()> {
+        self.vault_fees_held = self
+            .vault_fees_held
+            .checked_sub(amount)
+            .ok_or(CapsuleXError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    pub fn release_creator_fees(&mut self, amount: u64) -> Result<()> {
+        self.creator_fees_held = self
+            .creator_fees_held
+            .checked_sub(amount)
+            .ok_or(CapsuleXError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    // Guess.reschedule_consent value marking consent to the next reschedule
+    pub fn next_reschedule(&self) -> u16 {
+        self.reschedule_count + 1
+    }
+    
+    pub fn apply_reschedule(&mut self) {
+        self.reschedule_count += 1;
+        self.reschedule_consents = 0;
+    }
 }
 
 #[account]
 pub struct Guess {
     pub game_id: Pubkey,
     pub guesser: Pubkey,
-    pub guess_content: String,
+    pub guess_content: String, // Empty until the commitment is opened
     pub timestamp: i64,
     pub is_paid: bool,
     pub is_correct: bool,
     pub is_anonymous: bool,
     pub bump: u8,
+    pub is_verified: bool,
+    pub verified_at: i64, // 0 until verify_guess has run
+    pub commitment: [u8; 32], // sha256(guess_content || salt)
+    pub is_opened: bool,
+    pub is_refunded: bool, // Fee returned because the capsule was never revealed
+    pub fee_paid: u64, // Service fee charged at submission
+    pub creator_share_paid: u64, // Part of fee_paid credited to the creator's CreatorEarnings
+    pub reschedule_consent: u16, // Game.next_reschedule() the guesser last consented to; 0 if never
 }
 
 impl Guess {
@@ -180,7 +316,7 @@ impl Guess {
     pub fn new(
         game_id: Pubkey,
         guesser: Pubkey,
-        guess_content: String,
+        commitment: [u8; 32],
         is_paid: bool,
         is_anonymous: bool,
         bump: u8,
@@ -190,18 +326,49 @@ impl Guess {
         Self {
             game_id,
             guesser,
-            guess_content,
+            guess_content: String::new(),
             timestamp: clock.unix_timestamp,
             is_paid,
             is_correct: false,
             is_anonymous,
             bump,
+            is_verified: false,
+            verified_at: 0,
+            commitment,
+            is_opened: false,
+            is_refunded: false,
+            fee_paid: 0,
+            creator_share_paid: 0,
+            reschedule_consent: 0,
         }
     }
     
+    pub fn refundable_fee(&self) -> u64 {
+        if self.is_paid {
+            self.fee_paid
+        } else {
+            0
+        }
+    }
+    
+    // Part of the fee that went to the program vault
+    pub fn vault_share_paid(&self) -> u64 {
+        self.fee_paid.saturating_sub(self.creator_share_paid)
+    }
+    
+    pub fn open(&mut self, guess_content: String) {
+        self.guess_content = guess_content;
+        self.is_opened = true;
+    }
+    
     pub fn mark_correct(&mut self) {
         self.is_correct = true;
     }
+    
+    pub fn mark_verified(&mut self, verified_at: i64) {
+        self.is_verified = true;
+        self.verified_at = verified_at;
+    }
 }
 
 #[account]
@@ -250,11 +417,51 @@ impl LeaderboardEntry {
     }
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct PointsIssuer {
+    pub issuer: Pubkey,
+    pub points_cap: u64, // Lifetime points this issuer may grant
+    pub points_issued: u64,
+}
+
+impl PointsIssuer {
+    pub fn remaining_points(&self) -> u64 {
+        self.points_cap.saturating_sub(self.points_issued)
+    }
+}
+
+// Allow-list of keys (e.g. game services) that may grant leaderboard points
+#[account]
+pub struct PointsIssuerRegistry {
+    pub issuers: Vec<PointsIssuer>,
+    pub bump: u8,
+}
+
+impl PointsIssuerRegistry {
+    pub const LEN: usize = POINTS_ISSUER_REGISTRY_ACCOUNT_SIZE;
+    
+    pub fn find(&self, issuer: &Pubkey) -> Option<&PointsIssuer> {
+        self.issuers.iter().find(|entry| entry.issuer == *issuer)
+    }
+    
+    pub fn find_mut(&mut self, issuer: &Pubkey) -> Option<&mut PointsIssuer> {
+        self.issuers.iter_mut().find(|entry| entry.issuer == *issuer)
+    }
+    
+    pub fn is_issuer(&self, issuer: &Pubkey) -> bool {
+        self.find(issuer).is_some()
+    }
+    
+    pub fn remove(&mut self, issuer: &Pubkey) {
+        self.issuers.retain(|entry| entry.issuer != *issuer);
+    }
+}
+
 #[account]
 pub struct ProgramVault {
     pub authority: Pubkey,
     pub total_fees_collected: u64,
-    pub total_rewards_distributed: u64,
+    pub total_rewards_distributed: u64, // Fees paid out by withdraw_fees and distribute_fees
     pub bump: u8,
 }
 
@@ -279,4 +486,513 @@ impl ProgramVault {
     }
 }
 
- 
\ No newline at end of file
+// Runtime-configurable program settings, managed by the ProgramVault authority
+#[account]
+pub struct ProgramConfig {
+    pub app_authority: Pubkey, // May reveal any capsule on behalf of its creator
+    pub capsule_creation_fee: u64,
+    pub service_fee: u64,
+    pub badge_mint_fee: u64,
+    pub trophy_mint_fee: u64,
+    pub bump: u8,
+    pub creator_share_bps: u16, // Share of each guess fee credited to the capsule creator
+    pub refund_grace_period: i64, // Seconds after reveal_date before an unrevealed capsule's guessers are refunded
+    pub soulbound_nfts: bool, // Badges and trophies are minted as non-transferable Token-2022 NFTs
+    pub pending_refund_liability: u64, // Vault lamports owed back to guessers if their capsule is abandoned
+}
+
+impl ProgramConfig {
+    pub const LEN: usize = PROGRAM_CONFIG_ACCOUNT_SIZE;
+    
+    pub fn new(app_authority: Pubkey, bump: u8) -> Self {
+        Self {
+            app_authority,
+            capsule_creation_fee: DEFAULT_CAPSULE_CREATION_FEE,
+            service_fee: DEFAULT_SERVICE_FEE,
+            badge_mint_fee: DEFAULT_BADGE_MINT_FEE,
+            trophy_mint_fee: DEFAULT_TROPHY_MINT_FEE,
+            bump,
+            creator_share_bps: DEFAULT_CREATOR_SHARE_BPS,
+            refund_grace_period: DEFAULT_REFUND_GRACE_PERIOD,
+            soulbound_nfts: false,
+            pending_refund_liability: 0,
+        }
+    }
+    
+    pub fn creator_share(&self, fee: u64) -> u64 {
+        (fee as u128 * self.creator_share_bps as u128 / BASIS_POINTS_DENOMINATOR as u128) as u64
+    }
+    
+    pub fn reserve_refundable(&mut self, amount: u64) {
+        self.pending_refund_liability = self.pending_refund_liability.saturating_add(amount);
+    }
+    
+    pub fn release_refundable(&mut self, amount: u64) -> Result<()> {
+        self.pending_refund_liability = self
+            .pending_refund_liability
+            .checked_sub(amount)
+            .ok_or(CapsuleXError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct OracleKey {
+    pub public_key: Pubkey, // Ed25519 public key of the semantic service
+    pub activated_at: i64,
+    pub expires_at: Option<i64>, // None = valid until revoked or rotated out
+}
+
+impl OracleKey {
+    pub fn is_active(&self, now: i64) -> bool {
+        let not_expired = match self.expires_at {
+            Some(expires_at) => now < expires_at,
+            None => true,
+        };
+        now >= self.activated_at && not_expired
+    }
+    
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+#[account]
+pub struct OracleConfig {
+    pub oracles: Vec<OracleKey>,
+    pub bump: u8,
+    pub relayers: Vec<Pubkey>, // Relayer wallets allowed to call verify_guess
+}
+
+impl OracleConfig {
+    pub const LEN: usize = ORACLE_CONFIG_ACCOUNT_SIZE;
+    
+    pub fn find(&self, public_key: &Pubkey) -> Option<&OracleKey> {
+        self.oracles.iter().find(|oracle| oracle.public_key == *public_key)
+    }
+    
+    pub fn find_mut(&mut self, public_key: &Pubkey) -> Option<&mut OracleKey> {
+        self.oracles.iter_mut().find(|oracle| oracle.public_key == *public_key)
+    }
+    
+    pub fn is_active_oracle(&self, public_key: &Pubkey, now: i64) -> bool {
+        self.find(public_key).is_some_and(|oracle| oracle.is_active(now))
+    }
+    
+    // Drop keys whose expiry has passed so their slots can be reused
+    pub fn prune_expired(&mut self, now: i64) {
+        self.oracles.retain(|oracle| !oracle.is_expired(now));
+    }
+    
+    pub fn remove(&mut self, public_key: &Pubkey) {
+        self.oracles.retain(|oracle| oracle.public_key != *public_key);
+    }
+    
+    pub fn is_relayer(&self, relayer: &Pubkey) -> bool {
+        self.relayers.contains(relayer)
+    }
+}
+
+// Replay ledger entry: one per oracle nonce, kept until the signed verdict could no longer pass
+// the timestamp check, after which anyone can close it and refund the rent to the payer
+#[account]
+pub struct UsedNonce {
+    pub nonce_hash: [u8; 32],
+    pub oracle: Pubkey,
+    pub payer: Pubkey,
+    pub used_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl UsedNonce {
+    pub const LEN: usize = USED_NONCE_ACCOUNT_SIZE;
+    
+    pub fn new(
+        nonce_hash: [u8; 32],
+        oracle: Pubkey,
+        payer: Pubkey,
+        used_at: i64,
+        oracle_timestamp: i64,
+        bump: u8,
+    ) -> Self {
+        Self {
+            nonce_hash,
+            oracle,
+            payer,
+            used_at,
+            expires_at: oracle_timestamp + ORACLE_SIGNATURE_MAX_AGE,
+            bump,
+        }
+    }
+    
+    pub fn is_expired(&self, now: i64) -> bool {
+        now > self.expires_at
+    }
+    
+    // Seed for the nonce's PDA; nonces can be longer than the 32-byte seed limit
+    pub fn hash_nonce(oracle_nonce: &str) -> [u8; 32] {
+        hashv(&[oracle_nonce.as_bytes()]).to_bytes()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct TreasuryRecipient {
+    pub wallet: Pubkey,
+    pub share_bps: u16, // Share of each split payout in basis points
+}
+
+// Payout configuration and withdrawal accounting for fees held in the ProgramVault.
+// Payouts also count towards ProgramVault.total_rewards_distributed, the vault-wide total.
+#[account]
+pub struct Treasury {
+    pub recipients: Vec<TreasuryRecipient>,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+}
+
+impl Treasury {
+    pub const LEN: usize = TREASURY_ACCOUNT_SIZE;
+    
+    pub fn add_withdrawn(&mut self, amount: u64) {
+        self.total_withdrawn += amount;
+    }
+}
+
+// Pending vault authority handover; exists only while a proposal is open
+#[account]
+pub struct AuthorityProposal {
+    pub current_authority: Pubkey, // Proposer, refunded the rent when the proposal closes
+    pub proposed_authority: Pubkey,
+    pub proposed_at: i64,
+    pub bump: u8,
+}
+
+impl AuthorityProposal {
+    pub const LEN: usize = AUTHORITY_PROPOSAL_ACCOUNT_SIZE;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct PrizeFunder {
+    pub funder: Pubkey,
+    pub amount: u64, // Total contributed, returned if nobody wins
+    pub refunded: bool,
+}
+
+// Prize escrow for one game. SOL pools hold lamports on this account;
+// SPL pools hold tokens in the PRIZE_ESCROW_SEED token account owned by it.
+#[account]
+pub struct PrizePool {
+    pub game: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Option<Pubkey>, // None for SOL prizes
+    pub split_bps: Vec<u16>, // Share per placement in Game.winners, in basis points
+    pub total_funded: u64,
+    pub total_paid: u64, // Prizes claimed plus refunds
+    pub funders: Vec<PrizeFunder>,
+    pub claimed: Vec<Pubkey>, // Winners who already claimed
+    pub bump: u8,
+}
+
+impl PrizePool {
+    pub const LEN: usize = PRIZE_POOL_ACCOUNT_SIZE;
+    
+    pub fn new(game: Pubkey, creator: Pubkey, mint: Option<Pubkey>, split_bps: Vec<u16>, bump: u8) -> Self {
+        Self {
+            game,
+            creator,
+            mint,
+            split_bps,
+            total_funded: 0,
+            total_paid: 0,
+            funders: Vec::new(),
+            claimed: Vec::new(),
+            bump,
+        }
+    }
+    
+    pub fn has_claimed(&self, winner: &Pubkey) -> bool {
+        self.claimed.contains(winner)
+    }
+    
+    // Prizes are computed from total_funded, so no prize can be paid once a refund has gone out
+    pub fn has_refunds(&self) -> bool {
+        self.funders.iter().any(|entry| entry.refunded)
+    }
+    
+    // Every contribution has been paid out as a prize or refunded
+    pub fn is_settled(&self) -> bool {
+        self.total_paid >= self.total_funded
+    }
+    
+    // Share of the pot for the winner at `placement`. Only placements that both have a
+    // winner and a configured share take part, and their shares are rescaled to the whole pot;
+    // the last such placement takes the rounding dust.
+    pub fn prize_for(&self, placement: usize, winners_count: usize) -> u64 {
+        let eligible = winners_count.min(self.split_bps.len());
+        if placement >= eligible {
+            return 0;
+        }
+        
+        let total_bps: u128 = self.split_bps[..eligible].iter().map(|bps| *bps as u128).sum();
+        let share_of = |index: usize| {
+            (self.total_funded as u128 * self.split_bps[index] as u128 / total_bps) as u64
+        };
+        
+        if placement == eligible - 1 {
+            self.total_funded - (0..placement).map(share_of).sum::<u64>()
+        } else {
+            share_of(placement)
+        }
+    }
+}
+
+// Per-creator capsule counter. New capsules are seeded with the next index, and legacy
+// capsules can be given one through a LegacyCapsuleLink.
+#[account]
+pub struct CreatorProfile {
+    pub creator: Pubkey,
+    pub capsule_count: u64, // Indexes handed out so far, to new and registered legacy capsules
+    pub bump: u8,
+}
+
+impl CreatorProfile {
+    pub const LEN: usize = CREATOR_PROFILE_ACCOUNT_SIZE;
+    
+    pub fn next_index(&mut self) -> u64 {
+        let index = self.capsule_count;
+        self.capsule_count += 1;
+        index
+    }
+}
+
+// Stored at the capsule address for an index, pointing at a legacy reveal-date-seeded capsule
+#[account]
+pub struct LegacyCapsuleLink {
+    pub creator: Pubkey,
+    pub capsule: Pubkey,
+    pub capsule_index: u64,
+    pub bump: u8,
+}
+
+impl LegacyCapsuleLink {
+    pub const LEN: usize = LEGACY_CAPSULE_LINK_ACCOUNT_SIZE;
+}
+
+// NFTs minted by the program; each kind has its own verified collection
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum NftKind {
+    Capsule,
+    Badge,
+    Trophy,
+}
+
+impl NftKind {
+    pub fn seed(&self) -> &'static [u8] {
+        match self {
+            NftKind::Capsule => b"capsule",
+            NftKind::Badge => b"badge",
+            NftKind::Trophy => b"trophy",
+        }
+    }
+}
+
+// Placement a winner badge was minted for; the badge mint itself carries no data
+#[account]
+pub struct WinnerBadge {
+    pub game: Pubkey,
+    pub winner: Pubkey,
+    pub badge_mint: Pubkey,
+    pub placement: u8, // 1 for the first verified winner, 2 for the second, ...
+    pub badge_type: String,
+    pub minted_at: i64,
+    pub bump: u8,
+}
+
+impl WinnerBadge {
+    pub const LEN: usize = WINNER_BADGE_ACCOUNT_SIZE;
+    
+    // Badge name for a placement, e.g. "1st Place"; games hold at most 10 winners
+    pub fn badge_type_for(placement: u8) -> String {
+        let suffix = match placement {
+            1 => "st",
+            2 => "nd",
+            3 => "rd",
+            _ => "th",
+        };
+        format!("{}{} Place", placement, suffix)
+    }
+}
+
+// Leaderboard minimums a user must meet to mint a trophy
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct TrophyCriteria {
+    pub min_games_won: u32,
+    pub min_games_played: u32,
+    pub min_capsules_created: u32,
+    pub min_total_points: u64,
+}
+
+impl TrophyCriteria {
+    pub fn is_met_by(&self, leaderboard: &LeaderboardEntry) -> bool {
+        leaderboard.games_won >= self.min_games_won
+            && leaderboard.games_played >= self.min_games_played
+            && leaderboard.capsules_created >= self.min_capsules_created
+            && leaderboard.total_points >= self.min_total_points
+    }
+}
+
+// One PDA per trophy type, created by the vault authority; new trophies launch without a program upgrade
+#[account]
+pub struct TrophyDefinition {
+    pub trophy_type: String,
+    pub criteria: TrophyCriteria,
+    pub season: u16, // 0 for trophies not tied to a season
+    pub season_start: i64, // Trophies can be minted from this time on
+    pub season_end: Option<i64>, // None = mintable until deactivated
+    pub max_supply: Option<u32>, // None = unlimited
+    pub minted: u32,
+    pub metadata_uri_template: String, // TROPHY_SERIAL_PLACEHOLDER is replaced by the serial number
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+impl TrophyDefinition {
+    pub const LEN: usize = TROPHY_DEFINITION_ACCOUNT_SIZE;
+    
+    pub fn is_in_season(&self, now: i64) -> bool {
+        let before_end = match self.season_end {
+            Some(season_end) => now < season_end,
+            None => true,
+        };
+        now >= self.season_start && before_end
+    }
+    
+    pub fn is_sold_out(&self) -> bool {
+        self.max_supply.is_some_and(|max_supply| self.minted >= max_supply)
+    }
+    
+    // Counts the mint and returns its 1-based serial number
+    pub fn record_mint(&mut self) -> u32 {
+        self.minted += 1;
+        self.minted
+    }
+    
+    pub fn metadata_uri(&self, serial: u32) -> String {
+        self.metadata_uri_template
+            .replace(TROPHY_SERIAL_PLACEHOLDER, &serial.to_string())
+    }
+}
+
+// Plaintext of a revealed capsule, accepted only if it hashes to content_integrity_hash
+#[account]
+pub struct PublishedPlaintext {
+    pub capsule: Pubkey,
+    pub publisher: Pubkey,
+    pub plaintext: String,
+    pub content_hash: [u8; 32], // SHA-256 of plaintext, as verified on-chain
+    pub published_at: i64,
+    pub bump: u8,
+}
+
+impl PublishedPlaintext {
+    pub const LEN: usize = PUBLISHED_PLAINTEXT_ACCOUNT_SIZE;
+}
+
+// Creator's share of guess fees across all of their capsules. The lamports are held
+// on this account itself until the creator claims them.
+#[account]
+pub struct CreatorEarnings {
+    pub creator: Pubkey,
+    pub total_earned: u64, // Lifetime earnings across all capsules
+    pub total_claimed: u64,
+    pub available_balance: u64, // Earned but not yet claimed
+    pub last_claim: i64,
+    pub total_guesses_received: u64,
+    pub bump: u8,
+    pub locked_balance: u64, // Part of available_balance from capsules not yet revealed, kept for refunds
+}
+
+impl CreatorEarnings {
+    pub const LEN: usize = CREATOR_EARNINGS_ACCOUNT_SIZE;
+    
+    pub fn accrue(&mut self, amount: u64) {
+        self.total_earned += amount;
+        self.available_balance += amount;
+        self.total_guesses_received += 1;
+    }
+    
+    pub fn lock(&mut self, amount: u64) {
+        self.locked_balance += amount;
+    }
+    
+    pub fn unlock(&mut self, amount: u64) -> Result<()> {
+        self.locked_balance = self
+            .locked_balance
+            .checked_sub(amount)
+            .ok_or(CapsuleXError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    pub fn claimable_balance(&self) -> u64 {
+        self.available_balance.saturating_sub(self.locked_balance)
+    }
+    
+    // Moves the unlocked balance to claimed and returns it
+    pub fn claim(&mut self, now: i64) -> u64 {
+        let amount = self.claimable_balance();
+        self.available_balance -= amount;
+        self.total_claimed += amount;
+        self.last_claim = now;
+        amount
+    }
+    
+    // Takes back up to `amount` of a refunded guess's share, limited to what is still unclaimed
+    pub fn reclaim(&mut self, amount: u64) -> u64 {
+        let amount = amount.min(self.available_balance);
+        self.available_balance -= amount;
+        self.total_earned -= amount;
+        amount
+    }
+}
+
+// Lamports posted by the creator of a gamified capsule, held on this account.
+// Slashed to refund guessers if the capsule is abandoned, otherwise returned.
+#[account]
+pub struct BondAccount {
+    pub capsule: Pubkey,
+    pub creator: Pubkey,
+    pub initial_deposit: u64,
+    pub current_balance: u64,
+    pub refund_per_guess: u64, // Service fee at creation time; sizes the deposit, refunds repay each guess's own fee
+    pub refunds_paid: u32,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl BondAccount {
+    pub const LEN: usize = BOND_ACCOUNT_SIZE;
+    
+    pub fn new(capsule: Pubkey, creator: Pubkey, refund_per_guess: u64, max_guesses: u32, created_at: i64, bump: u8) -> Self {
+        let deposit = refund_per_guess * max_guesses as u64;
+        
+        Self {
+            capsule,
+            creator,
+            initial_deposit: deposit,
+            current_balance: deposit,
+            refund_per_guess,
+            refunds_paid: 0,
+            created_at,
+            bump,
+        }
+    }
+    
+    // Pays up to `amount` of a guess refund from the bond
+    pub fn cover(&mut self, amount: u64) -> u64 {
+        let amount = amount.min(self.current_balance);
+        self.current_balance -= amount;
+        self.refunds_paid += 1;
+        amount
+    }
+}
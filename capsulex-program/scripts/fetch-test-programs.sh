@@ -0,0 +1,15 @@
+#!/usr/bin/env sh
+# Dumps the mainnet programs the local test validator loads through [[test.genesis]] in Anchor.toml.
+# Binaries are not committed (*.so is gitignored); existing dumps are kept.
+set -e
+
+cd "$(dirname "$0")/.."
+mkdir -p tests/fixtures
+
+TOKEN_METADATA_PROGRAM_ID="metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+TOKEN_METADATA_FIXTURE="tests/fixtures/mpl_token_metadata.so"
+
+if [ ! -s "$TOKEN_METADATA_FIXTURE" ]; then
+  echo "Fetching Token Metadata program into $TOKEN_METADATA_FIXTURE"
+  solana program dump -u m "$TOKEN_METADATA_PROGRAM_ID" "$TOKEN_METADATA_FIXTURE"
+fi
@@ -2,7 +2,33 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Capsulex } from "../target/types/capsulex";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
-import { VAULT_SEED } from "../tests/constants";
+import { PROGRAM_CONFIG_SEED, VAULT_SEED } from "../tests/constants";
+
+// Vaults created before ProgramConfig existed need their config account created separately
+async function ensureConfigInitialized(
+  program: Program<Capsulex>,
+  provider: anchor.AnchorProvider
+) {
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PROGRAM_CONFIG_SEED)],
+    program.programId
+  );
+
+  try {
+    await program.account.programConfig.fetch(configPda);
+    console.log(`✅ Program config found: ${configPda.toBase58()}`);
+  } catch (fetchError) {
+    console.log("🔧 Program config missing, initializing default fees...");
+    const tx = await program.methods
+      .initializeConfig()
+      .accounts({
+        authority: provider.wallet.publicKey,
+        config: configPda,
+      } as any)
+      .rpc();
+    console.log(`🎉 Program config initialized: ${tx}`);
+  }
+}
 
 async function initializeProgram() {
   console.log("🚀 Initializing CapsuleX Program...");
@@ -37,6 +63,7 @@ async function initializeProgram() {
       console.log(
         `   Total Fees Collected: ${existingVault.totalFeesCollected.toNumber()}`
       );
+      await ensureConfigInitialized(program, provider);
       return;
     } catch (fetchError) {
       // Vault doesn't exist, proceed with initialization
@@ -3,7 +3,12 @@ import { Program } from "@coral-xyz/anchor";
 import { Capsulex } from "../target/types/capsulex";
 import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
 import { describe, before, it } from "mocha";
-import { VAULT_SEED, CAPSULE_SEED, CAPSULE_MINT_SEED } from "./constants";
+import {
+  VAULT_SEED,
+  CAPSULE_MINT_SEED,
+  PROGRAM_CONFIG_SEED,
+} from "./constants";
+import { nextCapsulePda } from "./capsule";
 import CryptoJS from "crypto-js";
 import { expect } from "chai";
 import crypto from "crypto";
@@ -13,22 +18,6 @@ function createSHA256Hash(content: string): string {
   return crypto.createHash("sha256").update(content, "utf8").digest("hex");
 }
 
-// Helper functions (matching main test file)
-function getCapsulePda(
-  creator: PublicKey,
-  revealDate: anchor.BN,
-  programId: PublicKey
-) {
-  return PublicKey.findProgramAddressSync(
-    [
-      Buffer.from(CAPSULE_SEED),
-      creator.toBuffer(),
-      Buffer.from(revealDate.toArray("le", 8)),
-    ],
-    programId
-  )[0];
-}
-
 function getNftMintPda(capsule: PublicKey, programId: PublicKey) {
   return PublicKey.findProgramAddressSync(
     [Buffer.from(CAPSULE_MINT_SEED), capsule.toBuffer()],
@@ -90,9 +79,7 @@ describe("Authority Reveal Tests", () => {
       program.programId
     );
 
-    // For testing purposes, use the provider wallet as the app authority
-    // In production, this would be the hardcoded app authority key
-    // but for testing we need the private key to sign transactions
+    // The provider wallet initializes the program, so it is the configured app authority
     appAuthorityPubkey = provider.wallet.publicKey;
 
     // Create test keypairs for regular users
@@ -160,11 +147,7 @@ describe("Authority Reveal Tests", () => {
     const currentTime = blockTime || Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 3); // Short for testing
 
-    const capsulePda = getCapsulePda(
-      regularUser.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, regularUser.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const gamePda = getGamePda(capsulePda, program.programId);
 
@@ -196,7 +179,7 @@ describe("Authority Reveal Tests", () => {
 
     // Creator reveals their own capsule
     await program.methods
-      .revealCapsule(revealDate)
+      .revealCapsule()
       .accounts({
         revealer: regularUser.publicKey,
         capsule: capsulePda,
@@ -228,11 +211,7 @@ describe("Authority Reveal Tests", () => {
     const currentTime = blockTime || Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 3); // Short for testing
 
-    const capsulePda = getCapsulePda(
-      regularUser.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, regularUser.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const gamePda = getGamePda(capsulePda, program.programId);
 
@@ -264,7 +243,7 @@ describe("Authority Reveal Tests", () => {
 
     // App authority reveals the user's capsule
     await program.methods
-      .revealCapsule(revealDate)
+      .revealCapsule()
       .accounts({
         revealer: appAuthorityPubkey, // App authority as revealer
         capsule: capsulePda,
@@ -296,11 +275,7 @@ describe("Authority Reveal Tests", () => {
     const currentTime = blockTime || Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 3); // Short for testing
 
-    const capsulePda = getCapsulePda(
-      regularUser.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, regularUser.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const gamePda = getGamePda(capsulePda, program.programId);
 
@@ -333,7 +308,7 @@ describe("Authority Reveal Tests", () => {
     // Unauthorized user tries to reveal
     try {
       await program.methods
-        .revealCapsule(revealDate)
+        .revealCapsule()
         .accounts({
           revealer: unauthorizedUser.publicKey, // Unauthorized user as revealer
           capsule: capsulePda,
@@ -366,11 +341,7 @@ describe("Authority Reveal Tests", () => {
     const currentTime = blockTime || Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 3600); // 1 hour from now
 
-    const capsulePda = getCapsulePda(
-      regularUser.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, regularUser.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const gamePda = getGamePda(capsulePda, program.programId);
 
@@ -400,7 +371,7 @@ describe("Authority Reveal Tests", () => {
     // App authority tries to reveal before reveal date (should fail)
     try {
       await program.methods
-        .revealCapsule(revealDate)
+        .revealCapsule()
         .accounts({
           revealer: appAuthorityPubkey,
           capsule: capsulePda,
@@ -435,11 +406,7 @@ describe("Authority Reveal Tests", () => {
     const currentTime = blockTime || Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 3); // Short for testing
 
-    const capsulePda = getCapsulePda(
-      regularUser.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, regularUser.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const gamePda = getGamePda(capsulePda, program.programId);
 
@@ -471,7 +438,7 @@ describe("Authority Reveal Tests", () => {
 
     // First reveal by creator
     await program.methods
-      .revealCapsule(revealDate)
+      .revealCapsule()
       .accounts({
         revealer: regularUser.publicKey,
         capsule: capsulePda,
@@ -486,7 +453,7 @@ describe("Authority Reveal Tests", () => {
     // Try to reveal again (should fail)
     try {
       await program.methods
-        .revealCapsule(revealDate)
+        .revealCapsule()
         .accounts({
           revealer: appAuthorityPubkey,
           capsule: capsulePda,
@@ -506,20 +473,22 @@ describe("Authority Reveal Tests", () => {
   });
 
   it("🔍 Verify app authority setup for testing", async () => {
-    // This test documents the app authority setup for testing
-    const productionAppAuthority =
-      "FnnLFxD5jZh9dhMbPBYvon3nBWm2gmJDaQnXJhYD2G12";
+    // The app authority lives in ProgramConfig (set to the initializer by initialize_program)
+    const [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(PROGRAM_CONFIG_SEED)],
+      program.programId
+    );
+    const config = await program.account.programConfig.fetch(configPda);
+    const configuredAppAuthority = config.appAuthority.toBase58();
     const testAppAuthority = appAuthorityPubkey.toBase58();
 
-    console.log(`📋 Production App Authority: ${productionAppAuthority}`);
+    console.log(`📋 Configured App Authority: ${configuredAppAuthority}`);
     console.log(`📋 Test App Authority: ${testAppAuthority}`);
     console.log(`📋 Program ID: ${program.programId.toBase58()}`);
-    console.log(`📋 Note: In testing, we use provider wallet as app authority`);
-    console.log(`📋 Note: In production, the hardcoded constant would be used`);
+    console.log(`📋 Note: App authority can be changed with update_config`);
 
     // Verify our test setup
     expect(testAppAuthority).to.equal(provider.wallet.publicKey.toBase58());
-    expect(productionAppAuthority.length).to.equal(44); // Base58 public key length
-    expect(testAppAuthority.length).to.equal(44); // Base58 public key length
+    expect(configuredAppAuthority).to.equal(testAppAuthority);
   });
 });
@@ -0,0 +1,160 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { describe, before, it } from "mocha";
+import { expect } from "chai";
+import { VAULT_SEED, AUTHORITY_PROPOSAL_SEED } from "./constants";
+import { airdrop } from "./capsule";
+
+describe("Vault Authority Transfer", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+  const [proposalPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(AUTHORITY_PROPOSAL_SEED)],
+    program.programId
+  );
+
+  // Stands in for a multisig taking over the vault
+  const multisig = Keypair.generate();
+  const outsider = Keypair.generate();
+
+  async function propose(authority: Keypair | null, newAuthority: PublicKey) {
+    const builder = program.methods.proposeAuthority(newAuthority).accounts({
+      authority: authority ? authority.publicKey : provider.wallet.publicKey,
+      vault: vaultPda,
+      proposal: proposalPda,
+      systemProgram: SystemProgram.programId,
+    } as any);
+    return authority ? builder.signers([authority]).rpc() : builder.rpc();
+  }
+
+  async function accept(newAuthority: Keypair | null, proposer: PublicKey) {
+    const builder = program.methods.acceptAuthority().accounts({
+      newAuthority: newAuthority
+        ? newAuthority.publicKey
+        : provider.wallet.publicKey,
+      currentAuthority: proposer,
+      vault: vaultPda,
+      proposal: proposalPda,
+    } as any);
+    return newAuthority ? builder.signers([newAuthority]).rpc() : builder.rpc();
+  }
+
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await airdrop(provider.connection, multisig.publicKey, 1_000_000_000);
+    await airdrop(provider.connection, outsider.publicKey, 1_000_000_000);
+  });
+
+  it("Rejects proposals from a non-authority", async () => {
+    try {
+      await propose(outsider, outsider.publicKey);
+      expect.fail("Expected InvalidProgramAuthority");
+    } catch (error) {
+      expect(error.message).to.include("InvalidProgramAuthority");
+    }
+  });
+
+  it("Rejects proposing the current authority", async () => {
+    try {
+      await propose(null, provider.wallet.publicKey);
+      expect.fail("Expected InvalidProposedAuthority");
+    } catch (error) {
+      expect(error.message).to.include("InvalidProposedAuthority");
+    }
+  });
+
+  it("Cancels a pending proposal", async () => {
+    await propose(null, outsider.publicKey);
+    const proposal = await program.account.authorityProposal.fetch(
+      proposalPda
+    );
+    expect(proposal.proposedAuthority.toBase58()).to.equal(
+      outsider.publicKey.toBase58()
+    );
+
+    await program.methods
+      .cancelAuthorityProposal()
+      .accounts({
+        authority: provider.wallet.publicKey,
+        vault: vaultPda,
+        proposal: proposalPda,
+      } as any)
+      .rpc();
+
+    const proposalInfo = await provider.connection.getAccountInfo(proposalPda);
+    expect(proposalInfo).to.be.null;
+
+    // A cancelled proposal can no longer be accepted
+    try {
+      await accept(outsider, provider.wallet.publicKey);
+      expect.fail("Expected the cancelled proposal to be gone");
+    } catch (error) {
+      expect(error.message).to.include("AccountNotInitialized");
+    }
+  });
+
+  it("Only the proposed authority can accept", async () => {
+    await propose(null, multisig.publicKey);
+
+    try {
+      await accept(outsider, provider.wallet.publicKey);
+      expect.fail("Expected UnauthorizedPendingAuthority");
+    } catch (error) {
+      expect(error.message).to.include("UnauthorizedPendingAuthority");
+    }
+
+    // Authority is unchanged until the proposal is accepted
+    const vault = await program.account.programVault.fetch(vaultPda);
+    expect(vault.authority.toBase58()).to.equal(
+      provider.wallet.publicKey.toBase58()
+    );
+  });
+
+  it("Transfers the vault authority on acceptance", async () => {
+    await accept(multisig, provider.wallet.publicKey);
+
+    const vault = await program.account.programVault.fetch(vaultPda);
+    expect(vault.authority.toBase58()).to.equal(multisig.publicKey.toBase58());
+    expect(await provider.connection.getAccountInfo(proposalPda)).to.be.null;
+
+    // The previous authority lost its admin rights
+    try {
+      await propose(null, outsider.publicKey);
+      expect.fail("Expected InvalidProgramAuthority");
+    } catch (error) {
+      expect(error.message).to.include("InvalidProgramAuthority");
+    }
+  });
+
+  it("Hands the vault authority back to the provider wallet", async () => {
+    // Restore the original authority so other suites keep admin rights
+    await propose(multisig, provider.wallet.publicKey);
+    await accept(null, multisig.publicKey);
+
+    const vault = await program.account.programVault.fetch(vaultPda);
+    expect(vault.authority.toBase58()).to.equal(
+      provider.wallet.publicKey.toBase58()
+    );
+  });
+});
@@ -0,0 +1,215 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { describe, before, it } from "mocha";
+import { expect } from "chai";
+import {
+  VAULT_SEED,
+  GUESS_SEED,
+  PROGRAM_CONFIG_SEED,
+  CREATOR_EARNINGS_SEED,
+} from "./constants";
+import { airdrop, createCapsule } from "./capsule";
+import { commitGuess } from "./guess";
+
+describe("Creator Bonds", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PROGRAM_CONFIG_SEED)],
+    program.programId
+  );
+
+  const creator = Keypair.generate();
+  const guesser = Keypair.generate();
+
+  const [creatorEarningsPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(CREATOR_EARNINGS_SEED), creator.publicKey.toBuffer()],
+    program.programId
+  );
+
+  function newCapsule(isGamified: boolean, revealIn: number) {
+    return createCapsule(program, {
+      creator,
+      answer: "bond secret",
+      isGamified,
+      revealIn,
+    });
+  }
+
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await airdrop(provider.connection, creator.publicKey, 2_000_000_000);
+    await airdrop(provider.connection, guesser.publicKey, 1_000_000_000);
+  });
+
+  it("Posts a bond proportional to max_guesses for gamified capsules", async () => {
+    const { gamePda, bondPda } = await newCapsule(true, 3600);
+
+    const config = await program.account.programConfig.fetch(configPda);
+    const game = await program.account.game.fetch(gamePda);
+    const bond = await program.account.bondAccount.fetch(bondPda);
+
+    const expectedDeposit = config.serviceFee.toNumber() * game.maxGuesses;
+    expect(bond.creator.toBase58()).to.equal(creator.publicKey.toBase58());
+    expect(bond.refundPerGuess.toNumber()).to.equal(
+      config.serviceFee.toNumber()
+    );
+    expect(bond.initialDeposit.toNumber()).to.equal(expectedDeposit);
+    expect(bond.currentBalance.toNumber()).to.equal(expectedDeposit);
+
+    // The bond lamports sit on top of the account's rent-exempt minimum
+    const bondInfo = await provider.connection.getAccountInfo(bondPda);
+    const rentExempt =
+      await provider.connection.getMinimumBalanceForRentExemption(
+        bondInfo.data.length
+      );
+    expect(bondInfo.lamports).to.equal(rentExempt + expectedDeposit);
+  });
+
+  it("Does not post a bond for plain capsules", async () => {
+    const { bondPda } = await newCapsule(false, 3600);
+
+    const bondInfo = await provider.connection.getAccountInfo(bondPda);
+    expect(bondInfo).to.be.null;
+  });
+
+  it("Keeps the bond locked while the capsule is pending", async () => {
+    const { capsulePda, gamePda, bondPda } = await newCapsule(true, 3600);
+
+    const [guessPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(GUESS_SEED),
+        gamePda.toBuffer(),
+        guesser.publicKey.toBuffer(),
+        Buffer.from([0, 0, 0, 0]),
+      ],
+      program.programId
+    );
+    await program.methods
+      .submitGuess(commitGuess("bond guess").commitment, false)
+      .accounts({
+        guesser: guesser.publicKey,
+        game: gamePda,
+        capsule: capsulePda,
+        guess: guessPda,
+        vault: vaultPda,
+        config: configPda,
+        creatorEarnings: creatorEarningsPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([guesser])
+      .rpc();
+
+    // Guessers can only be refunded after the reveal grace period
+    try {
+      await program.methods
+        .processBondRefund()
+        .accounts({
+          capsule: capsulePda,
+          game: gamePda,
+          bond: bondPda,
+          guess: guessPda,
+          guesser: guesser.publicKey,
+          vault: vaultPda,
+          config: configPda,
+          creatorEarnings: creatorEarningsPda,
+        } as any)
+        .rpc();
+      expect.fail("Expected a refund inside the grace period to fail");
+    } catch (error) {
+      expect(error.message).to.include("BondGracePeriodActive");
+    }
+
+    try {
+      await program.methods
+        .releaseBond()
+        .accounts({
+          creator: creator.publicKey,
+          capsule: capsulePda,
+          game: gamePda,
+          bond: bondPda,
+          config: configPda,
+        } as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("Expected releasing an unrevealed bond to fail");
+    } catch (error) {
+      expect(error.message).to.include("BondNotReleasable");
+    }
+  });
+
+  it("Returns the bond to the creator after reveal", async () => {
+    const { capsulePda, gamePda, bondPda, revealDate } = await newCapsule(
+      true,
+      3
+    );
+
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+    await program.methods
+      .revealCapsule()
+      .accounts({
+        revealer: creator.publicKey,
+        capsule: capsulePda,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    const releaseAccounts = {
+      creator: creator.publicKey,
+      capsule: capsulePda,
+      game: gamePda,
+      bond: bondPda,
+      config: configPda,
+    };
+
+    // Only the creator who posted the bond gets it back
+    try {
+      await program.methods
+        .releaseBond()
+        .accounts({ ...releaseAccounts, creator: guesser.publicKey } as any)
+        .signers([guesser])
+        .rpc();
+      expect.fail("Expected a release by another wallet to fail");
+    } catch (error) {
+      expect(error.message).to.include("UnauthorizedCreator");
+    }
+
+    const bondLamports = await provider.connection.getBalance(bondPda);
+    const balanceBefore = await provider.connection.getBalance(
+      creator.publicKey
+    );
+    await program.methods
+      .releaseBond()
+      .accounts(releaseAccounts as any)
+      .signers([creator])
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(
+      creator.publicKey
+    );
+
+    // Bond plus its rent is returned and the account is closed
+    expect(balanceAfter - balanceBefore).to.equal(bondLamports);
+    expect(await provider.connection.getAccountInfo(bondPda)).to.be.null;
+  });
+});
@@ -0,0 +1,310 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { describe, before, it } from "mocha";
+import { expect } from "chai";
+import {
+  VAULT_SEED,
+  GUESS_SEED,
+  PROGRAM_CONFIG_SEED,
+  CREATOR_EARNINGS_SEED,
+} from "./constants";
+import { airdrop, createCapsule, createSHA256Hash } from "./capsule";
+import { commitGuess } from "./guess";
+
+describe("Capsule Cancellation and Editing", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PROGRAM_CONFIG_SEED)],
+    program.programId
+  );
+
+  const creator = Keypair.generate();
+  const guesser = Keypair.generate();
+
+  const [creatorEarningsPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(CREATOR_EARNINGS_SEED), creator.publicKey.toBuffer()],
+    program.programId
+  );
+
+  function newCapsule(revealIn: number) {
+    return createCapsule(program, {
+      creator,
+      answer: "lifecycle secret",
+      revealIn,
+      encryptedContent: "encrypted with a typo",
+    });
+  }
+
+  async function submitGuess(
+    capsulePda: PublicKey,
+    gamePda: PublicKey,
+    index: number
+  ) {
+    const [guessPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(GUESS_SEED),
+        gamePda.toBuffer(),
+        guesser.publicKey.toBuffer(),
+        Buffer.from([index, 0, 0, 0]),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .submitGuess(commitGuess(`lifecycle guess ${index}`).commitment, false)
+      .accounts({
+        guesser: guesser.publicKey,
+        game: gamePda,
+        capsule: capsulePda,
+        guess: guessPda,
+        vault: vaultPda,
+        config: configPda,
+        creatorEarnings: creatorEarningsPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([guesser])
+      .rpc();
+
+    return guessPda;
+  }
+
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await airdrop(provider.connection, creator.publicKey, 2_000_000_000);
+    await airdrop(provider.connection, guesser.publicKey, 1_000_000_000);
+  });
+
+  describe("update_capsule_content", () => {
+    it("Lets the creator fix content before any guess", async () => {
+      const { capsulePda, gamePda } = await newCapsule(3600);
+      const newHash = createSHA256Hash("lifecycle secret, fixed");
+
+      await program.methods
+        .updateCapsuleContent("encrypted and fixed", { text: {} }, newHash)
+        .accounts({
+          creator: creator.publicKey,
+          capsule: capsulePda,
+          game: gamePda,
+        } as any)
+        .signers([creator])
+        .rpc();
+
+      const capsule = await program.account.capsule.fetch(capsulePda);
+      expect(capsule.encryptedContent).to.equal("encrypted and fixed");
+      expect(capsule.contentIntegrityHash).to.equal(newHash);
+    });
+
+    it("Re-validates the content and integrity hash", async () => {
+      const { capsulePda, gamePda } = await newCapsule(3600);
+
+      try {
+        await program.methods
+          .updateCapsuleContent("encrypted", { text: {} }, "not-a-sha256")
+          .accounts({
+            creator: creator.publicKey,
+            capsule: capsulePda,
+            game: gamePda,
+          } as any)
+          .signers([creator])
+          .rpc();
+        expect.fail("Expected a malformed integrity hash to be rejected");
+      } catch (error) {
+        expect(error.message).to.include("InvalidContentHash");
+      }
+
+      try {
+        await program.methods
+          .updateCapsuleContent(
+            "encrypted",
+            { document: { cid: "not-a-cid" } },
+            createSHA256Hash("lifecycle secret")
+          )
+          .accounts({
+            creator: creator.publicKey,
+            capsule: capsulePda,
+            game: gamePda,
+          } as any)
+          .signers([creator])
+          .rpc();
+        expect.fail("Expected an invalid storage CID to be rejected");
+      } catch (error) {
+        expect(error.message).to.include("InvalidCID");
+      }
+    });
+
+    it("Rejects edits from anyone but the creator", async () => {
+      const { capsulePda, gamePda } = await newCapsule(3600);
+
+      try {
+        await program.methods
+          .updateCapsuleContent(
+            "hijacked",
+            { text: {} },
+            createSHA256Hash("hijacked")
+          )
+          .accounts({
+            creator: guesser.publicKey,
+            capsule: capsulePda,
+            game: gamePda,
+          } as any)
+          .signers([guesser])
+          .rpc();
+        expect.fail("Expected an edit by another wallet to fail");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedCreator");
+      }
+    });
+
+    it("Locks content once a guess has been submitted", async () => {
+      const { capsulePda, gamePda } = await newCapsule(3600);
+      await submitGuess(capsulePda, gamePda, 0);
+
+      try {
+        await program.methods
+          .updateCapsuleContent(
+            "changed after guessing",
+            { text: {} },
+            createSHA256Hash("changed")
+          )
+          .accounts({
+            creator: creator.publicKey,
+            capsule: capsulePda,
+            game: gamePda,
+          } as any)
+          .signers([creator])
+          .rpc();
+        expect.fail("Expected an edit after a guess to fail");
+      } catch (error) {
+        expect(error.message).to.include("CapsuleContentLocked");
+      }
+    });
+  });
+
+  describe("cancel_capsule", () => {
+    it("Deactivates the capsule, ends its game and refunds guessers", async () => {
+      const { capsulePda, gamePda, bondPda, revealDate } =
+        await newCapsule(3600);
+      const guessPda = await submitGuess(capsulePda, gamePda, 0);
+
+      await program.methods
+        .cancelCapsule()
+        .accounts({
+          creator: creator.publicKey,
+          capsule: capsulePda,
+          game: gamePda,
+        } as any)
+        .signers([creator])
+        .rpc();
+
+      const capsule = await program.account.capsule.fetch(capsulePda);
+      const game = await program.account.game.fetch(gamePda);
+      expect(capsule.isActive).to.be.false;
+      expect(game.isActive).to.be.false;
+
+      // No more guesses, and the capsule can never be revealed
+      try {
+        await submitGuess(capsulePda, gamePda, 1);
+        expect.fail("Expected a guess on a cancelled capsule to fail");
+      } catch (error) {
+        expect(error.message).to.include("GameNotActive");
+      }
+      try {
+        await program.methods
+          .revealCapsule()
+          .accounts({
+            revealer: creator.publicKey,
+            capsule: capsulePda,
+          } as any)
+          .signers([creator])
+          .rpc();
+        expect.fail("Expected revealing a cancelled capsule to fail");
+      } catch (error) {
+        expect(error.message).to.include("CapsuleNotReady");
+      }
+
+      // Refunds open immediately, without waiting for the grace period
+      const guess = await program.account.guess.fetch(guessPda);
+      const balanceBefore = await provider.connection.getBalance(
+        guesser.publicKey
+      );
+      const guessRent = await provider.connection.getBalance(guessPda);
+      await program.methods
+        .claimGuessRefund()
+        .accounts({
+          capsule: capsulePda,
+          game: gamePda,
+          guess: guessPda,
+          guesser: guesser.publicKey,
+          bond: bondPda,
+          vault: vaultPda,
+          config: configPda,
+          creatorEarnings: creatorEarningsPda,
+        } as any)
+        .rpc();
+      const balanceAfter = await provider.connection.getBalance(
+        guesser.publicKey
+      );
+      expect(balanceAfter - balanceBefore).to.equal(
+        guess.feePaid.toNumber() + guessRent
+      );
+
+      // With every guesser refunded, the creator gets the rest of the bond back
+      await program.methods
+        .releaseBond()
+        .accounts({
+          creator: creator.publicKey,
+          capsule: capsulePda,
+          game: gamePda,
+          bond: bondPda,
+          config: configPda,
+        } as any)
+        .signers([creator])
+        .rpc();
+      expect(await provider.connection.getAccountInfo(bondPda)).to.be.null;
+    });
+
+    it("Rejects cancelling twice", async () => {
+      const { capsulePda, gamePda } = await newCapsule(3600);
+      const cancel = () =>
+        program.methods
+          .cancelCapsule()
+          .accounts({
+            creator: creator.publicKey,
+            capsule: capsulePda,
+            game: gamePda,
+          } as any)
+          .signers([creator])
+          .rpc();
+
+      await cancel();
+      try {
+        await cancel();
+        expect.fail("Expected a second cancellation to fail");
+      } catch (error) {
+        expect(error.message).to.include("CapsuleNotActive");
+      }
+    });
+  });
+});
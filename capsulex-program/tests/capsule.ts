@@ -0,0 +1,136 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { Connection, Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import crypto from "crypto";
+import {
+  BOND_SEED,
+  CAPSULE_MINT_SEED,
+  CAPSULE_SEED,
+  CREATOR_EARNINGS_SEED,
+  CREATOR_PROFILE_SEED,
+  GAME_SEED,
+  LEADERBOARD_SEED,
+  PROGRAM_CONFIG_SEED,
+  VAULT_SEED,
+} from "./constants";
+
+export function createSHA256Hash(content: string): string {
+  return crypto.createHash("sha256").update(content, "utf8").digest("hex");
+}
+
+export async function airdrop(
+  connection: Connection,
+  recipient: PublicKey,
+  lamports: number
+) {
+  const signature = await connection.requestAirdrop(recipient, lamports);
+  await connection.confirmTransaction(signature);
+}
+
+export function getCreatorProfilePda(creator: PublicKey, programId: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(CREATOR_PROFILE_SEED), creator.toBuffer()],
+    programId
+  )[0];
+}
+
+// Capsules are seeded by the creator's capsule index, so this must be derived
+// right before create_capsule; a creator without a profile starts at index 0
+export async function nextCapsulePda(
+  program: Program<Capsulex>,
+  creator: PublicKey
+) {
+  const profile = await program.account.creatorProfile.fetchNullable(
+    getCreatorProfilePda(creator, program.programId)
+  );
+  const index = profile ? profile.capsuleCount : new anchor.BN(0);
+
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(CAPSULE_SEED),
+      creator.toBuffer(),
+      index.toArrayLike(Buffer, "le", 8),
+    ],
+    program.programId
+  )[0];
+}
+
+// Reveal dates follow the validator clock, which can lag behind Date.now()
+export async function revealDateIn(connection: Connection, seconds: number) {
+  const slot = await connection.getSlot();
+  const blockTime = await connection.getBlockTime(slot);
+  const currentTime = blockTime || Math.floor(Date.now() / 1000);
+  return new anchor.BN(currentTime + seconds);
+}
+
+export interface CreateCapsuleOptions {
+  creator?: Keypair; // Defaults to the provider wallet
+  answer: string; // Hashed into content_integrity_hash
+  revealIn?: number; // Seconds from now; ignored when revealDate is set
+  revealDate?: anchor.BN;
+  isGamified?: boolean;
+  encryptedContent?: string;
+}
+
+// Creates a text capsule and returns its address with the PDAs derived from it
+export async function createCapsule(
+  program: Program<Capsulex>,
+  {
+    creator,
+    answer,
+    revealIn = 3600,
+    revealDate,
+    isGamified = true,
+    encryptedContent = "encrypted",
+  }: CreateCapsuleOptions
+) {
+  const connection = program.provider.connection;
+  const creatorKey = creator
+    ? creator.publicKey
+    : program.provider.publicKey;
+  const derive = (seed: string, key: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from(seed), key.toBuffer()],
+      program.programId
+    )[0];
+
+  revealDate = revealDate ?? (await revealDateIn(connection, revealIn));
+  const capsulePda = await nextCapsulePda(program, creatorKey);
+  const nftMintPda = derive(CAPSULE_MINT_SEED, capsulePda);
+  const gamePda = derive(GAME_SEED, capsulePda);
+  const bondPda = derive(BOND_SEED, capsulePda);
+
+  const builder = program.methods
+    .createCapsule(
+      encryptedContent,
+      { text: {} },
+      createSHA256Hash(answer),
+      revealDate,
+      isGamified
+    )
+    .accounts({
+      creator: creatorKey,
+      creatorProfile: getCreatorProfilePda(creatorKey, program.programId),
+      capsule: capsulePda,
+      nftMint: nftMintPda,
+      vault: PublicKey.findProgramAddressSync(
+        [Buffer.from(VAULT_SEED)],
+        program.programId
+      )[0],
+      config: PublicKey.findProgramAddressSync(
+        [Buffer.from(PROGRAM_CONFIG_SEED)],
+        program.programId
+      )[0],
+      creatorEarnings: derive(CREATOR_EARNINGS_SEED, creatorKey),
+      creatorLeaderboard: derive(LEADERBOARD_SEED, creatorKey),
+      game: gamePda,
+      bond: bondPda,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    } as any);
+  await (creator ? builder.signers([creator]) : builder).rpc();
+
+  return { capsulePda, nftMintPda, gamePda, bondPda, revealDate };
+}
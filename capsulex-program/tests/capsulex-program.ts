@@ -3,7 +3,8 @@ import { Program } from "@coral-xyz/anchor";
 import { Capsulex } from "../target/types/capsulex";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
 import { describe, before, it } from "mocha";
-import { VAULT_SEED, CAPSULE_SEED, CAPSULE_MINT_SEED } from "./constants";
+import { VAULT_SEED, CAPSULE_MINT_SEED } from "./constants";
+import { nextCapsulePda } from "./capsule";
 import CryptoJS from "crypto-js";
 import { expect } from "chai";
 import crypto from "crypto";
@@ -71,15 +72,8 @@ describe("capsulex-program", () => {
     // Simple encryption for demo (in production, use proper AES encryption)
     const encryptedContent = originalContent; // For this basic test, we'll use plain text
 
-    // Find the capsule PDA using reveal_date (new approach)
-    const [capsulePda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from(CAPSULE_SEED),
-        provider.wallet.publicKey.toBuffer(),
-        Buffer.from(revealDate.toArray("le", 8)), // 8 bytes for i64
-      ],
-      program.programId
-    );
+    // Find the capsule PDA from the creator's next capsule index
+    const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
 
     // Find the NFT mint PDA
     const [nftMintPda] = PublicKey.findProgramAddressSync(
@@ -143,15 +137,8 @@ describe("capsulex-program", () => {
       encryptionKey
     ).toString();
 
-    // Find the capsule PDA using reveal_date (timestamp-based addressing)
-    const [capsulePda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from(CAPSULE_SEED),
-        provider.wallet.publicKey.toBuffer(),
-        Buffer.from(revealDate.toArray("le", 8)), // 8 bytes for i64
-      ],
-      program.programId
-    );
+    // Find the capsule PDA from the creator's next capsule index
+    const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
 
     // Find the NFT mint PDA
     const [nftMintPda] = PublicKey.findProgramAddressSync(
@@ -232,22 +219,6 @@ describe("capsulex-program", () => {
     // console.log(`💰 Storage type: ${capsule.contentStorage.onChain ? 'OnChain' : 'IPFS'} (fee: ${capsule.contentStorage.onChain ? '2x' : '1x'})`);
   });
 
-  // --- Helper Functions ---
-  function getCapsulePda(
-    creator: PublicKey,
-    revealDate: anchor.BN,
-    programId: PublicKey
-  ) {
-    return PublicKey.findProgramAddressSync(
-      [
-        Buffer.from(CAPSULE_SEED),
-        creator.toBuffer(),
-        Buffer.from(revealDate.toArray("le", 8)),
-      ],
-      programId
-    )[0];
-  }
-
   function getNftMintPda(capsulePda: PublicKey, programId: PublicKey) {
     return PublicKey.findProgramAddressSync(
       [Buffer.from(CAPSULE_MINT_SEED), capsulePda.toBuffer()],
@@ -288,11 +259,7 @@ describe("capsulex-program", () => {
       "longkey123"
     ).toString();
     const revealDate = new anchor.BN(Math.floor(Date.now() / 1000) + 3600); // 1 hour from now
-    const capsulePda = getCapsulePda(
-      provider.wallet.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const gamePda = getGamePda(capsulePda, program.programId);
     const accounts = getDefaultAccounts({
@@ -326,11 +293,7 @@ describe("capsulex-program", () => {
     const ipfsHash = "Qm" + "a".repeat(44); // Valid mock IPFS hash (46 chars)
     const currentTime = Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 7200); // 2 hours from now
-    const capsulePda = getCapsulePda(
-      provider.wallet.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const gamePda = getGamePda(capsulePda, program.programId);
     const accounts = getDefaultAccounts({
@@ -383,11 +346,7 @@ describe("capsulex-program", () => {
     // console.log(`📅 Reveal date: ${new Date(revealDate.toNumber() * 1000).toLocaleString()}`);
 
     // Create PDAs
-    const capsulePda = getCapsulePda(
-      provider.wallet.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const gamePda = getGamePda(capsulePda, program.programId);
     const accounts = getDefaultAccounts({
@@ -454,11 +413,7 @@ describe("capsulex-program", () => {
     const currentTime = Math.floor(Date.now() / 1000);
     const invalidRevealDate = new anchor.BN(currentTime + 1800); // Only 30 minutes (less than 1 hour minimum)
 
-    const capsulePda = getCapsulePda(
-      provider.wallet.publicKey,
-      invalidRevealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const gamePda = getGamePda(capsulePda, program.programId);
     const accounts = getDefaultAccounts({
@@ -501,11 +456,7 @@ describe("capsulex-program", () => {
     ).toString();
     const currentTime = Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 2); // 2 seconds from now
-    const capsulePda = getCapsulePda(
-      provider.wallet.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const gamePda = getGamePda(capsulePda, program.programId);
     const accounts = getDefaultAccounts({
@@ -531,7 +482,7 @@ describe("capsulex-program", () => {
     await new Promise((resolve) => setTimeout(resolve, 3000));
     // Reveal capsule
     await program.methods
-      .revealCapsule(revealDate)
+      .revealCapsule()
       .accounts({
         creator: provider.wallet.publicKey,
         capsule: capsulePda,
@@ -551,11 +502,7 @@ describe("capsulex-program", () => {
     ).toString();
     const currentTime = Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 10); // 10 seconds from now (unique)
-    const capsulePda = getCapsulePda(
-      provider.wallet.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const gamePda = getGamePda(capsulePda, program.programId);
     const accounts = getDefaultAccounts({
@@ -580,7 +527,7 @@ describe("capsulex-program", () => {
     // Try to reveal before reveal date
     try {
       await program.methods
-        .revealCapsule(revealDate)
+        .revealCapsule()
         .accounts({
           creator: provider.wallet.publicKey,
           capsule: capsulePda,
@@ -602,11 +549,7 @@ describe("capsulex-program", () => {
     ).toString();
     const currentTime = Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 4); // 4 seconds from now (unique)
-    const capsulePda = getCapsulePda(
-      provider.wallet.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const gamePda = getGamePda(capsulePda, program.programId);
     const accounts = getDefaultAccounts({
@@ -632,7 +575,7 @@ describe("capsulex-program", () => {
     await new Promise((resolve) => setTimeout(resolve, 5000));
     // Reveal once
     await program.methods
-      .revealCapsule(revealDate)
+      .revealCapsule()
       .accounts({
         creator: provider.wallet.publicKey,
         capsule: capsulePda,
@@ -641,7 +584,7 @@ describe("capsulex-program", () => {
     // Try to reveal again
     try {
       await program.methods
-        .revealCapsule(revealDate)
+        .revealCapsule()
         .accounts({
           creator: provider.wallet.publicKey,
           capsule: capsulePda,
@@ -0,0 +1,533 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { describe, before, it } from "mocha";
+import { expect } from "chai";
+import {
+  VAULT_SEED,
+  GUESS_SEED,
+  LEADERBOARD_SEED,
+  PROGRAM_CONFIG_SEED,
+  CREATOR_EARNINGS_SEED,
+} from "./constants";
+import { airdrop, createCapsule } from "./capsule";
+import { commitGuess, revealGuess } from "./guess";
+import {
+  ensureOracleRegistered,
+  getUsedNoncePda,
+  loadDevOracleKey,
+  signOracleVerdict,
+} from "./oracle";
+
+describe("Account Closing", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+  const devOracle = loadDevOracleKey();
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PROGRAM_CONFIG_SEED)],
+    program.programId
+  );
+
+  const creator = Keypair.generate();
+  const guesser = Keypair.generate();
+
+  const [creatorEarningsPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(CREATOR_EARNINGS_SEED), creator.publicKey.toBuffer()],
+    program.programId
+  );
+  const [creatorLeaderboardPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(LEADERBOARD_SEED), creator.publicKey.toBuffer()],
+    program.programId
+  );
+  const [guesserLeaderboardPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(LEADERBOARD_SEED), guesser.publicKey.toBuffer()],
+    program.programId
+  );
+  const committedGuess = commitGuess("close guess");
+
+  let capsulePda: PublicKey;
+  let gamePda: PublicKey;
+  let bondPda: PublicKey;
+  let guessPda: PublicKey;
+  let revealDate: anchor.BN;
+
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await airdrop(provider.connection, creator.publicKey, 2_000_000_000);
+    await airdrop(provider.connection, guesser.publicKey, 1_000_000_000);
+    await ensureOracleRegistered(program, provider, devOracle);
+
+    await program.methods
+      .initializeLeaderboard(creator.publicKey)
+      .accounts({
+        authority: creator.publicKey,
+        user: creator.publicKey,
+        leaderboard: creatorLeaderboardPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    ({ capsulePda, gamePda, bondPda, revealDate } = await createCapsule(
+      program,
+      { creator, answer: "close secret", revealIn: 3 }
+    ));
+    [guessPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(GUESS_SEED),
+        gamePda.toBuffer(),
+        guesser.publicKey.toBuffer(),
+        Buffer.from([0, 0, 0, 0]),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .submitGuess(committedGuess.commitment, false)
+      .accounts({
+        guesser: guesser.publicKey,
+        game: gamePda,
+        capsule: capsulePda,
+        guess: guessPda,
+        vault: vaultPda,
+        config: configPda,
+        creatorEarnings: creatorEarningsPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([guesser])
+      .rpc();
+  });
+
+  it("Keeps accounts open while the lifecycle is running", async () => {
+    try {
+      await program.methods
+        .closeGuess()
+        .accounts({
+          guesser: guesser.publicKey,
+          guess: guessPda,
+          game: gamePda,
+          capsule: capsulePda,
+        } as any)
+        .signers([guesser])
+        .rpc();
+      expect.fail("Expected closing a guess in an active game to fail");
+    } catch (error) {
+      expect(error.message).to.include("GameStillActive");
+    }
+
+    try {
+      await program.methods
+        .closeCapsule()
+        .accounts({
+          creator: creator.publicKey,
+          capsule: capsulePda,
+          game: gamePda,
+          bond: bondPda,
+        } as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("Expected closing an unrevealed capsule to fail");
+    } catch (error) {
+      expect(error.message).to.include("CapsuleLifecycleNotFinished");
+    }
+  });
+
+  it("Closes the game to its creator once it is complete", async () => {
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+    await program.methods
+      .revealCapsule()
+      .accounts({
+        revealer: creator.publicKey,
+        capsule: capsulePda,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    // The capsule cannot go before its game and bond
+    try {
+      await program.methods
+        .closeCapsule()
+        .accounts({
+          creator: creator.publicKey,
+          capsule: capsulePda,
+          game: gamePda,
+          bond: bondPda,
+        } as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("Expected closing a capsule with an open game to fail");
+    } catch (error) {
+      expect(error.message).to.include("CapsuleAccountsStillOpen");
+    }
+
+    // The pending guess has to be judged before the game can end
+    await revealGuess(
+      program,
+      committedGuess,
+      {
+        guesser: guesser.publicKey,
+        guess: guessPda,
+        game: gamePda,
+        capsule: capsulePda,
+      },
+      guesser
+    );
+    await program.methods
+      .initializeLeaderboard(guesser.publicKey)
+      .accounts({
+        authority: guesser.publicKey,
+        user: guesser.publicKey,
+        leaderboard: guesserLeaderboardPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([guesser])
+      .rpc();
+    const verdict = signOracleVerdict(
+      devOracle,
+      committedGuess.guess,
+      "close secret",
+      false
+    );
+    await program.methods
+      .verifyGuess(
+        "close secret",
+        null,
+        false,
+        verdict.timestamp,
+        verdict.nonce,
+        verdict.signature
+      )
+      .accounts({
+        authority: creator.publicKey,
+        guess: guessPda,
+        game: gamePda,
+        capsule: capsulePda,
+        leaderboard: guesserLeaderboardPda,
+        usedNonce: getUsedNoncePda(program.programId, verdict.nonce),
+      } as any)
+      .preInstructions([verdict.ed25519Ix])
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .completeGame()
+      .accounts({
+        authority: creator.publicKey,
+        game: gamePda,
+        capsule: capsulePda,
+        creator_leaderboard: creatorLeaderboardPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    const closeGameAccounts = {
+      creator: creator.publicKey,
+      capsule: capsulePda,
+      game: gamePda,
+      bond: bondPda,
+    };
+
+    // Releasing the bond needs the game, so the game waits for it
+    try {
+      await program.methods
+        .closeGame()
+        .accounts(closeGameAccounts as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("Expected closing a game with a posted bond to fail");
+    } catch (error) {
+      expect(error.message).to.include("BondNotReleased");
+    }
+
+    await program.methods
+      .releaseBond()
+      .accounts({
+        creator: creator.publicKey,
+        capsule: capsulePda,
+        game: gamePda,
+        bond: bondPda,
+        config: configPda,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    const gameRent = await provider.connection.getBalance(gamePda);
+    const balanceBefore = await provider.connection.getBalance(
+      creator.publicKey
+    );
+
+    let closedWinners: PublicKey[] | null = null;
+    const listener = program.addEventListener("gameClosed", (event) => {
+      closedWinners = event.winners;
+    });
+    await program.methods
+      .closeGame()
+      .accounts(closeGameAccounts as any)
+      .signers([creator])
+      .rpc();
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await program.removeEventListener(listener);
+
+    const balanceAfter = await provider.connection.getBalance(
+      creator.publicKey
+    );
+    expect(balanceAfter - balanceBefore).to.equal(gameRent);
+    expect(await provider.connection.getAccountInfo(gamePda)).to.be.null;
+    // Winners are preserved in the GameClosed event
+    expect(closedWinners).to.not.be.null;
+  });
+
+  it("Closes a guess to its guesser after the game is gone", async () => {
+    const guessRent = await provider.connection.getBalance(guessPda);
+    const balanceBefore = await provider.connection.getBalance(
+      guesser.publicKey
+    );
+
+    await program.methods
+      .closeGuess()
+      .accounts({
+        guesser: guesser.publicKey,
+        guess: guessPda,
+        game: gamePda,
+        capsule: capsulePda,
+      } as any)
+      .signers([guesser])
+      .rpc();
+
+    const balanceAfter = await provider.connection.getBalance(
+      guesser.publicKey
+    );
+    expect(balanceAfter - balanceBefore).to.equal(guessRent);
+    expect(await provider.connection.getAccountInfo(guessPda)).to.be.null;
+  });
+
+  it("Closes the capsule to its creator last", async () => {
+    const capsuleRent = await provider.connection.getBalance(capsulePda);
+    const balanceBefore = await provider.connection.getBalance(
+      creator.publicKey
+    );
+
+    await program.methods
+      .closeCapsule()
+      .accounts({
+        creator: creator.publicKey,
+        capsule: capsulePda,
+        game: gamePda,
+        bond: bondPda,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    const balanceAfter = await provider.connection.getBalance(
+      creator.publicKey
+    );
+    expect(balanceAfter - balanceBefore).to.equal(capsuleRent);
+    expect(await provider.connection.getAccountInfo(capsulePda)).to.be.null;
+  });
+
+  describe("Cancelled capsules", () => {
+    // A gamified capsule with one paid guess, cancelled before its reveal date
+    async function createCancelledCapsule() {
+      const { capsulePda, gamePda, bondPda } = await createCapsule(program, {
+        creator,
+        answer: "cancelled secret",
+      });
+      const [guessPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GUESS_SEED),
+          gamePda.toBuffer(),
+          guesser.publicKey.toBuffer(),
+          Buffer.from([0, 0, 0, 0]),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .submitGuess(commitGuess("cancelled guess").commitment, false)
+        .accounts({
+          guesser: guesser.publicKey,
+          game: gamePda,
+          capsule: capsulePda,
+          guess: guessPda,
+          vault: vaultPda,
+          config: configPda,
+          creatorEarnings: creatorEarningsPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([guesser])
+        .rpc();
+
+      await program.methods
+        .cancelCapsule()
+        .accounts({
+          creator: creator.publicKey,
+          capsule: capsulePda,
+          game: gamePda,
+        } as any)
+        .signers([creator])
+        .rpc();
+
+      return { capsulePda, gamePda, bondPda, guessPda };
+    }
+
+    async function closeAfterRefunds(accounts: {
+      capsulePda: PublicKey;
+      gamePda: PublicKey;
+      bondPda: PublicKey;
+    }) {
+      await program.methods
+        .releaseBond()
+        .accounts({
+          creator: creator.publicKey,
+          capsule: accounts.capsulePda,
+          game: accounts.gamePda,
+          bond: accounts.bondPda,
+          config: configPda,
+        } as any)
+        .signers([creator])
+        .rpc();
+      await program.methods
+        .closeGame()
+        .accounts({
+          creator: creator.publicKey,
+          capsule: accounts.capsulePda,
+          game: accounts.gamePda,
+          bond: accounts.bondPda,
+        } as any)
+        .signers([creator])
+        .rpc();
+      await program.methods
+        .closeCapsule()
+        .accounts({
+          creator: creator.publicKey,
+          capsule: accounts.capsulePda,
+          game: accounts.gamePda,
+          bond: accounts.bondPda,
+        } as any)
+        .signers([creator])
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(accounts.gamePda)).to.be
+        .null;
+      expect(await provider.connection.getAccountInfo(accounts.capsulePda)).to
+        .be.null;
+    }
+
+    it("Keeps the game and capsule open until every guess is refunded", async () => {
+      const accounts = await createCancelledCapsule();
+
+      try {
+        await program.methods
+          .closeGame()
+          .accounts({
+            creator: creator.publicKey,
+            capsule: accounts.capsulePda,
+            game: accounts.gamePda,
+            bond: accounts.bondPda,
+          } as any)
+          .signers([creator])
+          .rpc();
+        expect.fail("Expected closing a game with unrefunded guesses to fail");
+      } catch (error) {
+        expect(error.message).to.include("GuessRefundsOutstanding");
+      }
+      try {
+        await program.methods
+          .closeCapsule()
+          .accounts({
+            creator: creator.publicKey,
+            capsule: accounts.capsulePda,
+            game: accounts.gamePda,
+            bond: accounts.bondPda,
+          } as any)
+          .signers([creator])
+          .rpc();
+        expect.fail("Expected closing the capsule before its game to fail");
+      } catch (error) {
+        expect(error.message).to.include("CapsuleAccountsStillOpen");
+      }
+
+      // The refund closes the guess and settles it on the game
+      await program.methods
+        .claimGuessRefund()
+        .accounts({
+          capsule: accounts.capsulePda,
+          game: accounts.gamePda,
+          guess: accounts.guessPda,
+          guesser: guesser.publicKey,
+          bond: accounts.bondPda,
+          vault: vaultPda,
+          config: configPda,
+          creatorEarnings: creatorEarningsPda,
+        } as any)
+        .rpc();
+      const game = await program.account.game.fetch(accounts.gamePda);
+      expect(game.guessesSettled).to.equal(game.currentGuesses);
+
+      await closeAfterRefunds(accounts);
+    });
+
+    it("Rejects closing an unrefunded guess until its refund is paid", async () => {
+      const accounts = await createCancelledCapsule();
+      const closeGuessAccounts = {
+        guesser: guesser.publicKey,
+        guess: accounts.guessPda,
+        game: accounts.gamePda,
+        capsule: accounts.capsulePda,
+      };
+
+      try {
+        await program.methods
+          .closeGuess()
+          .accounts(closeGuessAccounts as any)
+          .signers([guesser])
+          .rpc();
+        expect.fail("Expected closing an unrefunded guess to fail");
+      } catch (error) {
+        expect(error.message).to.include("GuessRefundsOutstanding");
+      }
+
+      // The refund closes the guess to its guesser and settles it once
+      await program.methods
+        .processBondRefund()
+        .accounts({
+          capsule: accounts.capsulePda,
+          game: accounts.gamePda,
+          bond: accounts.bondPda,
+          guess: accounts.guessPda,
+          guesser: guesser.publicKey,
+          vault: vaultPda,
+          config: configPda,
+          creatorEarnings: creatorEarningsPda,
+        } as any)
+        .rpc();
+      const game = await program.account.game.fetch(accounts.gamePda);
+      expect(game.guessesSettled).to.equal(1);
+      expect(await provider.connection.getAccountInfo(accounts.guessPda)).to.be
+        .null;
+
+      await closeAfterRefunds(accounts);
+    });
+  });
+});
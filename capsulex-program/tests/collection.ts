@@ -0,0 +1,78 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { VAULT_SEED } from "./constants";
+
+// Loaded into the local validator from tests/fixtures/mpl_token_metadata.so
+export const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
+export function getMetadataPda(mint: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+}
+
+export function getMasterEditionPda(mint: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+      Buffer.from("edition"),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+}
+
+// Metadata accounts start with a 1-byte key followed by the update authority
+export function metadataUpdateAuthority(data: Buffer) {
+  return new PublicKey(data.subarray(1, 33));
+}
+
+// Creates the capsule, badge and trophy collections once per validator
+export async function ensureNftCollections(
+  program: Program<Capsulex>,
+  provider: anchor.AnchorProvider
+) {
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+  const kinds = [
+    { kind: { capsule: {} }, name: "CapsuleX Capsules", symbol: "CXCAPS" },
+    { kind: { badge: {} }, name: "CapsuleX Winner Badges", symbol: "CXBADGE" },
+    { kind: { trophy: {} }, name: "CapsuleX Trophies", symbol: "CXTROPHY" },
+  ];
+
+  for (const { kind, name, symbol } of kinds) {
+    try {
+      await program.methods
+        .initializeNftCollection(
+          kind as any,
+          name,
+          symbol,
+          `https://metadata.capsulex.com/collection/${symbol.toLowerCase()}`
+        )
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Collection already created by another test file
+    }
+  }
+}
@@ -0,0 +1,249 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { describe, before, after, it } from "mocha";
+import { expect } from "chai";
+import { VAULT_SEED, GUESS_SEED, PROGRAM_CONFIG_SEED } from "./constants";
+import { createCapsule } from "./capsule";
+import { commitGuess } from "./guess";
+
+// Default fees written by initialize_program (mirror the Rust DEFAULT_* constants)
+const DEFAULT_CAPSULE_CREATION_FEE = 1_388_889;
+const DEFAULT_SERVICE_FEE = 55_556;
+const DEFAULT_BADGE_MINT_FEE = 25_000;
+const DEFAULT_TROPHY_MINT_FEE = 10_000;
+const DEFAULT_CREATOR_SHARE_BPS = 2500;
+const DEFAULT_REFUND_GRACE_PERIOD = 7 * 24 * 3600;
+const MAX_CONFIG_FEE = 1_000_000_000;
+
+function emptyUpdate() {
+  return {
+    appAuthority: null,
+    capsuleCreationFee: null,
+    serviceFee: null,
+    badgeMintFee: null,
+    trophyMintFee: null,
+    creatorShareBps: null,
+    refundGracePeriod: null,
+    soulboundNfts: null,
+  };
+}
+
+describe("Program Config", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PROGRAM_CONFIG_SEED)],
+    program.programId
+  );
+
+  const outsider = Keypair.generate();
+
+  function configAccounts(authority: PublicKey) {
+    return {
+      authority,
+      vault: vaultPda,
+      config: configPda,
+    };
+  }
+
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await provider.connection.requestAirdrop(
+      outsider.publicKey,
+      1_000_000_000
+    );
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+  });
+
+  after(async () => {
+    // Restore defaults so other suites see the documented fees
+    await program.methods
+      .updateConfig({
+        appAuthority: provider.wallet.publicKey,
+        capsuleCreationFee: new anchor.BN(DEFAULT_CAPSULE_CREATION_FEE),
+        serviceFee: new anchor.BN(DEFAULT_SERVICE_FEE),
+        badgeMintFee: new anchor.BN(DEFAULT_BADGE_MINT_FEE),
+        trophyMintFee: new anchor.BN(DEFAULT_TROPHY_MINT_FEE),
+        creatorShareBps: DEFAULT_CREATOR_SHARE_BPS,
+        refundGracePeriod: new anchor.BN(DEFAULT_REFUND_GRACE_PERIOD),
+        soulboundNfts: false,
+      })
+      .accounts(configAccounts(provider.wallet.publicKey) as any)
+      .rpc();
+  });
+
+  it("Initializes config with the vault authority", async () => {
+    const config = await program.account.programConfig.fetch(configPda);
+
+    expect(config.appAuthority.toBase58()).to.equal(
+      provider.wallet.publicKey.toBase58()
+    );
+    expect(config.serviceFee.toNumber()).to.be.greaterThan(0);
+    expect(config.capsuleCreationFee.toNumber()).to.be.greaterThan(0);
+    expect(config.soulboundNfts).to.be.false;
+    console.log("✅ Config:", {
+      capsuleCreationFee: config.capsuleCreationFee.toNumber(),
+      serviceFee: config.serviceFee.toNumber(),
+      badgeMintFee: config.badgeMintFee.toNumber(),
+      trophyMintFee: config.trophyMintFee.toNumber(),
+      creatorShareBps: config.creatorShareBps,
+      refundGracePeriod: config.refundGracePeriod.toNumber(),
+    });
+  });
+
+  it("Rejects initializing config twice", async () => {
+    try {
+      await program.methods
+        .initializeConfig()
+        .accounts(configAccounts(provider.wallet.publicKey) as any)
+        .rpc();
+      expect.fail("Expected config to already exist");
+    } catch (error) {
+      expect(error.message).to.include("already in use");
+    }
+  });
+
+  it("Rejects config updates from a non-authority", async () => {
+    try {
+      await program.methods
+        .updateConfig({ ...emptyUpdate(), serviceFee: new anchor.BN(1) })
+        .accounts(configAccounts(outsider.publicKey) as any)
+        .signers([outsider])
+        .rpc();
+      expect.fail("Expected InvalidProgramAuthority");
+    } catch (error) {
+      expect(error.message).to.include("InvalidProgramAuthority");
+    }
+  });
+
+  it("Rejects fees above the configured maximum", async () => {
+    try {
+      await program.methods
+        .updateConfig({
+          ...emptyUpdate(),
+          capsuleCreationFee: new anchor.BN(MAX_CONFIG_FEE + 1),
+        })
+        .accounts(configAccounts(provider.wallet.publicKey) as any)
+        .rpc();
+      expect.fail("Expected FeeTooHigh");
+    } catch (error) {
+      expect(error.message).to.include("FeeTooHigh");
+    }
+  });
+
+  it("Charges the updated service fee on submit_guess", async () => {
+    const newServiceFee = 77_777;
+    await program.methods
+      .updateConfig({
+        ...emptyUpdate(),
+        serviceFee: new anchor.BN(newServiceFee),
+      })
+      .accounts(configAccounts(provider.wallet.publicKey) as any)
+      .rpc();
+
+    const config = await program.account.programConfig.fetch(configPda);
+    expect(config.serviceFee.toNumber()).to.equal(newServiceFee);
+    // Untouched fields are preserved
+    expect(config.appAuthority.toBase58()).to.equal(
+      provider.wallet.publicKey.toBase58()
+    );
+
+    const { capsulePda, gamePda } = await createCapsule(program, {
+      answer: "config secret",
+    });
+    const [guessPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(GUESS_SEED),
+        gamePda.toBuffer(),
+        outsider.publicKey.toBuffer(),
+        Buffer.from([0, 0, 0, 0]),
+      ],
+      program.programId
+    );
+
+    const vaultBefore = await program.account.programVault.fetch(vaultPda);
+
+    await program.methods
+      .submitGuess(commitGuess("config guess").commitment, false)
+      .accounts({
+        guesser: outsider.publicKey,
+        game: gamePda,
+        capsule: capsulePda,
+        guess: guessPda,
+        vault: vaultPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([outsider])
+      .rpc();
+
+    // The vault keeps the fee minus the creator's share
+    const creatorShare = Math.floor(
+      (newServiceFee * config.creatorShareBps) / 10_000
+    );
+    const vaultAfter = await program.account.programVault.fetch(vaultPda);
+    const feeCharged = vaultAfter.totalFeesCollected.sub(
+      vaultBefore.totalFeesCollected
+    );
+    expect(feeCharged.toNumber()).to.equal(newServiceFee - creatorShare);
+    console.log(`✅ submit_guess charged the updated fee: ${newServiceFee}`);
+  });
+
+  it("Rejects a creator share above 100%", async () => {
+    try {
+      await program.methods
+        .updateConfig({ ...emptyUpdate(), creatorShareBps: 10_001 })
+        .accounts(configAccounts(provider.wallet.publicKey) as any)
+        .rpc();
+      expect.fail("Expected an out-of-range creator share to be rejected");
+    } catch (error) {
+      expect(error.message).to.include("InvalidCreatorShare");
+    }
+  });
+
+  it("Rejects a non-positive refund grace period", async () => {
+    try {
+      await program.methods
+        .updateConfig({ ...emptyUpdate(), refundGracePeriod: new anchor.BN(0) })
+        .accounts(configAccounts(provider.wallet.publicKey) as any)
+        .rpc();
+      expect.fail("Expected a zero refund grace period to be rejected");
+    } catch (error) {
+      expect(error.message).to.include("InvalidRefundGracePeriod");
+    }
+  });
+
+  it("Updates the app authority used by reveal_capsule", async () => {
+    await program.methods
+      .updateConfig({ ...emptyUpdate(), appAuthority: outsider.publicKey })
+      .accounts(configAccounts(provider.wallet.publicKey) as any)
+      .rpc();
+
+    const config = await program.account.programConfig.fetch(configPda);
+    expect(config.appAuthority.toBase58()).to.equal(
+      outsider.publicKey.toBase58()
+    );
+    // Fees are untouched by an authority-only update
+    expect(config.serviceFee.toNumber()).to.equal(77_777);
+  });
+});
@@ -8,3 +8,19 @@ export const TROPHY_MINT_SEED = "trophy_mint";
 export const GAME_SEED = "game";
 export const GUESS_SEED = "guess";
 export const LEADERBOARD_SEED = "leaderboard";
+export const ORACLE_CONFIG_SEED = "oracle_config";
+export const USED_NONCE_SEED = "used_nonce";
+export const PROGRAM_CONFIG_SEED = "program_config";
+export const TREASURY_SEED = "treasury";
+export const AUTHORITY_PROPOSAL_SEED = "authority_proposal";
+export const POINTS_ISSUER_REGISTRY_SEED = "points_issuers";
+export const PRIZE_POOL_SEED = "prize_pool";
+export const PRIZE_ESCROW_SEED = "prize_escrow";
+export const CREATOR_EARNINGS_SEED = "creator_earnings";
+export const BOND_SEED = "bond";
+export const CREATOR_PROFILE_SEED = "creator_profile";
+export const PUBLISHED_PLAINTEXT_SEED = "plaintext";
+export const WINNER_BADGE_SEED = "winner_badge";
+export const COLLECTION_MINT_SEED = "collection_mint";
+export const COLLECTION_AUTHORITY_SEED = "collection_authority";
+export const TROPHY_DEFINITION_SEED = "trophy_definition";
@@ -3,27 +3,11 @@ import { Program } from "@coral-xyz/anchor";
 import { Capsulex } from "../target/types/capsulex";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
 import { describe, before, it } from "mocha";
-import { VAULT_SEED, CAPSULE_SEED, CAPSULE_MINT_SEED } from "./constants";
+import { VAULT_SEED, CAPSULE_MINT_SEED } from "./constants";
+import { nextCapsulePda } from "./capsule";
 import { expect } from "chai";
 import crypto from "crypto";
 
-// Helper functions for PDA generation
-function getCapsulePda(
-  creator: PublicKey,
-  revealDate: anchor.BN,
-  programId: PublicKey
-) {
-  const [pda] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from(CAPSULE_SEED),
-      creator.toBuffer(),
-      Buffer.from(revealDate.toArray("le", 8)),
-    ],
-    programId
-  );
-  return pda;
-}
-
 function getNftMintPda(capsule: PublicKey, programId: PublicKey) {
   const [pda] = PublicKey.findProgramAddressSync(
     [Buffer.from(CAPSULE_MINT_SEED), capsule.toBuffer()],
@@ -98,11 +82,7 @@ describe("Content Storage System Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3600);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -139,11 +119,7 @@ describe("Content Storage System Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3601);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -183,11 +159,7 @@ describe("Content Storage System Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3602);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -222,11 +194,7 @@ describe("Content Storage System Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3603);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -267,11 +235,7 @@ describe("Content Storage System Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3604);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -317,11 +281,7 @@ describe("Content Storage System Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3605);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -373,11 +333,7 @@ describe("Content Storage System Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3606);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -429,11 +385,7 @@ describe("Content Storage System Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3607);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -478,11 +430,7 @@ describe("Content Storage System Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3608);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -512,11 +460,7 @@ describe("Content Storage System Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3609);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -547,11 +491,7 @@ describe("Content Storage System Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 7777); // Unique timestamp to avoid conflicts
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
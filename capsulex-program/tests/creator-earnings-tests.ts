@@ -0,0 +1,181 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { describe, before, it } from "mocha";
+import { expect } from "chai";
+import {
+  VAULT_SEED,
+  GUESS_SEED,
+  PROGRAM_CONFIG_SEED,
+  CREATOR_EARNINGS_SEED,
+} from "./constants";
+import { airdrop, createCapsule } from "./capsule";
+import { commitGuess } from "./guess";
+
+describe("Creator Earnings", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PROGRAM_CONFIG_SEED)],
+    program.programId
+  );
+
+  // Fresh creator so the earnings account starts empty
+  const creator = Keypair.generate();
+  const guesser = Keypair.generate();
+
+  const [creatorEarningsPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(CREATOR_EARNINGS_SEED), creator.publicKey.toBuffer()],
+    program.programId
+  );
+
+  let capsulePda: PublicKey;
+  let revealDate: anchor.BN;
+
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await airdrop(provider.connection, creator.publicKey, 2_000_000_000);
+    await airdrop(provider.connection, guesser.publicKey, 1_000_000_000);
+  });
+
+  it("Credits the creator's share of each guess fee", async () => {
+    const created = await createCapsule(program, {
+      creator,
+      answer: "earnings secret",
+      revealIn: 5,
+    });
+    ({ capsulePda, revealDate } = created);
+    const gamePda = created.gamePda;
+
+    let earnings = await program.account.creatorEarnings.fetch(
+      creatorEarningsPda
+    );
+    expect(earnings.creator.toBase58()).to.equal(creator.publicKey.toBase58());
+    expect(earnings.availableBalance.toNumber()).to.equal(0);
+
+    const config = await program.account.programConfig.fetch(configPda);
+    const creatorShare = Math.floor(
+      (config.serviceFee.toNumber() * config.creatorShareBps) / 10_000
+    );
+
+    for (let i = 0; i < 2; i++) {
+      const [guessPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GUESS_SEED),
+          gamePda.toBuffer(),
+          guesser.publicKey.toBuffer(),
+          Buffer.from([i, 0, 0, 0]),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .submitGuess(commitGuess(`earnings guess ${i}`).commitment, false)
+        .accounts({
+          guesser: guesser.publicKey,
+          game: gamePda,
+          capsule: capsulePda,
+          guess: guessPda,
+          vault: vaultPda,
+          config: configPda,
+          creatorEarnings: creatorEarningsPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([guesser])
+        .rpc();
+    }
+
+    earnings = await program.account.creatorEarnings.fetch(creatorEarningsPda);
+    expect(earnings.totalGuessesReceived.toNumber()).to.equal(2);
+    expect(earnings.totalEarned.toNumber()).to.equal(2 * creatorShare);
+    expect(earnings.availableBalance.toNumber()).to.equal(2 * creatorShare);
+    expect(earnings.lockedBalance.toNumber()).to.equal(2 * creatorShare);
+  });
+
+  it("Pays out the available balance to the creator once", async () => {
+    const claimAccounts = {
+      creator: creator.publicKey,
+      creatorEarnings: creatorEarningsPda,
+    };
+
+    // Shares stay locked for refunds until the capsule is revealed
+    try {
+      await program.methods
+        .claimCreatorEarnings()
+        .accounts(claimAccounts as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("Expected a claim of locked earnings to fail");
+    } catch (error) {
+      expect(error.message).to.include("NoCreatorEarnings");
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 6000));
+    await program.methods
+      .revealCapsule()
+      .accounts({
+        revealer: creator.publicKey,
+        capsule: capsulePda,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    const before = await program.account.creatorEarnings.fetch(
+      creatorEarningsPda
+    );
+    const available = before.availableBalance.toNumber();
+    expect(available).to.be.greaterThan(0);
+    expect(before.lockedBalance.toNumber()).to.equal(0);
+
+    const balanceBefore = await provider.connection.getBalance(
+      creator.publicKey
+    );
+    await program.methods
+      .claimCreatorEarnings()
+      .accounts(claimAccounts as any)
+      .signers([creator])
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(
+      creator.publicKey
+    );
+    expect(balanceAfter - balanceBefore).to.equal(available);
+
+    const after = await program.account.creatorEarnings.fetch(
+      creatorEarningsPda
+    );
+    expect(after.availableBalance.toNumber()).to.equal(0);
+    expect(after.totalClaimed.toNumber()).to.equal(available);
+    expect(after.lastClaim.toNumber()).to.be.greaterThan(0);
+
+    try {
+      await program.methods
+        .claimCreatorEarnings()
+        .accounts(claimAccounts as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("Expected a claim with nothing available to fail");
+    } catch (error) {
+      expect(error.message).to.include("NoCreatorEarnings");
+    }
+  });
+});
@@ -0,0 +1,111 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { describe, before, it } from "mocha";
+import { expect } from "chai";
+import { VAULT_SEED, CAPSULE_SEED } from "./constants";
+import {
+  airdrop,
+  createCapsule,
+  getCreatorProfilePda,
+  nextCapsulePda,
+} from "./capsule";
+
+describe("Creator Profiles", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+
+  const creator = Keypair.generate();
+  const creatorProfilePda = getCreatorProfilePda(
+    creator.publicKey,
+    program.programId
+  );
+
+  async function newCapsule(revealDate: anchor.BN) {
+    const { capsulePda } = await createCapsule(program, {
+      creator,
+      answer: "profile secret",
+      revealDate,
+      isGamified: false,
+    });
+    return capsulePda;
+  }
+
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await airdrop(provider.connection, creator.publicKey, 2_000_000_000);
+  });
+
+  it("Addresses capsules by a per-creator counter", async () => {
+    const revealDate = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+
+    // Two capsules sharing a reveal date no longer collide
+    const first = await newCapsule(revealDate);
+    const second = await newCapsule(revealDate);
+    expect(first.toBase58()).to.not.equal(second.toBase58());
+
+    const [expectedFirst] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(CAPSULE_SEED),
+        creator.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    expect(first.toBase58()).to.equal(expectedFirst.toBase58());
+
+    const profile = await program.account.creatorProfile.fetch(
+      creatorProfilePda
+    );
+    expect(profile.creator.toBase58()).to.equal(creator.publicKey.toBase58());
+    expect(profile.capsuleCount.toNumber()).to.equal(2);
+
+    const capsule = await program.account.capsule.fetch(second);
+    expect(capsule.capsuleIndex.toNumber()).to.equal(1);
+    expect(capsule.legacyIndex).to.be.null;
+  });
+
+  it("Rejects registering a capsule that already has an index", async () => {
+    const capsulePda = await newCapsule(
+      new anchor.BN(Math.floor(Date.now() / 1000) + 7200)
+    );
+    const capsuleLinkPda = await nextCapsulePda(program, creator.publicKey);
+
+    try {
+      await program.methods
+        .registerLegacyCapsule()
+        .accounts({
+          creator: creator.publicKey,
+          creatorProfile: creatorProfilePda,
+          capsule: capsulePda,
+          capsuleLink: capsuleLinkPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("Expected an indexed capsule to be rejected");
+    } catch (error) {
+      expect(error.message).to.include("CapsuleAlreadyIndexed");
+    }
+  });
+});
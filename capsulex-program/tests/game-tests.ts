@@ -3,33 +3,24 @@ import { Program } from "@coral-xyz/anchor";
 import { Capsulex } from "../target/types/capsulex";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
 import { describe, before, it } from "mocha";
-import { VAULT_SEED, CAPSULE_SEED, CAPSULE_MINT_SEED } from "./constants";
+import { VAULT_SEED, CAPSULE_MINT_SEED } from "./constants";
+import { nextCapsulePda } from "./capsule";
 import CryptoJS from "crypto-js";
 import { expect } from "chai";
 import crypto from "crypto";
+import {
+  ensureOracleRegistered,
+  getUsedNoncePda,
+  loadDevOracleKey,
+  signOracleVerdict,
+} from "./oracle";
+import { commitGuess, revealGuess } from "./guess";
 
 // Helper function to create content integrity hash
 function createSHA256Hash(content: string): string {
   return crypto.createHash("sha256").update(content, "utf8").digest("hex");
 }
 
-// Helper functions for PDA generation (matching existing test style)
-function getCapsulePda(
-  creator: PublicKey,
-  revealDate: anchor.BN,
-  programId: PublicKey
-) {
-  const [pda] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from(CAPSULE_SEED),
-      creator.toBuffer(),
-      Buffer.from(revealDate.toArray("le", 8)),
-    ],
-    programId
-  );
-  return pda;
-}
-
 // KeyVault removed - no longer needed
 // function getKeyVaultPda(capsule: PublicKey, programId: PublicKey) {
 //   const [pda] = PublicKey.findProgramAddressSync(
@@ -82,6 +73,7 @@ describe("CapsuleX Game Instructions", () => {
   anchor.setProvider(provider);
 
   const program = anchor.workspace.capsulex as Program<Capsulex>;
+  const devOracle = loadDevOracleKey();
 
   before(async () => {
     console.log("Starting tests with device-side encryption");
@@ -117,6 +109,9 @@ describe("CapsuleX Game Instructions", () => {
         // Continue with tests even if vault init fails (might already exist)
       }
     }
+
+    // Register the dev oracle so verify_guess accepts its signatures
+    await ensureOracleRegistered(program, provider, devOracle);
   });
 
   it("Game: Initialize game with correct parameters", async () => {
@@ -125,11 +120,7 @@ describe("CapsuleX Game Instructions", () => {
 
     const currentTime = Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 100); // Unique timestamp
-    const capsulePda = getCapsulePda(
-      capsuleCreator.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, capsuleCreator.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     // Get the game PDA (will be created automatically by createCapsule when is_gamified=true)
     const gamePda = getGamePda(capsulePda, program.programId);
@@ -183,11 +174,7 @@ describe("CapsuleX Game Instructions", () => {
 
     const currentTime = Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 200); // Unique timestamp
-    const capsulePda = getCapsulePda(
-      capsuleCreator.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, capsuleCreator.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const gamePda = getGamePda(capsulePda, program.programId);
     const accounts = {
@@ -222,11 +209,13 @@ describe("CapsuleX Game Instructions", () => {
       program.programId
     );
 
+    const publicCommit = commitGuess("public guess");
     await program.methods
-      .submitGuess("public guess", false)
+      .submitGuess(publicCommit.commitment, false)
       .accounts({
         guesser: gamePlayer.publicKey,
         game: gamePda,
+        capsule: capsulePda,
         guess: freePda,
         vault: getVaultPda(program.programId),
         systemProgram: SystemProgram.programId,
@@ -244,11 +233,13 @@ describe("CapsuleX Game Instructions", () => {
       program.programId
     );
 
+    const anonymousCommit = commitGuess("anonymous guess");
     await program.methods
-      .submitGuess("anonymous guess", true)
+      .submitGuess(anonymousCommit.commitment, true)
       .accounts({
         guesser: gamePlayer.publicKey,
         game: gamePda,
+        capsule: capsulePda,
         guess: paidPda,
         vault: getVaultPda(program.programId),
         systemProgram: SystemProgram.programId,
@@ -259,12 +250,18 @@ describe("CapsuleX Game Instructions", () => {
     const publicGuess = await program.account.guess.fetch(freePda);
     expect(publicGuess.isPaid).to.be.true; // All guesses now pay service fee
     expect(publicGuess.isAnonymous).to.be.false;
-    expect(publicGuess.guessContent).to.equal("public guess");
+    // Guess content stays sealed until it is revealed after the capsule opens
+    expect(publicGuess.guessContent).to.equal("");
+    expect(publicGuess.commitment).to.deep.equal(publicCommit.commitment);
+    expect(publicGuess.isOpened).to.be.false;
 
     const anonymousGuess = await program.account.guess.fetch(paidPda);
     expect(anonymousGuess.isPaid).to.be.true; // All guesses now pay service fee
     expect(anonymousGuess.isAnonymous).to.be.true;
-    expect(anonymousGuess.guessContent).to.equal("anonymous guess");
+    expect(anonymousGuess.guessContent).to.equal("");
+    expect(anonymousGuess.commitment).to.deep.equal(
+      anonymousCommit.commitment
+    );
 
     const game = await program.account.game.fetch(gamePda);
     expect(game.currentGuesses).to.equal(2);
@@ -278,11 +275,7 @@ describe("CapsuleX Game Instructions", () => {
 
     const currentTime = Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 300); // Unique timestamp
-    const capsulePda = getCapsulePda(
-      capsuleCreator.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, capsuleCreator.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const accounts = {
       creator: capsuleCreator.publicKey,
@@ -331,12 +324,13 @@ describe("CapsuleX Game Instructions", () => {
 
     await program.methods
       .submitGuess(
-        "wrong guess from gamePlayer1",
+        commitGuess("wrong guess from gamePlayer1").commitment,
         false // is_anonymous (public)
       )
       .accounts({
         guesser: gamePlayer1.publicKey,
         game: gamePda,
+        capsule: capsulePda,
         guess: guess1Pda,
         vault: getVaultPda(program.programId),
         systemProgram: SystemProgram.programId,
@@ -357,12 +351,13 @@ describe("CapsuleX Game Instructions", () => {
 
     await program.methods
       .submitGuess(
-        "another wrong guess",
+        commitGuess("another wrong guess").commitment,
         true // is_anonymous
       )
       .accounts({
         guesser: gamePlayer2.publicKey,
         game: gamePda,
+        capsule: capsulePda,
         guess: guess2Pda,
         vault: getVaultPda(program.programId),
         systemProgram: SystemProgram.programId,
@@ -406,11 +401,7 @@ describe("CapsuleX Game Instructions", () => {
     const currentTime = blockTime || Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 3); // Short for testing
     const secretAnswer = "The answer is 42";
-    const capsulePda = getCapsulePda(
-      capsuleCreator.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, capsuleCreator.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const accounts = {
       creator: capsuleCreator.publicKey,
@@ -462,11 +453,13 @@ describe("CapsuleX Game Instructions", () => {
       program.programId
     );
 
+    const correctCommit = commitGuess(secretAnswer);
     await program.methods
-      .submitGuess(secretAnswer, true)
+      .submitGuess(correctCommit.commitment, true)
       .accounts({
         guesser: gamePlayer.publicKey,
         game: gamePda,
+        capsule: capsulePda,
         guess: correctGuessPda,
         vault: getVaultPda(program.programId),
         systemProgram: SystemProgram.programId,
@@ -485,11 +478,13 @@ describe("CapsuleX Game Instructions", () => {
       program.programId
     );
 
+    const wrongCommit = commitGuess("Wrong answer");
     await program.methods
-      .submitGuess("Wrong answer", true)
+      .submitGuess(wrongCommit.commitment, true)
       .accounts({
         guesser: gamePlayer.publicKey,
         game: gamePda,
+        capsule: capsulePda,
         guess: wrongGuessPda,
         vault: getVaultPda(program.programId),
         systemProgram: SystemProgram.programId,
@@ -510,7 +505,7 @@ describe("CapsuleX Game Instructions", () => {
 
     // Reveal capsule (capsule creator reveals their capsule)
     await program.methods
-      .revealCapsule(revealDate)
+      .revealCapsule()
       .accounts({
         creator: capsuleCreator.publicKey,
         capsule: capsulePda,
@@ -518,6 +513,24 @@ describe("CapsuleX Game Instructions", () => {
       .signers([capsuleCreator.payer])
       .rpc();
 
+    // Game player opens both committed guesses now that the capsule is revealed
+    for (const [committed, guessPda] of [
+      [correctCommit, correctGuessPda],
+      [wrongCommit, wrongGuessPda],
+    ] as const) {
+      await revealGuess(
+        program,
+        committed,
+        {
+          guesser: gamePlayer.publicKey,
+          guess: guessPda,
+          game: gamePda,
+          capsule: capsulePda,
+        },
+        gamePlayer
+      );
+    }
+
     // Initialize leaderboard for verification (game player initializes their leaderboard)
     const [leaderboardPda] = PublicKey.findProgramAddressSync(
       [Buffer.from("leaderboard"), gamePlayer.publicKey.toBuffer()],
@@ -540,44 +553,58 @@ describe("CapsuleX Game Instructions", () => {
       console.log("Leaderboard already exists, continuing...");
     }
 
-    // Verify wrong guess first (game player verifies their guess)
+    // Verify wrong guess first (capsule creator verifies guesses for their game)
+    const wrongVerdict = signOracleVerdict(
+      devOracle,
+      "Wrong answer",
+      secretAnswer,
+      false
+    );
     await program.methods
       .verifyGuess(
         secretAnswer, // decrypted_content
         null, // verification_window_hours
         false, // semantic_result
-        new anchor.BN(Math.floor(Date.now() / 1000)), // oracle_timestamp
-        "test_nonce_wrong", // oracle_nonce
-        "" // oracle_signature (empty for fallback test)
+        wrongVerdict.timestamp,
+        wrongVerdict.nonce,
+        wrongVerdict.signature
       )
       .accounts({
-        authority: gamePlayer.publicKey,
+        authority: capsuleCreator.publicKey,
         guess: wrongGuessPda,
         game: gamePda,
         capsule: capsulePda,
         leaderboard: leaderboardPda,
+        usedNonce: getUsedNoncePda(program.programId, wrongVerdict.nonce),
       } as any)
-      .signers([gamePlayer])
+      .preInstructions([wrongVerdict.ed25519Ix])
       .rpc();
 
-    // Verify correct guess (game player verifies their winning guess)
+    // Verify correct guess (capsule creator verifies the winning guess)
+    const correctVerdict = signOracleVerdict(
+      devOracle,
+      secretAnswer,
+      secretAnswer,
+      true
+    );
     await program.methods
       .verifyGuess(
         secretAnswer, // decrypted_content
         null, // verification_window_hours (default 1 hour)
         true, // semantic_result
-        new anchor.BN(Math.floor(Date.now() / 1000)), // oracle_timestamp
-        "test_nonce_correct", // oracle_nonce
-        "" // oracle_signature (empty for fallback test)
+        correctVerdict.timestamp,
+        correctVerdict.nonce,
+        correctVerdict.signature
       )
       .accounts({
-        authority: gamePlayer.publicKey,
+        authority: capsuleCreator.publicKey,
         guess: correctGuessPda,
         game: gamePda,
         capsule: capsulePda,
         leaderboard: leaderboardPda,
+        usedNonce: getUsedNoncePda(program.programId, correctVerdict.nonce),
       } as any)
-      .signers([gamePlayer])
+      .preInstructions([correctVerdict.ed25519Ix])
       .rpc();
 
     // Check results
@@ -602,11 +629,7 @@ describe("CapsuleX Game Instructions", () => {
     const blockTime = await provider.connection.getBlockTime(slot);
     const currentTime = blockTime || Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 3); // Short for testing
-    const capsulePda = getCapsulePda(
-      capsuleCreator.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, capsuleCreator.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const accounts = {
       creator: capsuleCreator.publicKey,
@@ -653,11 +676,13 @@ describe("CapsuleX Game Instructions", () => {
       program.programId
     );
 
+    const rewardCommit = commitGuess("reward test");
     await program.methods
-      .submitGuess("reward test", true)
+      .submitGuess(rewardCommit.commitment, true)
       .accounts({
         guesser: gamePlayerWinner.publicKey,
         game: gamePda,
+        capsule: capsulePda,
         guess: guessPda,
         vault: getVaultPda(program.programId),
         systemProgram: SystemProgram.programId,
@@ -668,13 +693,25 @@ describe("CapsuleX Game Instructions", () => {
     // Wait and reveal (capsule creator reveals capsule)
     await new Promise((resolve) => setTimeout(resolve, 5000));
     await program.methods
-      .revealCapsule(revealDate)
+      .revealCapsule()
       .accounts({
         creator: capsuleCreator.publicKey,
         capsule: capsulePda,
       } as any)
       .rpc();
 
+    await revealGuess(
+      program,
+      rewardCommit,
+      {
+        guesser: gamePlayerWinner.publicKey,
+        guess: guessPda,
+        game: gamePda,
+        capsule: capsulePda,
+      },
+      gamePlayerWinner
+    );
+
     // Initialize leaderboard and verify guess (game player manages their leaderboard)
     const [leaderboardPda] = PublicKey.findProgramAddressSync(
       [Buffer.from("leaderboard"), gamePlayerWinner.publicKey.toBuffer()],
@@ -692,23 +729,31 @@ describe("CapsuleX Game Instructions", () => {
       .signers([gamePlayerWinner])
       .rpc();
 
+    // Oracle verdict signed by the dev semantic service key
+    const rewardVerdict = signOracleVerdict(
+      devOracle,
+      "reward test",
+      "reward test",
+      true
+    );
     await program.methods
       .verifyGuess(
         "reward test",
         null,
         true,
-        new anchor.BN(Math.floor(Date.now() / 1000)), // oracle_timestamp
-        "test_nonce_reward", // oracle_nonce
-        "" // oracle_signature (empty for fallback test)
+        rewardVerdict.timestamp,
+        rewardVerdict.nonce,
+        rewardVerdict.signature
       )
       .accounts({
-        authority: gamePlayerWinner.publicKey,
+        authority: capsuleCreator.publicKey,
         guess: guessPda,
         game: gamePda,
         capsule: capsulePda,
         leaderboard: leaderboardPda,
+        usedNonce: getUsedNoncePda(program.programId, rewardVerdict.nonce),
       } as any)
-      .signers([gamePlayerWinner])
+      .preInstructions([rewardVerdict.ed25519Ix])
       .rpc();
 
     // Initialize creator leaderboard for complete game (capsule creator manages their leaderboard)
@@ -775,11 +820,7 @@ describe("CapsuleX Game Instructions", () => {
 
     const currentTime = Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 700); // Unique timestamp - far in future
-    const capsulePda = getCapsulePda(
-      capsuleCreator.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, capsuleCreator.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const accounts = {
       creator: capsuleCreator.publicKey,
@@ -818,11 +859,13 @@ describe("CapsuleX Game Instructions", () => {
       program.programId
     );
 
+    const testCommit = commitGuess("test");
     await program.methods
-      .submitGuess("test", true)
+      .submitGuess(testCommit.commitment, true)
       .accounts({
         guesser: gamePlayer.publicKey,
         game: gamePda,
+        capsule: capsulePda,
         guess: guessPda,
         vault: getVaultPda(program.programId),
         systemProgram: SystemProgram.programId,
@@ -847,30 +890,58 @@ describe("CapsuleX Game Instructions", () => {
       .signers([gamePlayer])
       .rpc();
 
+    // Guesses cannot be opened until the capsule's reveal date
+    try {
+      await revealGuess(
+        program,
+        testCommit,
+        {
+          guesser: gamePlayer.publicKey,
+          guess: guessPda,
+          game: gamePda,
+          capsule: capsulePda,
+        },
+        gamePlayer
+      );
+
+      expect.fail("Expected guess reveal to fail before the reveal date");
+    } catch (error) {
+      expect(error.message).to.include("GuessRevealNotStarted");
+    }
+
     // Try to verify guess before reveal (should fail)
+    const failVerdict = signOracleVerdict(
+      devOracle,
+      "test",
+      "test",
+      false
+    );
     try {
       await program.methods
         .verifyGuess(
           "test",
           null,
           false,
-          new anchor.BN(Math.floor(Date.now() / 1000)), // oracle_timestamp
-          "test_nonce_fail", // oracle_nonce
-          "" // oracle_signature (empty for fallback test)
+          failVerdict.timestamp,
+          failVerdict.nonce,
+          failVerdict.signature
         )
         .accounts({
-          authority: gamePlayer.publicKey, // Player verifies their own guess
+          authority: capsuleCreator.publicKey, // Creator verifies guesses
           guess: guessPda,
           game: gamePda,
           capsule: capsulePda,
           leaderboard: leaderboardPda,
+          usedNonce: getUsedNoncePda(program.programId, failVerdict.nonce),
         } as any)
-        .signers([gamePlayer])
+        .preInstructions([failVerdict.ed25519Ix])
+        .signers([capsuleCreator])
         .rpc();
 
       expect.fail("Expected verification to fail before reveal");
     } catch (error) {
-      expect(error.message).to.include("CapsuleNotReady");
+      // The guess is still sealed, so it cannot be judged yet
+      expect(error.message).to.include("GuessNotOpened");
       // console.log("✅ Correctly prevented verification before reveal");
     }
   });
@@ -881,11 +952,7 @@ describe("CapsuleX Game Instructions", () => {
 
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 100);
-      const capsulePda = getCapsulePda(
-        capsuleCreator.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, capsuleCreator.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const accounts = {
         creator: capsuleCreator.publicKey,
@@ -933,6 +1000,25 @@ describe("CapsuleX Game Instructions", () => {
         // May already exist
       }
 
+      // Only the creator or app authority can complete the game
+      const outsider = anchor.web3.Keypair.generate();
+      try {
+        await program.methods
+          .completeGame()
+          .accounts({
+            authority: outsider.publicKey,
+            game: gamePda,
+            capsule: capsulePda,
+            creator_leaderboard: creatorLeaderboardPda,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .signers([outsider])
+          .rpc();
+        expect.fail("Outsider should not be able to complete the game");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedGameCompletion");
+      }
+
       await program.methods
         .completeGame()
         .accounts({
@@ -947,9 +1033,11 @@ describe("CapsuleX Game Instructions", () => {
       const finalGame = await program.account.game.fetch(gamePda);
       expect(finalGame.isActive).to.be.false;
       expect(finalGame.currentGuesses).to.equal(0);
+      // No participants, so no creator bonus
+      expect(finalGame.bonusPaid).to.be.false;
     });
 
-    it("Complete Game: After reveal date (creator can end anytime)", async () => {
+    it("Complete Game: After reveal once every guess is verified", async () => {
       const capsuleCreator = provider.wallet;
       const gamePlayer = anchor.web3.Keypair.generate();
 
@@ -961,11 +1049,7 @@ describe("CapsuleX Game Instructions", () => {
 
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3); // Short reveal time
-      const capsulePda = getCapsulePda(
-        capsuleCreator.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, capsuleCreator.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const accounts = {
         creator: capsuleCreator.publicKey,
@@ -1003,11 +1087,13 @@ describe("CapsuleX Game Instructions", () => {
         program.programId
       );
 
+      const wrongCommit = commitGuess("wrong guess");
       await program.methods
-        .submitGuess("wrong guess", false)
+        .submitGuess(wrongCommit.commitment, false)
         .accounts({
           guesser: gamePlayer.publicKey,
           game: gamePda,
+          capsule: capsulePda,
           guess: guessPda,
           vault: getVaultPda(program.programId),
           systemProgram: SystemProgram.programId,
@@ -1052,29 +1138,126 @@ describe("CapsuleX Game Instructions", () => {
       // Wait for reveal time and reveal
       await new Promise((resolve) => setTimeout(resolve, 4000));
       await program.methods
-        .revealCapsule(revealDate)
+        .revealCapsule()
         .accounts({
           creator: capsuleCreator.publicKey,
           capsule: capsulePda,
         } as any)
         .rpc();
 
-      // Now should be able to complete after reveal
+      const completeAccounts = {
+        authority: capsuleCreator.publicKey,
+        game: gamePda,
+        capsule: capsulePda,
+        creator_leaderboard: creatorLeaderboardPda,
+        systemProgram: SystemProgram.programId,
+      };
+
+      // Revealing alone does not end the game while a guess awaits verification
+      try {
+        await program.methods
+          .completeGame()
+          .accounts(completeAccounts as any)
+          .rpc();
+        expect.fail("Should not be able to complete with an unverified guess");
+      } catch (error) {
+        expect(error.message).to.include("GameNotEnded");
+      }
+
+      await revealGuess(
+        program,
+        wrongCommit,
+        {
+          guesser: gamePlayer.publicKey,
+          guess: guessPda,
+          game: gamePda,
+          capsule: capsulePda,
+        },
+        gamePlayer
+      );
+
+      const [playerLeaderboardPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("leaderboard"), gamePlayer.publicKey.toBuffer()],
+        program.programId
+      );
       await program.methods
-        .completeGame()
+        .initializeLeaderboard(gamePlayer.publicKey)
+        .accounts({
+          authority: gamePlayer.publicKey,
+          user: gamePlayer.publicKey,
+          leaderboard: playerLeaderboardPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([gamePlayer])
+        .rpc();
+
+      const wrongVerdict = signOracleVerdict(
+        devOracle,
+        "wrong guess",
+        content,
+        false
+      );
+      await program.methods
+        .verifyGuess(
+          content,
+          null,
+          false,
+          wrongVerdict.timestamp,
+          wrongVerdict.nonce,
+          wrongVerdict.signature
+        )
         .accounts({
           authority: capsuleCreator.publicKey,
+          guess: guessPda,
           game: gamePda,
           capsule: capsulePda,
-          creator_leaderboard: creatorLeaderboardPda,
-          systemProgram: SystemProgram.programId,
+          leaderboard: playerLeaderboardPda,
+          usedNonce: getUsedNoncePda(program.programId, wrongVerdict.nonce),
         } as any)
+        .preInstructions([wrongVerdict.ed25519Ix])
+        .rpc();
+
+      const creatorBefore = await program.account.leaderboardEntry.fetch(
+        creatorLeaderboardPda
+      );
+
+      // Every guess has been judged, so the creator can end the game
+      await program.methods
+        .completeGame()
+        .accounts(completeAccounts as any)
         .rpc();
 
       const finalGame = await program.account.game.fetch(gamePda);
       expect(finalGame.isActive).to.be.false;
       expect(finalGame.currentGuesses).to.equal(1);
       expect(finalGame.winnersFound).to.equal(0); // No winners found
+      expect(finalGame.bonusPaid).to.be.true;
+
+      // Creator bonus: 50 points per participant
+      const creatorAfter = await program.account.leaderboardEntry.fetch(
+        creatorLeaderboardPda
+      );
+      expect(
+        creatorAfter.totalPoints.sub(creatorBefore.totalPoints).toNumber()
+      ).to.equal(50);
+
+      // Completing again is rejected and does not pay the bonus twice
+      try {
+        await program.methods
+          .completeGame()
+          .accounts(completeAccounts as any)
+          .rpc();
+        expect.fail("Game should not be completable twice");
+      } catch (error) {
+        expect(error.message).to.include("GameNotActive");
+      }
+
+      const creatorFinal = await program.account.leaderboardEntry.fetch(
+        creatorLeaderboardPda
+      );
+      expect(creatorFinal.totalPoints.toNumber()).to.equal(
+        creatorAfter.totalPoints.toNumber()
+      );
     });
 
     it("Complete Game: Max guesses reached", async () => {
@@ -1082,11 +1265,7 @@ describe("CapsuleX Game Instructions", () => {
 
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3);
-      const capsulePda = getCapsulePda(
-        capsuleCreator.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, capsuleCreator.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const accounts = {
         creator: capsuleCreator.publicKey,
@@ -1126,10 +1305,11 @@ describe("CapsuleX Game Instructions", () => {
         );
 
         await program.methods
-          .submitGuess(`guess ${i}`, false)
+          .submitGuess(commitGuess(`guess ${i}`).commitment, false)
           .accounts({
             guesser: capsuleCreator.publicKey,
             game: gamePda,
+            capsule: capsulePda,
             guess: guessPda,
             vault: getVaultPda(program.programId),
             systemProgram: SystemProgram.programId,
@@ -1140,7 +1320,7 @@ describe("CapsuleX Game Instructions", () => {
       // Wait and reveal
       await new Promise((resolve) => setTimeout(resolve, 4000));
       await program.methods
-        .revealCapsule(revealDate)
+        .revealCapsule()
         .accounts({
           creator: capsuleCreator.publicKey,
           capsule: capsulePda,
@@ -1184,4 +1364,261 @@ describe("CapsuleX Game Instructions", () => {
       expect(finalGame.isActive).to.be.false;
     });
   });
+
+  describe("Commit-Reveal Guessing", () => {
+    it("Reveal Guess: Rejects a wrong salt and a second opening", async () => {
+      const capsuleCreator = provider.wallet;
+
+      const slot = await provider.connection.getSlot();
+      const blockTime = await provider.connection.getBlockTime(slot);
+      const currentTime = blockTime || Math.floor(Date.now() / 1000);
+      const revealDate = new anchor.BN(currentTime + 3);
+      const capsulePda = await nextCapsulePda(program, capsuleCreator.publicKey);
+      const nftMintPda = getNftMintPda(capsulePda, program.programId);
+      const gamePda = getGamePda(capsulePda, program.programId);
+
+      const content = "commit reveal test";
+      await program.methods
+        .createCapsule(
+          content,
+          { text: {} },
+          createSHA256Hash(content),
+          revealDate,
+          true
+        )
+        .accounts({
+          creator: capsuleCreator.publicKey,
+          capsule: capsulePda,
+          nftMint: nftMintPda,
+          vault: getVaultPda(program.programId),
+          game: gamePda,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        } as any)
+        .rpc();
+
+      const [guessPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("guess"),
+          gamePda.toBuffer(),
+          capsuleCreator.publicKey.toBuffer(),
+          Buffer.from([0, 0, 0, 0]),
+        ],
+        program.programId
+      );
+
+      const committed = commitGuess(content);
+      await program.methods
+        .submitGuess(committed.commitment, false)
+        .accounts({
+          guesser: capsuleCreator.publicKey,
+          game: gamePda,
+          capsule: capsulePda,
+          guess: guessPda,
+          vault: getVaultPda(program.programId),
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+
+      await new Promise((resolve) => setTimeout(resolve, 5000));
+      await program.methods
+        .revealCapsule()
+        .accounts({
+          creator: capsuleCreator.publicKey,
+          capsule: capsulePda,
+        } as any)
+        .rpc();
+
+      const revealAccounts = {
+        guesser: capsuleCreator.publicKey,
+        guess: guessPda,
+        game: gamePda,
+        capsule: capsulePda,
+      };
+
+      // Same guess with a different salt does not match the commitment
+      try {
+        await revealGuess(
+          program,
+          { ...committed, salt: Array.from(crypto.randomBytes(32)) },
+          revealAccounts
+        );
+        expect.fail("Expected reveal with the wrong salt to fail");
+      } catch (error) {
+        expect(error.message).to.include("InvalidGuessCommitment");
+      }
+
+      await revealGuess(program, committed, revealAccounts);
+
+      const opened = await program.account.guess.fetch(guessPda);
+      expect(opened.isOpened).to.be.true;
+      expect(opened.guessContent).to.equal(content);
+
+      try {
+        await revealGuess(program, committed, revealAccounts);
+        expect.fail("Expected a second reveal to fail");
+      } catch (error) {
+        expect(error.message).to.include("GuessAlreadyOpened");
+      }
+
+      // Only guesses from before commit-reveal need to be migrated
+      try {
+        await program.methods
+          .migrateLegacyGuess()
+          .accounts({
+            payer: capsuleCreator.publicKey,
+            guess: guessPda,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .rpc();
+        expect.fail("Expected migrating a current guess to fail");
+      } catch (error) {
+        expect(error.message).to.include("GuessAlreadyMigrated");
+      }
+    });
+
+    it("Reveal Guess: Opens a guess at the maximum content length", async () => {
+      const capsuleCreator = provider.wallet;
+
+      const slot = await provider.connection.getSlot();
+      const blockTime = await provider.connection.getBlockTime(slot);
+      const currentTime = blockTime || Math.floor(Date.now() / 1000);
+      const revealDate = new anchor.BN(currentTime + 3);
+      const capsulePda = await nextCapsulePda(program, capsuleCreator.publicKey);
+      const nftMintPda = getNftMintPda(capsulePda, program.programId);
+      const gamePda = getGamePda(capsulePda, program.programId);
+
+      const content = "max length guess test";
+      await program.methods
+        .createCapsule(
+          content,
+          { text: {} },
+          createSHA256Hash(content),
+          revealDate,
+          true
+        )
+        .accounts({
+          creator: capsuleCreator.publicKey,
+          capsule: capsulePda,
+          nftMint: nftMintPda,
+          vault: getVaultPda(program.programId),
+          game: gamePda,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        } as any)
+        .rpc();
+
+      const [guessPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("guess"),
+          gamePda.toBuffer(),
+          capsuleCreator.publicKey.toBuffer(),
+          Buffer.from([0, 0, 0, 0]),
+        ],
+        program.programId
+      );
+
+      // MAX_GUESS_CONTENT_LENGTH bytes plus the 4-byte String prefix must fit in Guess::LEN
+      const committed = commitGuess("x".repeat(280));
+      await program.methods
+        .submitGuess(committed.commitment, false)
+        .accounts({
+          guesser: capsuleCreator.publicKey,
+          game: gamePda,
+          capsule: capsulePda,
+          guess: guessPda,
+          vault: getVaultPda(program.programId),
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+
+      await new Promise((resolve) => setTimeout(resolve, 5000));
+      await program.methods
+        .revealCapsule()
+        .accounts({
+          creator: capsuleCreator.publicKey,
+          capsule: capsulePda,
+        } as any)
+        .rpc();
+
+      await revealGuess(program, committed, {
+        guesser: capsuleCreator.publicKey,
+        guess: guessPda,
+        game: gamePda,
+        capsule: capsulePda,
+      });
+
+      const opened = await program.account.guess.fetch(guessPda);
+      expect(opened.isOpened).to.be.true;
+      expect(opened.guessContent).to.have.lengthOf(280);
+    });
+
+    it("Submit Guess: Rejects commitments after the reveal date", async () => {
+      const capsuleCreator = provider.wallet;
+
+      const slot = await provider.connection.getSlot();
+      const blockTime = await provider.connection.getBlockTime(slot);
+      const currentTime = blockTime || Math.floor(Date.now() / 1000);
+      const revealDate = new anchor.BN(currentTime + 3);
+      const capsulePda = await nextCapsulePda(program, capsuleCreator.publicKey);
+      const nftMintPda = getNftMintPda(capsulePda, program.programId);
+      const gamePda = getGamePda(capsulePda, program.programId);
+
+      const content = "late commitment test";
+      await program.methods
+        .createCapsule(
+          content,
+          { text: {} },
+          createSHA256Hash(content),
+          revealDate,
+          true
+        )
+        .accounts({
+          creator: capsuleCreator.publicKey,
+          capsule: capsulePda,
+          nftMint: nftMintPda,
+          vault: getVaultPda(program.programId),
+          game: gamePda,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        } as any)
+        .rpc();
+
+      // The game is still active, but the commit phase ended at the reveal date
+      await new Promise((resolve) => setTimeout(resolve, 5000));
+
+      const [guessPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("guess"),
+          gamePda.toBuffer(),
+          capsuleCreator.publicKey.toBuffer(),
+          Buffer.from([0, 0, 0, 0]),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .submitGuess(commitGuess(content).commitment, false)
+          .accounts({
+            guesser: capsuleCreator.publicKey,
+            game: gamePda,
+            capsule: capsulePda,
+            guess: guessPda,
+            vault: getVaultPda(program.programId),
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .rpc();
+        expect.fail("Expected a commitment after the reveal date to fail");
+      } catch (error) {
+        expect(error.message).to.include("GuessCommitPhaseClosed");
+      }
+
+      const game = await program.account.game.fetch(gamePda);
+      expect(game.currentGuesses).to.equal(0);
+    });
+  });
 });
@@ -0,0 +1,622 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { describe, before, after, it } from "mocha";
+import { expect } from "chai";
+import {
+  VAULT_SEED,
+  GUESS_SEED,
+  PROGRAM_CONFIG_SEED,
+  CREATOR_EARNINGS_SEED,
+  TREASURY_SEED,
+} from "./constants";
+import { airdrop, createCapsule } from "./capsule";
+import { commitGuess } from "./guess";
+
+const DEFAULT_REFUND_GRACE_PERIOD = 7 * 24 * 3600;
+
+describe("Guess Refunds", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PROGRAM_CONFIG_SEED)],
+    program.programId
+  );
+
+  const [treasuryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(TREASURY_SEED)],
+    program.programId
+  );
+
+  const creator = Keypair.generate();
+  const guesser = Keypair.generate();
+
+  const [creatorEarningsPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(CREATOR_EARNINGS_SEED), creator.publicKey.toBuffer()],
+    program.programId
+  );
+
+  async function updateConfig(update: Record<string, any>) {
+    await program.methods
+      .updateConfig({
+        appAuthority: null,
+        capsuleCreationFee: null,
+        serviceFee: null,
+        badgeMintFee: null,
+        trophyMintFee: null,
+        creatorShareBps: null,
+        refundGracePeriod: null,
+        soulboundNfts: null,
+        ...update,
+      })
+      .accounts({
+        authority: provider.wallet.publicKey,
+        vault: vaultPda,
+        config: configPda,
+      } as any)
+      .rpc();
+  }
+
+  async function setRefundGracePeriod(seconds: number) {
+    await updateConfig({ refundGracePeriod: new anchor.BN(seconds) });
+  }
+
+  // Creates a gamified capsule and submits one guess against it
+  async function createGuessedCapsule(
+    revealIn: number,
+    beforeGuess: () => Promise<void> = async () => {}
+  ) {
+    const { capsulePda, gamePda, bondPda, revealDate } = await createCapsule(
+      program,
+      { creator, answer: "refund secret", revealIn }
+    );
+    const [guessPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(GUESS_SEED),
+        gamePda.toBuffer(),
+        guesser.publicKey.toBuffer(),
+        Buffer.from([0, 0, 0, 0]),
+      ],
+      program.programId
+    );
+
+    await beforeGuess();
+    await program.methods
+      .submitGuess(commitGuess("refund guess").commitment, false)
+      .accounts({
+        guesser: guesser.publicKey,
+        game: gamePda,
+        capsule: capsulePda,
+        guess: guessPda,
+        vault: vaultPda,
+        config: configPda,
+        creatorEarnings: creatorEarningsPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([guesser])
+      .rpc();
+
+    return { capsulePda, gamePda, bondPda, guessPda, revealDate };
+  }
+
+  function refundAccounts(capsule: {
+    capsulePda: PublicKey;
+    gamePda: PublicKey;
+    bondPda: PublicKey;
+    guessPda: PublicKey;
+  }) {
+    return {
+      capsule: capsule.capsulePda,
+      game: capsule.gamePda,
+      guess: capsule.guessPda,
+      guesser: guesser.publicKey,
+      bond: capsule.bondPda,
+      vault: vaultPda,
+      config: configPda,
+      creatorEarnings: creatorEarningsPda,
+    };
+  }
+
+  async function refundLiability() {
+    const config = await program.account.programConfig.fetch(configPda);
+    return config.pendingRefundLiability.toNumber();
+  }
+
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await airdrop(provider.connection, creator.publicKey, 2_000_000_000);
+    await airdrop(provider.connection, guesser.publicKey, 1_000_000_000);
+
+    // Shorten the grace period so abandonment can be reached locally
+    await setRefundGracePeriod(1);
+  });
+
+  after(async () => {
+    await setRefundGracePeriod(DEFAULT_REFUND_GRACE_PERIOD);
+  });
+
+  it("Rejects refunds before the reveal grace period has passed", async () => {
+    const capsule = await createGuessedCapsule(3600);
+
+    try {
+      await program.methods
+        .claimGuessRefund()
+        .accounts(refundAccounts(capsule) as any)
+        .rpc();
+      expect.fail("Expected a refund for a pending capsule to fail");
+    } catch (error) {
+      expect(error.message).to.include("BondGracePeriodActive");
+    }
+  });
+
+  it("Refunds the fee from the bond and closes the guess", async () => {
+    const capsule = await createGuessedCapsule(2);
+    const guess = await program.account.guess.fetch(capsule.guessPda);
+    const bondBefore = await program.account.bondAccount.fetch(
+      capsule.bondPda
+    );
+    const guessRent = await provider.connection.getBalance(capsule.guessPda);
+
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+
+    const balanceBefore = await provider.connection.getBalance(
+      guesser.publicKey
+    );
+    // Any wallet can push the refund; the provider pays the transaction fee
+    await program.methods
+      .claimGuessRefund()
+      .accounts(refundAccounts(capsule) as any)
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(
+      guesser.publicKey
+    );
+
+    const feePaid = guess.feePaid.toNumber();
+    expect(feePaid).to.be.greaterThan(0);
+    expect(balanceAfter - balanceBefore).to.equal(feePaid + guessRent);
+    expect(
+      await provider.connection.getAccountInfo(capsule.guessPda)
+    ).to.be.null;
+
+    const bondAfter = await program.account.bondAccount.fetch(capsule.bondPda);
+    expect(bondAfter.refundsPaid).to.equal(1);
+    expect(
+      bondBefore.currentBalance.sub(bondAfter.currentBalance).toNumber()
+    ).to.equal(feePaid);
+
+    // The guess is gone, so the refund cannot be claimed twice
+    try {
+      await program.methods
+        .claimGuessRefund()
+        .accounts(refundAccounts(capsule) as any)
+        .rpc();
+      expect.fail("Expected a second refund to fail");
+    } catch (error) {
+      expect(error.message).to.include("AccountNotInitialized");
+    }
+  });
+
+  it("Bond refunds repay the fee the guess paid and close the guess", async () => {
+    const config = await program.account.programConfig.fetch(configPda);
+    const serviceFee = config.serviceFee;
+
+    // The bond is sized with half the fee the guess later pays
+    await updateConfig({ serviceFee: serviceFee.divn(2) });
+    const capsule = await createGuessedCapsule(3600, async () => {
+      await updateConfig({ serviceFee });
+    });
+    const guess = await program.account.guess.fetch(capsule.guessPda);
+    const bond = await program.account.bondAccount.fetch(capsule.bondPda);
+    expect(guess.feePaid.toNumber()).to.be.greaterThan(
+      bond.refundPerGuess.toNumber()
+    );
+
+    await program.methods
+      .cancelCapsule()
+      .accounts({
+        creator: creator.publicKey,
+        capsule: capsule.capsulePda,
+        game: capsule.gamePda,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    const guessRent = await provider.connection.getBalance(capsule.guessPda);
+    const balanceBefore = await provider.connection.getBalance(
+      guesser.publicKey
+    );
+    await program.methods
+      .processBondRefund()
+      .accounts(refundAccounts(capsule) as any)
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(
+      guesser.publicKey
+    );
+
+    expect(balanceAfter - balanceBefore).to.equal(
+      guess.feePaid.toNumber() + guessRent
+    );
+    expect(
+      await provider.connection.getAccountInfo(capsule.guessPda)
+    ).to.be.null;
+
+    // Both refund paths see the same closed guess, so it cannot be paid twice
+    try {
+      await program.methods
+        .claimGuessRefund()
+        .accounts(refundAccounts(capsule) as any)
+        .rpc();
+      expect.fail("Expected a second refund to fail");
+    } catch (error) {
+      expect(error.message).to.include("AccountNotInitialized");
+    }
+  });
+
+  it("Keeps a bond-refunded guess's creator share locked until the bond is released", async () => {
+    const capsule = await createGuessedCapsule(3600);
+    const guess = await program.account.guess.fetch(capsule.guessPda);
+    const creatorShare = guess.creatorSharePaid.toNumber();
+    expect(creatorShare).to.be.greaterThan(0);
+
+    await program.methods
+      .cancelCapsule()
+      .accounts({
+        creator: creator.publicKey,
+        capsule: capsule.capsulePda,
+        game: capsule.gamePda,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    const lockedBefore = (
+      await program.account.creatorEarnings.fetch(creatorEarningsPda)
+    ).lockedBalance;
+    await program.methods
+      .processBondRefund()
+      .accounts(refundAccounts(capsule) as any)
+      .rpc();
+
+    // The bond paid the guesser, so CreatorEarnings gave nothing back and keeps the share locked
+    const afterRefund = await program.account.creatorEarnings.fetch(
+      creatorEarningsPda
+    );
+    expect(afterRefund.lockedBalance.toString()).to.equal(
+      lockedBefore.toString()
+    );
+
+    await program.methods
+      .releaseBond()
+      .accounts({
+        creator: creator.publicKey,
+        capsule: capsule.capsulePda,
+        game: capsule.gamePda,
+        bond: capsule.bondPda,
+        config: configPda,
+        creatorEarnings: creatorEarningsPda,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    const afterRelease = await program.account.creatorEarnings.fetch(
+      creatorEarningsPda
+    );
+    expect(
+      lockedBefore.sub(afterRelease.lockedBalance).toNumber()
+    ).to.equal(creatorShare);
+    const game = await program.account.game.fetch(capsule.gamePda);
+    expect(game.creatorFeesHeld.toNumber()).to.equal(0);
+
+    // The unlocked share is now part of what the creator can claim
+    const claimable = afterRelease.availableBalance
+      .sub(afterRelease.lockedBalance)
+      .toNumber();
+    expect(claimable).to.be.at.least(creatorShare);
+    const earningsBalanceBefore = await provider.connection.getBalance(
+      creatorEarningsPda
+    );
+    await program.methods
+      .claimCreatorEarnings()
+      .accounts({
+        creator: creator.publicKey,
+        creatorEarnings: creatorEarningsPda,
+      } as any)
+      .signers([creator])
+      .rpc();
+    const earningsBalanceAfter = await provider.connection.getBalance(
+      creatorEarningsPda
+    );
+    expect(earningsBalanceBefore - earningsBalanceAfter).to.equal(claimable);
+  });
+
+  it("Pays back only what the vault received once the bond is empty", async () => {
+    const config = await program.account.programConfig.fetch(configPda);
+    const serviceFee = config.serviceFee;
+
+    // A capsule created while the fee was zero posts an empty bond
+    await updateConfig({ serviceFee: new anchor.BN(0) });
+    let vaultBefore = 0;
+    let liabilityBefore = 0;
+    let earningsBefore = new anchor.BN(0);
+    const capsule = await createGuessedCapsule(3600, async () => {
+      await updateConfig({ serviceFee });
+      vaultBefore = await provider.connection.getBalance(vaultPda);
+      liabilityBefore = await refundLiability();
+      earningsBefore = (
+        await program.account.creatorEarnings.fetch(creatorEarningsPda)
+      ).availableBalance;
+    });
+
+    const guess = await program.account.guess.fetch(capsule.guessPda);
+    const feePaid = guess.feePaid.toNumber();
+    const creatorShare = guess.creatorSharePaid.toNumber();
+    expect(creatorShare).to.be.greaterThan(0);
+    const vaultReceived =
+      (await provider.connection.getBalance(vaultPda)) - vaultBefore;
+    expect(vaultReceived).to.equal(feePaid - creatorShare);
+    expect(await refundLiability()).to.equal(liabilityBefore + vaultReceived);
+
+    await program.methods
+      .cancelCapsule()
+      .accounts({
+        creator: creator.publicKey,
+        capsule: capsule.capsulePda,
+        game: capsule.gamePda,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    const guessRent = await provider.connection.getBalance(capsule.guessPda);
+    const balanceBefore = await provider.connection.getBalance(
+      guesser.publicKey
+    );
+    await program.methods
+      .claimGuessRefund()
+      .accounts(refundAccounts(capsule) as any)
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(
+      guesser.publicKey
+    );
+
+    // The guesser is made whole, the creator's share is taken back from
+    // CreatorEarnings, and the vault pays out exactly what it took in
+    expect(balanceAfter - balanceBefore).to.equal(feePaid + guessRent);
+    expect(await provider.connection.getBalance(vaultPda)).to.equal(
+      vaultBefore
+    );
+    const earnings = await program.account.creatorEarnings.fetch(
+      creatorEarningsPda
+    );
+    expect(earnings.availableBalance.toString()).to.equal(
+      earningsBefore.toString()
+    );
+    expect(await refundLiability()).to.equal(liabilityBefore);
+  });
+
+  it("Refunds the full fee even after the creator claims their earnings", async () => {
+    const config = await program.account.programConfig.fetch(configPda);
+    const serviceFee = config.serviceFee;
+
+    // An empty bond leaves the refund to CreatorEarnings and the vault
+    await updateConfig({ serviceFee: new anchor.BN(0) });
+    const capsule = await createGuessedCapsule(3600, async () => {
+      await updateConfig({ serviceFee });
+    });
+    const guess = await program.account.guess.fetch(capsule.guessPda);
+    const creatorShare = guess.creatorSharePaid.toNumber();
+
+    // Earnings from earlier revealed capsules may be claimable; this guess's share is not
+    try {
+      await program.methods
+        .claimCreatorEarnings()
+        .accounts({
+          creator: creator.publicKey,
+          creatorEarnings: creatorEarningsPda,
+        } as any)
+        .signers([creator])
+        .rpc();
+    } catch (error) {
+      expect(error.message).to.include("NoCreatorEarnings");
+    }
+    const earnings = await program.account.creatorEarnings.fetch(
+      creatorEarningsPda
+    );
+    expect(earnings.availableBalance.toNumber()).to.be.at.least(creatorShare);
+    expect(earnings.lockedBalance.toNumber()).to.be.at.least(creatorShare);
+
+    await program.methods
+      .cancelCapsule()
+      .accounts({
+        creator: creator.publicKey,
+        capsule: capsule.capsulePda,
+        game: capsule.gamePda,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    const guessRent = await provider.connection.getBalance(capsule.guessPda);
+    const balanceBefore = await provider.connection.getBalance(
+      guesser.publicKey
+    );
+    await program.methods
+      .claimGuessRefund()
+      .accounts(refundAccounts(capsule) as any)
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(
+      guesser.publicKey
+    );
+    expect(balanceAfter - balanceBefore).to.equal(
+      guess.feePaid.toNumber() + guessRent
+    );
+  });
+
+  it("Keeps refundable guess fees out of fee withdrawals until reveal", async () => {
+    let liabilityBefore = 0;
+    const capsule = await createGuessedCapsule(3, async () => {
+      liabilityBefore = await refundLiability();
+    });
+
+    const guess = await program.account.guess.fetch(capsule.guessPda);
+    const vaultShare =
+      guess.feePaid.toNumber() - guess.creatorSharePaid.toNumber();
+    expect(vaultShare).to.be.greaterThan(0);
+    const liability = await refundLiability();
+    expect(liability).to.equal(liabilityBefore + vaultShare);
+    let game = await program.account.game.fetch(capsule.gamePda);
+    expect(game.vaultFeesHeld.toNumber()).to.equal(vaultShare);
+
+    // The authority can take everything above rent except what guessers may still reclaim
+    const vaultInfo = await provider.connection.getAccountInfo(vaultPda);
+    const rentExemptMinimum =
+      await provider.connection.getMinimumBalanceForRentExemption(
+        vaultInfo.data.length
+      );
+    const withdrawable = vaultInfo.lamports - rentExemptMinimum - liability;
+    try {
+      await program.methods
+        .withdrawFees(new anchor.BN(withdrawable + 1))
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          treasury: treasuryPda,
+          destination: provider.wallet.publicKey,
+          config: configPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+      expect.fail("Expected a withdrawal of reserved fees to fail");
+    } catch (error) {
+      expect(error.message).to.include("InsufficientVaultBalance");
+    }
+
+    // Revealing ends the refund window, so the fee becomes withdrawable
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+    await program.methods
+      .revealCapsule()
+      .accounts({
+        revealer: creator.publicKey,
+        capsule: capsule.capsulePda,
+        game: capsule.gamePda,
+        config: configPda,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    expect(await refundLiability()).to.equal(liabilityBefore);
+    game = await program.account.game.fetch(capsule.gamePda);
+    expect(game.vaultFeesHeld.toNumber()).to.equal(0);
+  });
+
+  it("Rejects a reveal after the grace period until every guess is refunded", async () => {
+    let liabilityBefore = 0;
+    const capsule = await createGuessedCapsule(2, async () => {
+      liabilityBefore = await refundLiability();
+    });
+    const revealAccounts = {
+      revealer: creator.publicKey,
+      capsule: capsule.capsulePda,
+      game: capsule.gamePda,
+      config: configPda,
+    };
+    const releaseAccounts = {
+      creator: creator.publicKey,
+      capsule: capsule.capsulePda,
+      game: capsule.gamePda,
+      bond: capsule.bondPda,
+      config: configPda,
+    };
+
+    // The guess became refundable before the creator got around to revealing
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+    try {
+      await program.methods
+        .revealCapsule()
+        .accounts(revealAccounts as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("Expected a late reveal with a refund owed to fail");
+    } catch (error) {
+      expect(error.message).to.include("GuessRefundsOutstanding");
+    }
+    try {
+      await program.methods
+        .releaseBond()
+        .accounts(releaseAccounts as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("Expected the bond to stay locked while a refund is owed");
+    } catch (error) {
+      expect(error.message).to.include("BondNotReleasable");
+    }
+    // The vault share stays reserved for the guesser
+    expect(await refundLiability()).to.be.greaterThan(liabilityBefore);
+
+    await program.methods
+      .claimGuessRefund()
+      .accounts(refundAccounts(capsule) as any)
+      .rpc();
+
+    // With nobody left to refund, the creator may still reveal and recover the rest of the bond
+    await program.methods
+      .revealCapsule()
+      .accounts(revealAccounts as any)
+      .signers([creator])
+      .rpc();
+    const revealed = await program.account.capsule.fetch(capsule.capsulePda);
+    expect(revealed.revealedAt.toNumber()).to.be.greaterThan(
+      capsule.revealDate.toNumber() + 1
+    );
+
+    await program.methods
+      .releaseBond()
+      .accounts(releaseAccounts as any)
+      .signers([creator])
+      .rpc();
+    expect(await provider.connection.getAccountInfo(capsule.bondPda)).to.be
+      .null;
+    expect(await refundLiability()).to.equal(liabilityBefore);
+  });
+
+  it("Rejects refunds once the capsule is revealed", async () => {
+    const capsule = await createGuessedCapsule(2);
+
+    await new Promise((resolve) => setTimeout(resolve, 4000));
+    await program.methods
+      .revealCapsule()
+      .accounts({
+        revealer: creator.publicKey,
+        capsule: capsule.capsulePda,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    try {
+      await program.methods
+        .claimGuessRefund()
+        .accounts(refundAccounts(capsule) as any)
+        .rpc();
+      expect.fail("Expected a refund for a revealed capsule to fail");
+    } catch (error) {
+      expect(error.message).to.include("CapsuleAlreadyRevealed");
+    }
+  });
+});
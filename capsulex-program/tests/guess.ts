@@ -0,0 +1,45 @@
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import crypto from "crypto";
+
+export interface GuessCommitment {
+  guess: string;
+  salt: number[];
+  commitment: number[];
+}
+
+// Commitment format must match reveal_guess: sha256(guess || salt)
+export function commitGuess(
+  guess: string,
+  salt: Buffer = crypto.randomBytes(32)
+): GuessCommitment {
+  const commitment = crypto
+    .createHash("sha256")
+    .update(Buffer.concat([Buffer.from(guess, "utf8"), salt]))
+    .digest();
+
+  return {
+    guess,
+    salt: Array.from(salt),
+    commitment: Array.from(commitment),
+  };
+}
+
+// Opens a committed guess; only valid after the capsule's reveal date
+export async function revealGuess(
+  program: Program<Capsulex>,
+  committed: GuessCommitment,
+  accounts: {
+    guesser: PublicKey;
+    guess: PublicKey;
+    game: PublicKey;
+    capsule: PublicKey;
+  },
+  signer?: Keypair
+) {
+  const builder = program.methods
+    .revealGuess(committed.guess, committed.salt)
+    .accounts(accounts as any);
+  return signer ? builder.signers([signer]).rpc() : builder.rpc();
+}
@@ -0,0 +1,214 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { describe, before, it } from "mocha";
+import { expect } from "chai";
+import {
+  VAULT_SEED,
+  LEADERBOARD_SEED,
+  POINTS_ISSUER_REGISTRY_SEED,
+} from "./constants";
+import { airdrop } from "./capsule";
+
+describe("Leaderboard Access Control", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+  const [pointsIssuersPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(POINTS_ISSUER_REGISTRY_SEED)],
+    program.programId
+  );
+
+  const attacker = Keypair.generate();
+  const victim = Keypair.generate();
+  const issuer = Keypair.generate();
+
+  function getLeaderboardPda(user: PublicKey) {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(LEADERBOARD_SEED), user.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  function grantPoints(
+    signer: Keypair | null,
+    user: PublicKey,
+    points: number
+  ) {
+    const builder = program.methods
+      .updateLeaderboard(user, new anchor.BN(points))
+      .accounts({
+        authority: signer ? signer.publicKey : provider.wallet.publicKey,
+        user,
+        vault: vaultPda,
+        pointsIssuers: pointsIssuersPda,
+        leaderboard: getLeaderboardPda(user),
+        systemProgram: SystemProgram.programId,
+      } as any);
+    return signer ? builder.signers([signer]).rpc() : builder.rpc();
+  }
+
+  function issuerAccounts(authority: PublicKey) {
+    return {
+      authority,
+      vault: vaultPda,
+      pointsIssuers: pointsIssuersPda,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await airdrop(provider.connection, attacker.publicKey, 1_000_000_000);
+    await airdrop(provider.connection, victim.publicKey, 1_000_000_000);
+    await airdrop(provider.connection, issuer.publicKey, 1_000_000_000);
+  });
+
+  it("Rejects an arbitrary wallet granting itself points", async () => {
+    try {
+      await grantPoints(attacker, attacker.publicKey, 1_000_000);
+      expect.fail("Expected UnauthorizedPointsIssuer");
+    } catch (error) {
+      expect(error.message).to.include("UnauthorizedPointsIssuer");
+    }
+
+    const entry = await provider.connection.getAccountInfo(
+      getLeaderboardPda(attacker.publicKey)
+    );
+    expect(entry).to.be.null;
+  });
+
+  it("Rejects an arbitrary wallet granting points to others", async () => {
+    try {
+      await grantPoints(attacker, victim.publicKey, 500);
+      expect.fail("Expected UnauthorizedPointsIssuer");
+    } catch (error) {
+      expect(error.message).to.include("UnauthorizedPointsIssuer");
+    }
+  });
+
+  it("Rejects creating another user's leaderboard entry", async () => {
+    try {
+      await program.methods
+        .initializeLeaderboard(victim.publicKey)
+        .accounts({
+          authority: attacker.publicKey,
+          user: victim.publicKey,
+          leaderboard: getLeaderboardPda(victim.publicKey),
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([attacker])
+        .rpc();
+      expect.fail("Expected UnauthorizedPointsIssuer");
+    } catch (error) {
+      expect(error.message).to.include("UnauthorizedPointsIssuer");
+    }
+  });
+
+  it("Lets users create their own leaderboard entry", async () => {
+    await program.methods
+      .initializeLeaderboard(victim.publicKey)
+      .accounts({
+        authority: victim.publicKey,
+        user: victim.publicKey,
+        leaderboard: getLeaderboardPda(victim.publicKey),
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([victim])
+      .rpc();
+
+    const entry = await program.account.leaderboardEntry.fetch(
+      getLeaderboardPda(victim.publicKey)
+    );
+    expect(entry.totalPoints.toNumber()).to.equal(0);
+  });
+
+  it("Lets the vault authority grant points", async () => {
+    await grantPoints(null, victim.publicKey, 250);
+
+    const entry = await program.account.leaderboardEntry.fetch(
+      getLeaderboardPda(victim.publicKey)
+    );
+    expect(entry.totalPoints.toNumber()).to.equal(250);
+  });
+
+  it("Rejects points issuer registration from a non-authority", async () => {
+    try {
+      await program.methods
+        .setPointsIssuer(attacker.publicKey, new anchor.BN(1_000_000))
+        .accounts(issuerAccounts(attacker.publicKey) as any)
+        .signers([attacker])
+        .rpc();
+      expect.fail("Expected InvalidProgramAuthority");
+    } catch (error) {
+      expect(error.message).to.include("InvalidProgramAuthority");
+    }
+  });
+
+  it("Caps the points a registered issuer can grant", async () => {
+    await program.methods
+      .setPointsIssuer(issuer.publicKey, new anchor.BN(150))
+      .accounts(issuerAccounts(provider.wallet.publicKey) as any)
+      .rpc();
+
+    await grantPoints(issuer, victim.publicKey, 100);
+
+    try {
+      await grantPoints(issuer, victim.publicKey, 100);
+      expect.fail("Expected PointsCapExceeded");
+    } catch (error) {
+      expect(error.message).to.include("PointsCapExceeded");
+    }
+
+    const registry = await program.account.pointsIssuerRegistry.fetch(
+      pointsIssuersPda
+    );
+    const entry = registry.issuers.find((registered) =>
+      registered.issuer.equals(issuer.publicKey)
+    );
+    expect(entry.pointsIssued.toNumber()).to.equal(100);
+
+    const leaderboard = await program.account.leaderboardEntry.fetch(
+      getLeaderboardPda(victim.publicKey)
+    );
+    expect(leaderboard.totalPoints.toNumber()).to.equal(350);
+  });
+
+  it("Stops a removed issuer from granting points", async () => {
+    await program.methods
+      .removePointsIssuer(issuer.publicKey)
+      .accounts({
+        authority: provider.wallet.publicKey,
+        vault: vaultPda,
+        pointsIssuers: pointsIssuersPda,
+      } as any)
+      .rpc();
+
+    try {
+      await grantPoints(issuer, victim.publicKey, 10);
+      expect.fail("Expected UnauthorizedPointsIssuer");
+    } catch (error) {
+      expect(error.message).to.include("UnauthorizedPointsIssuer");
+    }
+  });
+});
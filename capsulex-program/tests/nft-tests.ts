@@ -2,15 +2,29 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Capsulex } from "../target/types/capsulex";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { getMint } from "@solana/spl-token";
 import { describe, before, it } from "mocha";
 import {
   VAULT_SEED,
-  CAPSULE_SEED,
   CAPSULE_MINT_SEED,
   BADGE_MINT_SEED,
-  TROPHY_MINT_SEED,
   LEADERBOARD_SEED,
+  COLLECTION_MINT_SEED,
+  COLLECTION_AUTHORITY_SEED,
 } from "./constants";
+import { nextCapsulePda } from "./capsule";
+import {
+  ensureNftCollections,
+  getMasterEditionPda,
+  getMetadataPda,
+  metadataUpdateAuthority,
+  TOKEN_METADATA_PROGRAM_ID,
+} from "./collection";
+import {
+  ensureTrophyDefinitions,
+  getTrophyDefinitionPda,
+  getTrophyMintPda,
+} from "./trophy";
 import { expect } from "chai";
 import crypto from "crypto";
 
@@ -19,23 +33,6 @@ function createSHA256Hash(content: string): string {
   return crypto.createHash("sha256").update(content, "utf8").digest("hex");
 }
 
-// Helper functions for PDA generation (matching existing test style)
-function getCapsulePda(
-  creator: PublicKey,
-  revealDate: anchor.BN,
-  programId: PublicKey
-) {
-  const [pda] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from(CAPSULE_SEED),
-      creator.toBuffer(),
-      Buffer.from(revealDate.toArray("le", 8)),
-    ],
-    programId
-  );
-  return pda;
-}
-
 function getNftMintPda(capsule: PublicKey, programId: PublicKey) {
   const [pda] = PublicKey.findProgramAddressSync(
     [Buffer.from(CAPSULE_MINT_SEED), capsule.toBuffer()],
@@ -115,6 +112,9 @@ describe("CapsuleX NFT Instructions", () => {
         // Continue with tests even if vault init fails (might already exist)
       }
     }
+
+    await ensureNftCollections(program, provider);
+    await ensureTrophyDefinitions(program, provider);
   });
 
   it("NFT: Mint capsule NFT for time capsule creator", async () => {
@@ -123,11 +123,7 @@ describe("CapsuleX NFT Instructions", () => {
 
     const currentTime = Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 100); // Unique timestamp
-    const capsulePda = getCapsulePda(
-      capsuleCreator.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, capsuleCreator.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
 
     // Use same accounts structure as game-tests.ts (NO KeyVault)
@@ -174,6 +170,7 @@ describe("CapsuleX NFT Instructions", () => {
         nftMint: nftMintPda,
         creatorTokenAccount: creatorTokenAccount,
         vault: getVaultPda(program.programId),
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
         tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
         associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
@@ -188,6 +185,53 @@ describe("CapsuleX NFT Instructions", () => {
     expect(tokenAccount.value.amount).to.equal("1");
     expect(tokenAccount.value.decimals).to.equal(0);
 
+    // Wallets see a real NFT: metadata updated by the capsule PDA and a
+    // master edition that now holds the mint authority
+    const metadata = await provider.connection.getAccountInfo(
+      getMetadataPda(nftMintPda)
+    );
+    expect(metadata.owner.toBase58()).to.equal(
+      TOKEN_METADATA_PROGRAM_ID.toBase58()
+    );
+    expect(metadataUpdateAuthority(metadata.data).toBase58()).to.equal(
+      capsulePda.toBase58()
+    );
+    const masterEditionPda = getMasterEditionPda(nftMintPda);
+    const mint = await getMint(provider.connection, nftMintPda);
+    expect(mint.mintAuthority.toBase58()).to.equal(
+      masterEditionPda.toBase58()
+    );
+
+    // One-of-one: the capsule records the mint and rejects a second call
+    const capsule = await program.account.capsule.fetch(capsulePda);
+    expect(capsule.nftMinted).to.be.true;
+    try {
+      await program.methods
+        .mintCapsuleNft(
+          "Time Capsule #1",
+          "CAPS",
+          "https://metadata.capsulex.com/capsule/1"
+        )
+        .accounts({
+          creator: capsuleCreator.publicKey,
+          capsule: capsulePda,
+          nftMint: nftMintPda,
+          creatorTokenAccount: creatorTokenAccount,
+          vault: getVaultPda(program.programId),
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        } as any)
+        .rpc();
+      expect.fail("Second capsule NFT mint should have failed");
+    } catch (error) {
+      expect(error.message).to.include("CapsuleNftAlreadyMinted");
+    }
+    const supply = await getMint(provider.connection, nftMintPda);
+    expect(supply.supply.toString()).to.equal("1");
+
     // console.log("✅ Capsule NFT successfully minted to creator");
   });
 
@@ -201,11 +245,7 @@ describe("CapsuleX NFT Instructions", () => {
 
     const currentTime = Math.floor(Date.now() / 1000);
     const revealDate = new anchor.BN(currentTime + 300); // Unique timestamp
-    const capsulePda = getCapsulePda(
-      capsuleCreator.publicKey,
-      revealDate,
-      program.programId
-    );
+    const capsulePda = await nextCapsulePda(program, capsuleCreator.publicKey);
     const nftMintPda = getNftMintPda(capsulePda, program.programId);
     const gamePda = getGamePda(capsulePda, program.programId);
     const accounts = getDefaultAccounts({
@@ -241,7 +281,7 @@ describe("CapsuleX NFT Instructions", () => {
 
     try {
       await program.methods
-        .mintWinnerBadge("fake_winner", "https://fake.uri")
+        .mintWinnerBadge("https://fake.uri")
         .accounts({
           authority: nonWinner.publicKey,
           winner: nonWinner.publicKey,
@@ -249,6 +289,7 @@ describe("CapsuleX NFT Instructions", () => {
           badgeMint: badgeMintPda,
           winnerTokenAccount: nonWinnerTokenAccount,
           vault: getVaultPda(program.programId),
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
           tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
           associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -294,13 +335,10 @@ describe("CapsuleX NFT Instructions", () => {
     // console.log("🏅 Testing trophy eligibility requirements:");
 
     for (const trophyType of trophyTypes) {
-      const [trophyMintPda] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from(TROPHY_MINT_SEED),
-          testUser.publicKey.toBuffer(),
-          Buffer.from(trophyType),
-        ],
-        program.programId
+      const trophyMintPda = getTrophyMintPda(
+        program,
+        testUser.publicKey,
+        trophyType
       );
 
       const userTokenAccount = await anchor.utils.token.associatedAddress({
@@ -310,17 +348,16 @@ describe("CapsuleX NFT Instructions", () => {
 
       try {
         await program.methods
-          .mintTrophyNft(
-            trophyType,
-            `https://metadata.capsulex.com/trophy/${trophyType}/1`
-          )
+          .mintTrophyNft(trophyType)
           .accounts({
             authority: testUser.publicKey,
             user: testUser.publicKey,
             leaderboard: leaderboardPda,
+            trophyDefinition: getTrophyDefinitionPda(program, trophyType),
             trophyMint: trophyMintPda,
             userTokenAccount: userTokenAccount,
             vault: getVaultPda(program.programId),
+            tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
             tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
             associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -365,13 +402,10 @@ describe("CapsuleX NFT Instructions", () => {
       .signers([testUser])
       .rpc();
 
-    const [invalidTrophyMintPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from(TROPHY_MINT_SEED),
-        testUser.publicKey.toBuffer(),
-        Buffer.from("invalid_type"),
-      ],
-      program.programId
+    const invalidTrophyMintPda = getTrophyMintPda(
+      program,
+      testUser.publicKey,
+      "invalid_type"
     );
 
     const userTokenAccount = await anchor.utils.token.associatedAddress({
@@ -381,14 +415,16 @@ describe("CapsuleX NFT Instructions", () => {
 
     try {
       await program.methods
-        .mintTrophyNft("invalid_type", "https://fake.uri")
+        .mintTrophyNft("invalid_type")
         .accounts({
           authority: testUser.publicKey,
           user: testUser.publicKey,
           leaderboard: leaderboardPda,
+          trophyDefinition: getTrophyDefinitionPda(program, "invalid_type"),
           trophyMint: invalidTrophyMintPda,
           userTokenAccount: userTokenAccount,
           vault: getVaultPda(program.programId),
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
           tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
           associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -399,8 +435,34 @@ describe("CapsuleX NFT Instructions", () => {
 
       expect.fail("Should have failed for invalid trophy type");
     } catch (error) {
-      expect(error.message).to.include("NotEligibleForReward");
+      // Trophy types without a TrophyDefinition cannot be minted
+      expect(error.message).to.include("AccountNotInitialized");
       // console.log("✅ Correctly rejected invalid trophy type");
     }
   });
+
+  it("NFT: Each NFT kind has a collection owned by the program", async () => {
+    const [collectionAuthorityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(COLLECTION_AUTHORITY_SEED)],
+      program.programId
+    );
+
+    for (const kind of ["capsule", "badge", "trophy"]) {
+      const [collectionMintPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from(COLLECTION_MINT_SEED), Buffer.from(kind)],
+        program.programId
+      );
+      const metadata = await provider.connection.getAccountInfo(
+        getMetadataPda(collectionMintPda)
+      );
+      expect(metadataUpdateAuthority(metadata.data).toBase58()).to.equal(
+        collectionAuthorityPda.toBase58()
+      );
+      expect(
+        await provider.connection.getAccountInfo(
+          getMasterEditionPda(collectionMintPda)
+        )
+      ).to.not.be.null;
+    }
+  });
 });
@@ -0,0 +1,854 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { describe, before, it } from "mocha";
+import { expect } from "chai";
+import { VAULT_SEED, GUESS_SEED, LEADERBOARD_SEED } from "./constants";
+import { createCapsule } from "./capsule";
+import {
+  ed25519InstructionFor,
+  ensureOracleRegistered,
+  generateOracleKey,
+  getOracleConfigPda,
+  getUsedNoncePda,
+  loadDevOracleKey,
+  OracleKey,
+  oracleMessage,
+  signOracleVerdict,
+} from "./oracle";
+import { commitGuess, GuessCommitment, revealGuess } from "./guess";
+
+describe("Oracle Signature Verification", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+  const devOracle = loadDevOracleKey();
+  const secretAnswer = "oracle secret";
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await ensureOracleRegistered(program, provider, devOracle);
+  });
+
+  // Creates a revealed gamified capsule with guesses from a fresh player
+  async function setupRevealedGuess(guessContent: string, guessCount = 1) {
+    const player = Keypair.generate();
+    await provider.connection.requestAirdrop(player.publicKey, 1_000_000_000);
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    const { capsulePda, gamePda, revealDate } = await createCapsule(program, {
+      answer: secretAnswer,
+      revealIn: 3,
+    });
+    const guessPdas = Array.from({ length: guessCount }, (_, index) => {
+      const [pda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GUESS_SEED),
+          gamePda.toBuffer(),
+          player.publicKey.toBuffer(),
+          Buffer.from([index, 0, 0, 0]),
+        ],
+        program.programId
+      );
+      return pda;
+    });
+    const guessPda = guessPdas[0];
+    const [leaderboardPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(LEADERBOARD_SEED), player.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const commitments: GuessCommitment[] = [];
+    for (const pda of guessPdas) {
+      const committed = commitGuess(guessContent);
+      commitments.push(committed);
+      await program.methods
+        .submitGuess(committed.commitment, false)
+        .accounts({
+          guesser: player.publicKey,
+          game: gamePda,
+          capsule: capsulePda,
+          guess: pda,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([player])
+        .rpc();
+    }
+
+    await program.methods
+      .initializeLeaderboard(player.publicKey)
+      .accounts({
+        authority: player.publicKey,
+        user: player.publicKey,
+        leaderboard: leaderboardPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([player])
+      .rpc();
+
+    // Wait for reveal date before revealing
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+    await program.methods
+      .revealCapsule()
+      .accounts({
+        revealer: provider.wallet.publicKey,
+        capsule: capsulePda,
+      } as any)
+      .rpc();
+
+    // Open every committed guess so it can be judged
+    for (const [index, pda] of guessPdas.entries()) {
+      await revealGuess(
+        program,
+        commitments[index],
+        {
+          guesser: player.publicKey,
+          guess: pda,
+          game: gamePda,
+          capsule: capsulePda,
+        },
+        player
+      );
+    }
+
+    return { player, capsulePda, gamePda, guessPda, guessPdas, leaderboardPda };
+  }
+
+  function verifyAccounts(
+    setup: {
+      player: Keypair;
+      capsulePda: PublicKey;
+      gamePda: PublicKey;
+      guessPda: PublicKey;
+      leaderboardPda: PublicKey;
+    },
+    nonce: string
+  ) {
+    return {
+      // Capsule creator (the provider wallet) verifies guesses for its games
+      authority: provider.wallet.publicKey,
+      guess: setup.guessPda,
+      game: setup.gamePda,
+      capsule: setup.capsulePda,
+      leaderboard: setup.leaderboardPda,
+      oracleConfig: getOracleConfigPda(program.programId),
+      usedNonce: getUsedNoncePda(program.programId, nonce),
+      instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+    };
+  }
+
+  function registryAccounts() {
+    return {
+      authority: provider.wallet.publicKey,
+      vault: vaultPda,
+      oracleConfig: getOracleConfigPda(program.programId),
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  // Runs verify_guess for a fresh correct guess signed by the given oracle key
+  async function verifyWithOracle(key: OracleKey) {
+    const setup = await setupRevealedGuess(secretAnswer);
+    const verdict = signOracleVerdict(key, secretAnswer, secretAnswer, true);
+
+    await program.methods
+      .verifyGuess(
+        secretAnswer,
+        null,
+        true,
+        verdict.timestamp,
+        verdict.nonce,
+        verdict.signature
+      )
+      .accounts(verifyAccounts(setup, verdict.nonce) as any)
+      .preInstructions([verdict.ed25519Ix])
+      .rpc();
+
+    return setup;
+  }
+
+  it("Rejects a verdict without a preceding Ed25519 instruction", async () => {
+    const setup = await setupRevealedGuess(secretAnswer);
+    const verdict = signOracleVerdict(
+      devOracle,
+      secretAnswer,
+      secretAnswer,
+      true
+    );
+
+    try {
+      await program.methods
+        .verifyGuess(
+          secretAnswer,
+          null,
+          true,
+          verdict.timestamp,
+          verdict.nonce,
+          verdict.signature
+        )
+        .accounts(verifyAccounts(setup, verdict.nonce) as any)
+        .rpc();
+      expect.fail("Expected verification without Ed25519 instruction to fail");
+    } catch (error) {
+      expect(error.message).to.include("MissingOracleVerification");
+    }
+
+    const guess = await program.account.guess.fetch(setup.guessPda);
+    expect(guess.isCorrect).to.be.false;
+  });
+
+  it("Rejects a verdict signed by an unknown oracle key", async () => {
+    const setup = await setupRevealedGuess(secretAnswer);
+    const throwawayOracle = generateOracleKey();
+    const verdict = signOracleVerdict(
+      throwawayOracle,
+      secretAnswer,
+      secretAnswer,
+      true
+    );
+
+    try {
+      await program.methods
+        .verifyGuess(
+          secretAnswer,
+          null,
+          true,
+          verdict.timestamp,
+          verdict.nonce,
+          verdict.signature
+        )
+        .accounts(verifyAccounts(setup, verdict.nonce) as any)
+        .preInstructions([verdict.ed25519Ix])
+        .rpc();
+      expect.fail("Expected verification with unknown oracle key to fail");
+    } catch (error) {
+      expect(error.message).to.include("InactiveOracleKey");
+    }
+  });
+
+  it("Rejects a verdict whose result differs from the signed message", async () => {
+    const setup = await setupRevealedGuess("wrong guess");
+    // Oracle signed "false"; caller claims "true"
+    const verdict = signOracleVerdict(
+      devOracle,
+      "wrong guess",
+      secretAnswer,
+      false
+    );
+
+    try {
+      await program.methods
+        .verifyGuess(
+          secretAnswer,
+          null,
+          true,
+          verdict.timestamp,
+          verdict.nonce,
+          verdict.signature
+        )
+        .accounts(verifyAccounts(setup, verdict.nonce) as any)
+        .preInstructions([verdict.ed25519Ix])
+        .rpc();
+      expect.fail("Expected tampered verdict to fail");
+    } catch (error) {
+      expect(error.message).to.include("InvalidOracleSignature");
+    }
+  });
+
+  it("Rejects decrypted content that does not match the integrity hash", async () => {
+    const setup = await setupRevealedGuess("made up answer");
+    // A genuine oracle verdict, but judged against content the capsule never held
+    const verdict = signOracleVerdict(
+      devOracle,
+      "made up answer",
+      "made up answer",
+      true
+    );
+
+    try {
+      await program.methods
+        .verifyGuess(
+          "made up answer",
+          null,
+          true,
+          verdict.timestamp,
+          verdict.nonce,
+          verdict.signature
+        )
+        .accounts(verifyAccounts(setup, verdict.nonce) as any)
+        .preInstructions([verdict.ed25519Ix])
+        .rpc();
+      expect.fail("Expected mismatched decrypted content to fail");
+    } catch (error) {
+      expect(error.message).to.include("ContentIntegrityMismatch");
+    }
+
+    const guess = await program.account.guess.fetch(setup.guessPda);
+    expect(guess.isVerified).to.be.false;
+  });
+
+  it("Rejects an Ed25519 instruction carrying a forged signature", async () => {
+    const setup = await setupRevealedGuess(secretAnswer);
+    const verdict = signOracleVerdict(
+      generateOracleKey(),
+      secretAnswer,
+      secretAnswer,
+      true
+    );
+    // Claim the dev oracle key signed it; the Ed25519 program must reject the transaction
+    const forgedIx = ed25519InstructionFor(
+      devOracle.publicKey,
+      oracleMessage(
+        secretAnswer,
+        secretAnswer,
+        true,
+        verdict.timestamp.toNumber(),
+        verdict.nonce
+      ),
+      verdict.signature
+    );
+
+    try {
+      await program.methods
+        .verifyGuess(
+          secretAnswer,
+          null,
+          true,
+          verdict.timestamp,
+          verdict.nonce,
+          verdict.signature
+        )
+        .accounts(verifyAccounts(setup, verdict.nonce) as any)
+        .preInstructions([forgedIx])
+        .rpc();
+      expect.fail("Expected forged signature to fail");
+    } catch (error) {
+      expect(error).to.exist;
+    }
+
+    const guess = await program.account.guess.fetch(setup.guessPda);
+    expect(guess.isCorrect).to.be.false;
+  });
+
+  it("Accepts a verdict signed by the oracle", async () => {
+    const setup = await setupRevealedGuess(secretAnswer);
+    const verdict = signOracleVerdict(
+      devOracle,
+      secretAnswer,
+      secretAnswer,
+      true
+    );
+
+    await program.methods
+      .verifyGuess(
+        secretAnswer,
+        null,
+        true,
+        verdict.timestamp,
+        verdict.nonce,
+        verdict.signature
+      )
+      .accounts(verifyAccounts(setup, verdict.nonce) as any)
+      .preInstructions([verdict.ed25519Ix])
+      .rpc();
+
+    const guess = await program.account.guess.fetch(setup.guessPda);
+    expect(guess.isCorrect).to.be.true;
+
+    const game = await program.account.game.fetch(setup.gamePda);
+    expect(game.winner.toBase58()).to.equal(setup.player.publicKey.toBase58());
+  });
+
+  describe("Nonce Replay Protection", () => {
+    it("Rejects replaying a consumed oracle verdict", async () => {
+      // Two games with identical guess and answer produce the same signed message
+      const first = await setupRevealedGuess(secretAnswer);
+      const second = await setupRevealedGuess(secretAnswer);
+      const verdict = signOracleVerdict(
+        devOracle,
+        secretAnswer,
+        secretAnswer,
+        true
+      );
+
+      await program.methods
+        .verifyGuess(
+          secretAnswer,
+          null,
+          true,
+          verdict.timestamp,
+          verdict.nonce,
+          verdict.signature
+        )
+        .accounts(verifyAccounts(first, verdict.nonce) as any)
+        .preInstructions([verdict.ed25519Ix])
+        .rpc();
+
+      try {
+        await program.methods
+          .verifyGuess(
+            secretAnswer,
+            null,
+            true,
+            verdict.timestamp,
+            verdict.nonce,
+            verdict.signature
+          )
+          .accounts(verifyAccounts(second, verdict.nonce) as any)
+          .preInstructions([verdict.ed25519Ix])
+          .rpc();
+        expect.fail("Expected replayed oracle verdict to fail");
+      } catch (error) {
+        expect(error.message).to.include("OracleNonceReused");
+      }
+
+      const replayedGuess = await program.account.guess.fetch(second.guessPda);
+      expect(replayedGuess.isCorrect).to.be.false;
+    });
+
+    it("Accepts a verdict whose nonce account was pre-funded", async () => {
+      const setup = await setupRevealedGuess(secretAnswer);
+      const verdict = signOracleVerdict(
+        devOracle,
+        secretAnswer,
+        secretAnswer,
+        true
+      );
+      const usedNoncePda = getUsedNoncePda(program.programId, verdict.nonce);
+
+      // Anyone can send lamports to the PDA before the verdict lands
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            toPubkey: usedNoncePda,
+            lamports: 1_000_000,
+          })
+        )
+      );
+
+      await program.methods
+        .verifyGuess(
+          secretAnswer,
+          null,
+          true,
+          verdict.timestamp,
+          verdict.nonce,
+          verdict.signature
+        )
+        .accounts(verifyAccounts(setup, verdict.nonce) as any)
+        .preInstructions([verdict.ed25519Ix])
+        .rpc();
+
+      const verifiedGuess = await program.account.guess.fetch(setup.guessPda);
+      expect(verifiedGuess.isCorrect).to.be.true;
+      const usedNonce = await program.account.usedNonce.fetch(usedNoncePda);
+      expect(usedNonce.expiresAt.toNumber()).to.equal(
+        verdict.timestamp.toNumber() + 900
+      );
+    });
+
+    it("Keeps the nonce record until the signed verdict expires", async () => {
+      const setup = await setupRevealedGuess(secretAnswer);
+      const verdict = signOracleVerdict(
+        devOracle,
+        secretAnswer,
+        secretAnswer,
+        true
+      );
+      const usedNoncePda = getUsedNoncePda(program.programId, verdict.nonce);
+
+      await program.methods
+        .verifyGuess(
+          secretAnswer,
+          null,
+          true,
+          verdict.timestamp,
+          verdict.nonce,
+          verdict.signature
+        )
+        .accounts(verifyAccounts(setup, verdict.nonce) as any)
+        .preInstructions([verdict.ed25519Ix])
+        .rpc();
+
+      const usedNonce = await program.account.usedNonce.fetch(usedNoncePda);
+      expect(usedNonce.payer.toBase58()).to.equal(
+        provider.wallet.publicKey.toBase58()
+      );
+      expect(usedNonce.oracle.toBase58()).to.equal(
+        new PublicKey(devOracle.publicKey).toBase58()
+      );
+      expect(usedNonce.expiresAt.toNumber()).to.equal(
+        verdict.timestamp.toNumber() + 900
+      );
+
+      try {
+        await program.methods
+          .closeUsedNonce()
+          .accounts({
+            closer: provider.wallet.publicKey,
+            payer: provider.wallet.publicKey,
+            usedNonce: usedNoncePda,
+          } as any)
+          .rpc();
+        expect.fail("Expected closing an unexpired nonce record to fail");
+      } catch (error) {
+        expect(error.message).to.include("OracleNonceNotExpired");
+      }
+    });
+  });
+
+  describe("Double Verification", () => {
+    it("Rejects verifying the same guess twice", async () => {
+      const setup = await verifyWithOracle(devOracle);
+
+      const guess = await program.account.guess.fetch(setup.guessPda);
+      expect(guess.isVerified).to.be.true;
+      expect(guess.verifiedAt.toNumber()).to.be.greaterThan(0);
+      const pointsBefore = (
+        await program.account.leaderboardEntry.fetch(setup.leaderboardPda)
+      ).totalPoints.toNumber();
+
+      // A fresh, validly signed verdict cannot re-verify the guess
+      const verdict = signOracleVerdict(
+        devOracle,
+        secretAnswer,
+        secretAnswer,
+        true
+      );
+      try {
+        await program.methods
+          .verifyGuess(
+            secretAnswer,
+            null,
+            true,
+            verdict.timestamp,
+            verdict.nonce,
+            verdict.signature
+          )
+          .accounts(verifyAccounts(setup, verdict.nonce) as any)
+          .preInstructions([verdict.ed25519Ix])
+          .rpc();
+        expect.fail("Expected re-verification to fail");
+      } catch (error) {
+        expect(error.message).to.include("GuessAlreadyVerified");
+      }
+
+      const leaderboard = await program.account.leaderboardEntry.fetch(
+        setup.leaderboardPda
+      );
+      expect(leaderboard.totalPoints.toNumber()).to.equal(pointsBefore);
+    });
+
+    it("Lists a player in winners at most once", async () => {
+      const setup = await setupRevealedGuess(secretAnswer, 2);
+
+      for (const guessPda of setup.guessPdas) {
+        const verdict = signOracleVerdict(
+          devOracle,
+          secretAnswer,
+          secretAnswer,
+          true
+        );
+        await program.methods
+          .verifyGuess(
+            secretAnswer,
+            null,
+            true,
+            verdict.timestamp,
+            verdict.nonce,
+            verdict.signature
+          )
+          .accounts({
+            ...verifyAccounts(setup, verdict.nonce),
+            guess: guessPda,
+          } as any)
+          .preInstructions([verdict.ed25519Ix])
+          .rpc();
+      }
+
+      const game = await program.account.game.fetch(setup.gamePda);
+      expect(game.winnersFound).to.equal(1);
+      expect(
+        game.winners.filter((winner) => winner.equals(setup.player.publicKey))
+      ).to.have.length(1);
+
+      // Second correct guess is verified but does not win again
+      const secondGuess = await program.account.guess.fetch(
+        setup.guessPdas[1]
+      );
+      expect(secondGuess.isVerified).to.be.true;
+      expect(secondGuess.isCorrect).to.be.false;
+
+      const leaderboard = await program.account.leaderboardEntry.fetch(
+        setup.leaderboardPda
+      );
+      expect(leaderboard.gamesWon).to.equal(1);
+    });
+  });
+
+  describe("Verifier Authorization", () => {
+    const relayer = Keypair.generate();
+
+    function relayerAccounts(authority: PublicKey) {
+      return {
+        authority,
+        vault: vaultPda,
+        oracleConfig: getOracleConfigPda(program.programId),
+      };
+    }
+
+    // Signs a fresh correct verdict and submits it with the given verifier
+    async function verifyAs(
+      setup: Awaited<ReturnType<typeof setupRevealedGuess>>,
+      verifier: Keypair
+    ) {
+      const verdict = signOracleVerdict(
+        devOracle,
+        secretAnswer,
+        secretAnswer,
+        true
+      );
+      await program.methods
+        .verifyGuess(
+          secretAnswer,
+          null,
+          true,
+          verdict.timestamp,
+          verdict.nonce,
+          verdict.signature
+        )
+        .accounts({
+          ...verifyAccounts(setup, verdict.nonce),
+          authority: verifier.publicKey,
+        } as any)
+        .preInstructions([verdict.ed25519Ix])
+        .signers([verifier])
+        .rpc();
+    }
+
+    before(async () => {
+      const signature = await provider.connection.requestAirdrop(
+        relayer.publicKey,
+        1_000_000_000
+      );
+      await provider.connection.confirmTransaction(signature);
+    });
+
+    it("Rejects a guesser verifying their own guess", async () => {
+      const setup = await setupRevealedGuess(secretAnswer);
+
+      try {
+        await verifyAs(setup, setup.player);
+        expect.fail("Expected UnauthorizedVerifier");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedVerifier");
+      }
+
+      const guess = await program.account.guess.fetch(setup.guessPda);
+      expect(guess.isVerified).to.be.false;
+      expect(guess.isCorrect).to.be.false;
+    });
+
+    it("Rejects relayer registration from a non-authority wallet", async () => {
+      try {
+        await program.methods
+          .addOracleRelayer(relayer.publicKey)
+          .accounts(relayerAccounts(relayer.publicKey) as any)
+          .signers([relayer])
+          .rpc();
+        expect.fail("Expected InvalidProgramAuthority");
+      } catch (error) {
+        expect(error.message).to.include("InvalidProgramAuthority");
+      }
+    });
+
+    it("Accepts verdicts from a registered relayer until removed", async () => {
+      await program.methods
+        .addOracleRelayer(relayer.publicKey)
+        .accounts(relayerAccounts(provider.wallet.publicKey) as any)
+        .rpc();
+
+      const accepted = await setupRevealedGuess(secretAnswer);
+      await verifyAs(accepted, relayer);
+      const guess = await program.account.guess.fetch(accepted.guessPda);
+      expect(guess.isCorrect).to.be.true;
+
+      await program.methods
+        .removeOracleRelayer(relayer.publicKey)
+        .accounts(relayerAccounts(provider.wallet.publicKey) as any)
+        .rpc();
+
+      const rejected = await setupRevealedGuess(secretAnswer);
+      try {
+        await verifyAs(rejected, relayer);
+        expect.fail("Expected UnauthorizedVerifier after removal");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedVerifier");
+      }
+    });
+  });
+
+  describe("Oracle Registry", () => {
+    it("Rejects oracle registration from a non-authority wallet", async () => {
+      const attacker = Keypair.generate();
+      await provider.connection.requestAirdrop(
+        attacker.publicKey,
+        1_000_000_000
+      );
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+
+      try {
+        await program.methods
+          .addOracle(new PublicKey(generateOracleKey().publicKey), null, null)
+          .accounts({
+            ...registryAccounts(),
+            authority: attacker.publicKey,
+          } as any)
+          .signers([attacker])
+          .rpc();
+        expect.fail("Expected non-authority registration to fail");
+      } catch (error) {
+        expect(error.message).to.include("InvalidProgramAuthority");
+      }
+    });
+
+    it("Rejects registering the same oracle key twice", async () => {
+      try {
+        await program.methods
+          .addOracle(new PublicKey(devOracle.publicKey), null, null)
+          .accounts(registryAccounts() as any)
+          .rpc();
+        expect.fail("Expected duplicate registration to fail");
+      } catch (error) {
+        expect(error.message).to.include("OracleAlreadyRegistered");
+      }
+    });
+
+    it("Accepts a newly registered key and rejects it after revocation", async () => {
+      const newOracle = generateOracleKey();
+      const newOracleKey = new PublicKey(newOracle.publicKey);
+
+      await program.methods
+        .addOracle(newOracleKey, null, null)
+        .accounts(registryAccounts() as any)
+        .rpc();
+
+      const config = await program.account.oracleConfig.fetch(
+        getOracleConfigPda(program.programId)
+      );
+      expect(
+        config.oracles.some((oracle) => oracle.publicKey.equals(newOracleKey))
+      ).to.be.true;
+
+      const setup = await verifyWithOracle(newOracle);
+      const guess = await program.account.guess.fetch(setup.guessPda);
+      expect(guess.isCorrect).to.be.true;
+
+      await program.methods
+        .revokeOracle(newOracleKey)
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          oracleConfig: getOracleConfigPda(program.programId),
+        } as any)
+        .rpc();
+
+      try {
+        await verifyWithOracle(newOracle);
+        expect.fail("Expected revoked oracle key to be rejected");
+      } catch (error) {
+        expect(error.message).to.include("InactiveOracleKey");
+      }
+    });
+
+    it("Rejects a key before its activation time", async () => {
+      const futureOracle = generateOracleKey();
+      const futureOracleKey = new PublicKey(futureOracle.publicKey);
+      const activatesAt = Math.floor(Date.now() / 1000) + 3600;
+
+      await program.methods
+        .addOracle(futureOracleKey, new anchor.BN(activatesAt), null)
+        .accounts(registryAccounts() as any)
+        .rpc();
+
+      try {
+        await verifyWithOracle(futureOracle);
+        expect.fail("Expected not-yet-active oracle key to be rejected");
+      } catch (error) {
+        expect(error.message).to.include("InactiveOracleKey");
+      } finally {
+        await program.methods
+          .revokeOracle(futureOracleKey)
+          .accounts({
+            authority: provider.wallet.publicKey,
+            vault: vaultPda,
+            oracleConfig: getOracleConfigPda(program.programId),
+          } as any)
+          .rpc();
+      }
+    });
+
+    it("Rotates an oracle key without a redeploy", async () => {
+      const oldOracle = generateOracleKey();
+      const newOracle = generateOracleKey();
+      const oldOracleKey = new PublicKey(oldOracle.publicKey);
+      const newOracleKey = new PublicKey(newOracle.publicKey);
+
+      await program.methods
+        .addOracle(oldOracleKey, null, null)
+        .accounts(registryAccounts() as any)
+        .rpc();
+
+      // No grace period: the old key stops being accepted immediately
+      await program.methods
+        .rotateOracle(oldOracleKey, newOracleKey, new anchor.BN(0))
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          oracleConfig: getOracleConfigPda(program.programId),
+        } as any)
+        .rpc();
+
+      try {
+        await verifyWithOracle(oldOracle);
+        expect.fail("Expected rotated-out oracle key to be rejected");
+      } catch (error) {
+        expect(error.message).to.include("InactiveOracleKey");
+      }
+
+      const setup = await verifyWithOracle(newOracle);
+      const guess = await program.account.guess.fetch(setup.guessPda);
+      expect(guess.isCorrect).to.be.true;
+
+      await program.methods
+        .revokeOracle(newOracleKey)
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          oracleConfig: getOracleConfigPda(program.programId),
+        } as any)
+        .rpc();
+    });
+  });
+});
@@ -0,0 +1,146 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import {
+  Ed25519Program,
+  PublicKey,
+  SystemProgram,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import crypto from "crypto";
+import fs from "fs";
+import path from "path";
+import { ORACLE_CONFIG_SEED, USED_NONCE_SEED, VAULT_SEED } from "./constants";
+
+// Development oracle key committed with the semantic service; the test suites register it on the
+// local validator only, and it must never be registered on a real cluster
+const DEV_ORACLE_KEY_PATH = path.join(
+  __dirname,
+  "../../semantic-service/oracle_private_key.pem"
+);
+
+export interface OracleKey {
+  privateKey: crypto.KeyObject;
+  publicKey: Buffer; // raw 32-byte ed25519 public key
+}
+
+export interface OracleVerdict {
+  timestamp: anchor.BN;
+  nonce: string;
+  signature: string; // base64, as returned by the semantic service
+  ed25519Ix: TransactionInstruction;
+}
+
+function toOracleKey(privateKey: crypto.KeyObject): OracleKey {
+  const jwk = crypto.createPublicKey(privateKey).export({ format: "jwk" });
+  return {
+    privateKey,
+    publicKey: Buffer.from(jwk.x as string, "base64url"),
+  };
+}
+
+export function loadDevOracleKey(): OracleKey {
+  return toOracleKey(
+    crypto.createPrivateKey(fs.readFileSync(DEV_ORACLE_KEY_PATH))
+  );
+}
+
+// Throwaway oracle keypair for negative tests
+export function generateOracleKey(): OracleKey {
+  return toOracleKey(crypto.generateKeyPairSync("ed25519").privateKey);
+}
+
+// Message format must match verify_guess: guess:decrypted:result:timestamp:nonce
+export function oracleMessage(
+  guess: string,
+  answer: string,
+  isCorrect: boolean,
+  timestamp: number,
+  nonce: string
+): Buffer {
+  return Buffer.from(
+    `${guess}:${answer}:${isCorrect}:${timestamp}:${nonce}`,
+    "utf8"
+  );
+}
+
+// Build an Ed25519 verification instruction for an existing (base64) signature
+export function ed25519InstructionFor(
+  publicKey: Buffer,
+  message: Buffer,
+  signatureBase64: string
+): TransactionInstruction {
+  return Ed25519Program.createInstructionWithPublicKey({
+    publicKey,
+    message,
+    signature: Buffer.from(signatureBase64, "base64"),
+  });
+}
+
+// Sign a semantic verdict the same way the semantic service does
+export function signOracleVerdict(
+  key: OracleKey,
+  guess: string,
+  answer: string,
+  isCorrect: boolean,
+  timestamp: number = Math.floor(Date.now() / 1000),
+  nonce: string = crypto.randomBytes(16).toString("base64url")
+): OracleVerdict {
+  const message = oracleMessage(guess, answer, isCorrect, timestamp, nonce);
+  const signature = crypto
+    .sign(null, message, key.privateKey)
+    .toString("base64");
+
+  return {
+    timestamp: new anchor.BN(timestamp),
+    nonce,
+    signature,
+    ed25519Ix: ed25519InstructionFor(key.publicKey, message, signature),
+  };
+}
+
+export function getOracleConfigPda(programId: PublicKey) {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(ORACLE_CONFIG_SEED)],
+    programId
+  );
+  return pda;
+}
+
+// Replay ledger PDA for an oracle nonce (seeded by the SHA-256 of the nonce)
+export function getUsedNoncePda(programId: PublicKey, nonce: string) {
+  const nonceHash = crypto.createHash("sha256").update(nonce, "utf8").digest();
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(USED_NONCE_SEED), nonceHash],
+    programId
+  );
+  return pda;
+}
+
+// Register an oracle key in the on-chain registry (no-op if it is already registered)
+export async function ensureOracleRegistered(
+  program: Program<Capsulex>,
+  provider: anchor.AnchorProvider,
+  key: OracleKey
+) {
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+
+  try {
+    await program.methods
+      .addOracle(new PublicKey(key.publicKey), null, null)
+      .accounts({
+        authority: provider.wallet.publicKey,
+        vault: vaultPda,
+        oracleConfig: getOracleConfigPda(program.programId),
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+  } catch (error) {
+    if (!error.message.includes("OracleAlreadyRegistered")) {
+      throw error;
+    }
+  }
+}
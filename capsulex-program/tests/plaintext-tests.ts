@@ -0,0 +1,141 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { describe, before, it } from "mocha";
+import { expect } from "chai";
+import { VAULT_SEED, PUBLISHED_PLAINTEXT_SEED } from "./constants";
+import { airdrop, createCapsule, createSHA256Hash } from "./capsule";
+
+describe("Plaintext Publication", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+  const secret = "the plaintext behind the capsule";
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+
+  const creator = Keypair.generate();
+  const publisher = Keypair.generate();
+
+  async function newCapsule(revealIn: number) {
+    const { capsulePda, revealDate } = await createCapsule(program, {
+      creator,
+      answer: secret,
+      revealIn,
+      isGamified: false,
+    });
+    const [plaintextPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(PUBLISHED_PLAINTEXT_SEED), capsulePda.toBuffer()],
+      program.programId
+    );
+
+    return { capsulePda, plaintextPda, revealDate };
+  }
+
+  async function createRevealedCapsule() {
+    const capsule = await newCapsule(2);
+
+    await new Promise((resolve) => setTimeout(resolve, 4000));
+    await program.methods
+      .revealCapsule()
+      .accounts({
+        revealer: creator.publicKey,
+        capsule: capsule.capsulePda,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    return capsule;
+  }
+
+  function publish(
+    capsule: { capsulePda: PublicKey; plaintextPda: PublicKey },
+    plaintext: string
+  ) {
+    return program.methods
+      .publishPlaintext(plaintext)
+      .accounts({
+        publisher: publisher.publicKey,
+        capsule: capsule.capsulePda,
+        publishedPlaintext: capsule.plaintextPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([publisher])
+      .rpc();
+  }
+
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await airdrop(provider.connection, creator.publicKey, 2_000_000_000);
+    await airdrop(provider.connection, publisher.publicKey, 1_000_000_000);
+  });
+
+  it("Records plaintext that matches the integrity hash", async () => {
+    const capsule = await createRevealedCapsule();
+
+    // Anyone can publish; the hash check is what makes the record trustworthy
+    await publish(capsule, secret);
+
+    const record = await program.account.publishedPlaintext.fetch(
+      capsule.plaintextPda
+    );
+    expect(record.capsule.toBase58()).to.equal(capsule.capsulePda.toBase58());
+    expect(record.publisher.toBase58()).to.equal(
+      publisher.publicKey.toBase58()
+    );
+    expect(record.plaintext).to.equal(secret);
+    expect(Buffer.from(record.contentHash).toString("hex")).to.equal(
+      createSHA256Hash(secret)
+    );
+
+    // The record is written once per capsule
+    try {
+      await publish(capsule, secret);
+      expect.fail("Expected a second publication to fail");
+    } catch (error) {
+      expect(error.message).to.include("already in use");
+    }
+  });
+
+  it("Rejects plaintext that does not match the integrity hash", async () => {
+    const capsule = await createRevealedCapsule();
+
+    try {
+      await publish(capsule, "something the creator never wrote");
+      expect.fail("Expected mismatched plaintext to be rejected");
+    } catch (error) {
+      expect(error.message).to.include("ContentIntegrityMismatch");
+    }
+    expect(
+      await provider.connection.getAccountInfo(capsule.plaintextPda)
+    ).to.be.null;
+  });
+
+  it("Rejects publication before the capsule is revealed", async () => {
+    const capsule = await newCapsule(3600);
+
+    try {
+      await publish(capsule, secret);
+      expect.fail("Expected publishing a sealed capsule to fail");
+    } catch (error) {
+      expect(error.message).to.include("CapsuleNotReady");
+    }
+  });
+});
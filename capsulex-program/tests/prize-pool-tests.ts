@@ -0,0 +1,879 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import {
+  createMint,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { describe, before, it } from "mocha";
+import { expect } from "chai";
+import {
+  VAULT_SEED,
+  GUESS_SEED,
+  LEADERBOARD_SEED,
+  PRIZE_POOL_SEED,
+  PRIZE_ESCROW_SEED,
+} from "./constants";
+import { airdrop, createCapsule } from "./capsule";
+import {
+  ensureOracleRegistered,
+  getUsedNoncePda,
+  loadDevOracleKey,
+  signOracleVerdict,
+} from "./oracle";
+import { commitGuess, revealGuess } from "./guess";
+
+describe("Prize Pools", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+  const devOracle = loadDevOracleKey();
+  const secretAnswer = "prize secret";
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+  const [creatorLeaderboardPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(LEADERBOARD_SEED), provider.wallet.publicKey.toBuffer()],
+    program.programId
+  );
+
+  const sponsor = Keypair.generate();
+
+  function getPrizePoolPda(gamePda: PublicKey) {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(PRIZE_POOL_SEED), gamePda.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  function getPrizeEscrowPda(prizePoolPda: PublicKey) {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(PRIZE_ESCROW_SEED), prizePoolPda.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  // Creates a gamified capsule (revealing in a few seconds) owned by the provider wallet
+  async function createGame() {
+    const capsule = await createCapsule(program, {
+      answer: secretAnswer,
+      revealIn: 3,
+    });
+
+    return { ...capsule, prizePoolPda: getPrizePoolPda(capsule.gamePda) };
+  }
+
+  // Player submits the correct answer, the capsule is revealed, the guess is
+  // verified as a winner and, unless told otherwise, the creator completes the game
+  async function winGame(
+    setup: { capsulePda: PublicKey; gamePda: PublicKey },
+    player: Keypair,
+    complete = true
+  ) {
+    const { guessPda, leaderboardPda } = await openGuess(setup, player);
+    await verifyWinner(setup, guessPda, leaderboardPda);
+
+    if (complete) {
+      await completeGame(setup);
+    }
+  }
+
+  // Player commits the correct answer, the capsule is revealed and the guess is opened
+  async function openGuess(
+    setup: { capsulePda: PublicKey; gamePda: PublicKey },
+    player: Keypair
+  ) {
+    const [guessPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(GUESS_SEED),
+        setup.gamePda.toBuffer(),
+        player.publicKey.toBuffer(),
+        Buffer.from([0, 0, 0, 0]),
+      ],
+      program.programId
+    );
+    const [leaderboardPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(LEADERBOARD_SEED), player.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const committed = commitGuess(secretAnswer);
+    await program.methods
+      .submitGuess(committed.commitment, false)
+      .accounts({
+        guesser: player.publicKey,
+        game: setup.gamePda,
+        capsule: setup.capsulePda,
+        guess: guessPda,
+        vault: vaultPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([player])
+      .rpc();
+
+    await program.methods
+      .initializeLeaderboard(player.publicKey)
+      .accounts({
+        authority: player.publicKey,
+        user: player.publicKey,
+        leaderboard: leaderboardPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([player])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+    await program.methods
+      .revealCapsule()
+      .accounts({
+        revealer: provider.wallet.publicKey,
+        capsule: setup.capsulePda,
+      } as any)
+      .rpc();
+
+    await revealGuess(
+      program,
+      committed,
+      {
+        guesser: player.publicKey,
+        guess: guessPda,
+        game: setup.gamePda,
+        capsule: setup.capsulePda,
+      },
+      player
+    );
+
+    return { guessPda, leaderboardPda };
+  }
+
+  async function verifyWinner(
+    setup: { capsulePda: PublicKey; gamePda: PublicKey },
+    guessPda: PublicKey,
+    leaderboardPda: PublicKey
+  ) {
+    const verdict = signOracleVerdict(
+      devOracle,
+      secretAnswer,
+      secretAnswer,
+      true
+    );
+    await program.methods
+      .verifyGuess(
+        secretAnswer,
+        null,
+        true,
+        verdict.timestamp,
+        verdict.nonce,
+        verdict.signature
+      )
+      .accounts({
+        authority: provider.wallet.publicKey,
+        guess: guessPda,
+        game: setup.gamePda,
+        capsule: setup.capsulePda,
+        leaderboard: leaderboardPda,
+        usedNonce: getUsedNoncePda(program.programId, verdict.nonce),
+      } as any)
+      .preInstructions([verdict.ed25519Ix])
+      .rpc();
+  }
+
+  async function completeGame(setup: {
+    capsulePda: PublicKey;
+    gamePda: PublicKey;
+  }) {
+    const game = await program.account.game.fetch(setup.gamePda);
+    if (!game.isActive) {
+      return; // Verification already ended the game
+    }
+
+    await program.methods
+      .completeGame()
+      .accounts({
+        authority: provider.wallet.publicKey,
+        game: setup.gamePda,
+        capsule: setup.capsulePda,
+        creatorLeaderboard: creatorLeaderboardPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+  }
+
+  async function newPlayer() {
+    const player = Keypair.generate();
+    await airdrop(provider.connection, player.publicKey, 1_000_000_000);
+    return player;
+  }
+
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await ensureOracleRegistered(program, provider, devOracle);
+    await airdrop(provider.connection, sponsor.publicKey, 2_000_000_000);
+
+    try {
+      await program.methods
+        .initializeLeaderboard(provider.wallet.publicKey)
+        .accounts({
+          authority: provider.wallet.publicKey,
+          user: provider.wallet.publicKey,
+          leaderboard: creatorLeaderboardPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Creator leaderboard already exists
+    }
+  });
+
+  it("Rejects a split that does not total 100%", async () => {
+    const setup = await createGame();
+
+    try {
+      await program.methods
+        .createPrizePool([6000, 3000])
+        .accounts({
+          creator: provider.wallet.publicKey,
+          game: setup.gamePda,
+          prizePool: setup.prizePoolPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+      expect.fail("Expected an incomplete split to be rejected");
+    } catch (error) {
+      expect(error.message).to.include("InvalidPrizeSplit");
+    }
+  });
+
+  it("Only the game creator can open a prize pool", async () => {
+    const setup = await createGame();
+
+    try {
+      await program.methods
+        .createPrizePool([10000])
+        .accounts({
+          creator: sponsor.publicKey,
+          game: setup.gamePda,
+          prizePool: setup.prizePoolPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([sponsor])
+        .rpc();
+      expect.fail("Expected a non-creator to be rejected");
+    } catch (error) {
+      expect(error.message).to.include("UnauthorizedCreator");
+    }
+  });
+
+  it("Pays the whole SOL pot to the only winner", async () => {
+    const setup = await createGame();
+    const winner = await newPlayer();
+
+    await program.methods
+      .createPrizePool([7000, 3000])
+      .accounts({
+        creator: provider.wallet.publicKey,
+        game: setup.gamePda,
+        prizePool: setup.prizePoolPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    const fundAccounts = {
+      game: setup.gamePda,
+      prizePool: setup.prizePoolPda,
+      systemProgram: SystemProgram.programId,
+    };
+    await program.methods
+      .fundPrizePool(new anchor.BN(200_000_000))
+      .accounts({ ...fundAccounts, funder: provider.wallet.publicKey } as any)
+      .rpc();
+    await program.methods
+      .fundPrizePool(new anchor.BN(100_000_000))
+      .accounts({ ...fundAccounts, funder: sponsor.publicKey } as any)
+      .signers([sponsor])
+      .rpc();
+
+    let pool = await program.account.prizePool.fetch(setup.prizePoolPda);
+    expect(pool.totalFunded.toNumber()).to.equal(300_000_000);
+    expect(pool.funders.length).to.equal(2);
+    expect(pool.mint).to.be.null;
+
+    const claimAccounts = {
+      winner: winner.publicKey,
+      game: setup.gamePda,
+      prizePool: setup.prizePoolPda,
+    };
+
+    // Prizes are locked until the game completes
+    try {
+      await program.methods
+        .claimPrize()
+        .accounts(claimAccounts as any)
+        .signers([winner])
+        .rpc();
+      expect.fail("Expected claim on an active game to fail");
+    } catch (error) {
+      expect(error.message).to.include("GameNotEnded");
+    }
+
+    await winGame(setup, winner);
+
+    const balanceBefore = await provider.connection.getBalance(
+      winner.publicKey
+    );
+    await program.methods
+      .claimPrize()
+      .accounts(claimAccounts as any)
+      .signers([winner])
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(
+      winner.publicKey
+    );
+
+    // With a single winner the 70/30 split is rescaled to the whole pot
+    expect(balanceAfter - balanceBefore).to.equal(300_000_000);
+
+    pool = await program.account.prizePool.fetch(setup.prizePoolPda);
+    expect(pool.totalPaid.toNumber()).to.equal(300_000_000);
+    expect(pool.claimed.map((key) => key.toBase58())).to.deep.equal([
+      winner.publicKey.toBase58(),
+    ]);
+
+    try {
+      await program.methods
+        .claimPrize()
+        .accounts(claimAccounts as any)
+        .signers([winner])
+        .rpc();
+      expect.fail("Expected a second claim to fail");
+    } catch (error) {
+      expect(error.message).to.include("PrizeAlreadyClaimed");
+    }
+
+    try {
+      await program.methods
+        .claimPrize()
+        .accounts({ ...claimAccounts, winner: sponsor.publicKey } as any)
+        .signers([sponsor])
+        .rpc();
+      expect.fail("Expected a non-winner claim to fail");
+    } catch (error) {
+      expect(error.message).to.include("NotEligibleForReward");
+    }
+
+    // Funders are only refunded when nobody wins
+    try {
+      await program.methods
+        .refundPrize()
+        .accounts({
+          funder: sponsor.publicKey,
+          game: setup.gamePda,
+          capsule: setup.capsulePda,
+          prizePool: setup.prizePoolPda,
+        } as any)
+        .signers([sponsor])
+        .rpc();
+      expect.fail("Expected a refund on a won game to fail");
+    } catch (error) {
+      expect(error.message).to.include("PrizeRefundUnavailable");
+    }
+  });
+
+  it("Refunds funders when the game ends without a winner", async () => {
+    const setup = await createGame();
+
+    await program.methods
+      .createPrizePool([10000])
+      .accounts({
+        creator: provider.wallet.publicKey,
+        game: setup.gamePda,
+        prizePool: setup.prizePoolPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    await program.methods
+      .fundPrizePool(new anchor.BN(50_000_000))
+      .accounts({
+        funder: sponsor.publicKey,
+        game: setup.gamePda,
+        prizePool: setup.prizePoolPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([sponsor])
+      .rpc();
+
+    // No guesses were submitted, so the creator can close the game right away
+    await completeGame(setup);
+
+    const closeAccounts = {
+      creator: provider.wallet.publicKey,
+      prizePool: setup.prizePoolPda,
+    };
+
+    // The pool stays open while a funder is still owed a refund
+    try {
+      await program.methods
+        .closePrizePool()
+        .accounts(closeAccounts as any)
+        .rpc();
+      expect.fail("Expected closing an unsettled pool to fail");
+    } catch (error) {
+      expect(error.message).to.include("PrizePoolNotSettled");
+    }
+
+    const refundAccounts = {
+      funder: sponsor.publicKey,
+      game: setup.gamePda,
+      capsule: setup.capsulePda,
+      prizePool: setup.prizePoolPda,
+    };
+
+    const balanceBefore = await provider.connection.getBalance(
+      sponsor.publicKey
+    );
+    await program.methods
+      .refundPrize()
+      .accounts(refundAccounts as any)
+      .signers([sponsor])
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(
+      sponsor.publicKey
+    );
+    expect(balanceAfter - balanceBefore).to.equal(50_000_000);
+
+    try {
+      await program.methods
+        .refundPrize()
+        .accounts(refundAccounts as any)
+        .signers([sponsor])
+        .rpc();
+      expect.fail("Expected a second refund to fail");
+    } catch (error) {
+      expect(error.message).to.include("PrizeRefundUnavailable");
+    }
+
+    await program.methods
+      .closePrizePool()
+      .accounts(closeAccounts as any)
+      .rpc();
+    const closedPool = await provider.connection.getAccountInfo(
+      setup.prizePoolPda
+    );
+    expect(closedPool).to.be.null;
+  });
+
+  it("Refunds funders when a game without a winner is never completed", async () => {
+    const setup = await createGame();
+
+    await program.methods
+      .createPrizePool([10000])
+      .accounts({
+        creator: provider.wallet.publicKey,
+        game: setup.gamePda,
+        prizePool: setup.prizePoolPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    await program.methods
+      .fundPrizePool(new anchor.BN(50_000_000))
+      .accounts({
+        funder: sponsor.publicKey,
+        game: setup.gamePda,
+        prizePool: setup.prizePoolPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([sponsor])
+      .rpc();
+
+    const refundAccounts = {
+      funder: sponsor.publicKey,
+      game: setup.gamePda,
+      capsule: setup.capsulePda,
+      prizePool: setup.prizePoolPda,
+    };
+
+    try {
+      await program.methods
+        .refundPrize()
+        .accounts(refundAccounts as any)
+        .signers([sponsor])
+        .rpc();
+      expect.fail("Expected a refund before the reveal date to fail");
+    } catch (error) {
+      expect(error.message).to.include("GameNotEnded");
+    }
+
+    // Once the reveal date passes with no guesses left to judge, funders take their money back
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+    const balanceBefore = await provider.connection.getBalance(
+      sponsor.publicKey
+    );
+    await program.methods
+      .refundPrize()
+      .accounts(refundAccounts as any)
+      .signers([sponsor])
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(
+      sponsor.publicKey
+    );
+    expect(balanceAfter - balanceBefore).to.equal(50_000_000);
+
+    await program.methods
+      .closePrizePool()
+      .accounts({
+        creator: provider.wallet.publicKey,
+        prizePool: setup.prizePoolPda,
+      } as any)
+      .rpc();
+  });
+
+  it("Keeps the pot for the winner when a won game is never completed", async () => {
+    const setup = await createGame();
+    const winner = await newPlayer();
+
+    await program.methods
+      .createPrizePool([10000])
+      .accounts({
+        creator: provider.wallet.publicKey,
+        game: setup.gamePda,
+        prizePool: setup.prizePoolPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    await program.methods
+      .fundPrizePool(new anchor.BN(50_000_000))
+      .accounts({
+        funder: sponsor.publicKey,
+        game: setup.gamePda,
+        prizePool: setup.prizePoolPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([sponsor])
+      .rpc();
+
+    // The game has a winner but stays active
+    await winGame(setup, winner, false);
+
+    // A recorded winner keeps funders from pulling the pot
+    try {
+      await program.methods
+        .refundPrize()
+        .accounts({
+          funder: sponsor.publicKey,
+          game: setup.gamePda,
+          capsule: setup.capsulePda,
+          prizePool: setup.prizePoolPda,
+        } as any)
+        .signers([sponsor])
+        .rpc();
+      expect.fail("Expected a refund of a won pool to fail");
+    } catch (error) {
+      expect(error.message).to.include("PrizeRefundUnavailable");
+    }
+
+    // A late completion still pays the winner in full
+    await completeGame(setup);
+    const balanceBefore = await provider.connection.getBalance(
+      winner.publicKey
+    );
+    await program.methods
+      .claimPrize()
+      .accounts({
+        winner: winner.publicKey,
+        game: setup.gamePda,
+        prizePool: setup.prizePoolPda,
+      } as any)
+      .signers([winner])
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(
+      winner.publicKey
+    );
+    expect(balanceAfter - balanceBefore).to.equal(50_000_000);
+  });
+
+  it("Holds the pot while a revealed guess is still waiting for verification", async () => {
+    const setup = await createGame();
+    const winner = await newPlayer();
+
+    await program.methods
+      .createPrizePool([10000])
+      .accounts({
+        creator: provider.wallet.publicKey,
+        game: setup.gamePda,
+        prizePool: setup.prizePoolPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    await program.methods
+      .fundPrizePool(new anchor.BN(50_000_000))
+      .accounts({
+        funder: sponsor.publicKey,
+        game: setup.gamePda,
+        prizePool: setup.prizePoolPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([sponsor])
+      .rpc();
+
+    const { guessPda, leaderboardPda } = await openGuess(setup, winner);
+
+    // The creator cannot end the game over an unjudged guess
+    try {
+      await program.methods
+        .completeGame()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          game: setup.gamePda,
+          capsule: setup.capsulePda,
+          creatorLeaderboard: creatorLeaderboardPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+      expect.fail("Expected completing a game with a pending guess to fail");
+    } catch (error) {
+      expect(error.message).to.include("GameNotEnded");
+    }
+
+    // ...and funders cannot pull the pot out from under it
+    try {
+      await program.methods
+        .refundPrize()
+        .accounts({
+          funder: sponsor.publicKey,
+          game: setup.gamePda,
+          capsule: setup.capsulePda,
+          prizePool: setup.prizePoolPda,
+        } as any)
+        .signers([sponsor])
+        .rpc();
+      expect.fail("Expected a refund during verification to fail");
+    } catch (error) {
+      expect(error.message).to.include("GameNotEnded");
+    }
+
+    await verifyWinner(setup, guessPda, leaderboardPda);
+    await completeGame(setup);
+
+    const balanceBefore = await provider.connection.getBalance(
+      winner.publicKey
+    );
+    await program.methods
+      .claimPrize()
+      .accounts({
+        winner: winner.publicKey,
+        game: setup.gamePda,
+        prizePool: setup.prizePoolPda,
+      } as any)
+      .signers([winner])
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(
+      winner.publicKey
+    );
+    expect(balanceAfter - balanceBefore).to.equal(50_000_000);
+  });
+
+  it("Keeps a funder slot for the creator and rejects dust sponsors", async () => {
+    const setup = await createGame();
+
+    await program.methods
+      .createPrizePool([10000])
+      .accounts({
+        creator: provider.wallet.publicKey,
+        game: setup.gamePda,
+        prizePool: setup.prizePoolPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    const fundAccounts = {
+      game: setup.gamePda,
+      prizePool: setup.prizePoolPda,
+      systemProgram: SystemProgram.programId,
+    };
+
+    try {
+      await program.methods
+        .fundPrizePool(new anchor.BN(1))
+        .accounts({ ...fundAccounts, funder: sponsor.publicKey } as any)
+        .signers([sponsor])
+        .rpc();
+      expect.fail("Expected a dust contribution to be rejected");
+    } catch (error) {
+      expect(error.message).to.include("PrizeContributionTooSmall");
+    }
+
+    // Sponsors can fill every slot but the one kept for the creator
+    for (let i = 0; i < 7; i++) {
+      const funder = await newPlayer();
+      await program.methods
+        .fundPrizePool(new anchor.BN(10_000_000))
+        .accounts({ ...fundAccounts, funder: funder.publicKey } as any)
+        .signers([funder])
+        .rpc();
+    }
+
+    try {
+      await program.methods
+        .fundPrizePool(new anchor.BN(10_000_000))
+        .accounts({ ...fundAccounts, funder: sponsor.publicKey } as any)
+        .signers([sponsor])
+        .rpc();
+      expect.fail("Expected a sponsor to be refused the creator's slot");
+    } catch (error) {
+      expect(error.message).to.include("PrizeFunderLimitReached");
+    }
+
+    // The creator may still top up the pot with any amount
+    await program.methods
+      .fundPrizePool(new anchor.BN(1))
+      .accounts({ ...fundAccounts, funder: provider.wallet.publicKey } as any)
+      .rpc();
+
+    const pool = await program.account.prizePool.fetch(setup.prizePoolPda);
+    expect(pool.funders.length).to.equal(8);
+    expect(pool.totalFunded.toNumber()).to.equal(70_000_001);
+  });
+
+  it("Pays an SPL prize from the escrow token account", async () => {
+    const setup = await createGame();
+    const winner = await newPlayer();
+    const prizeEscrowPda = getPrizeEscrowPda(setup.prizePoolPda);
+
+    const mint = await createMint(
+      provider.connection,
+      (provider.wallet as anchor.Wallet).payer,
+      provider.wallet.publicKey,
+      null,
+      6
+    );
+    const creatorTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      (provider.wallet as anchor.Wallet).payer,
+      mint,
+      provider.wallet.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      (provider.wallet as anchor.Wallet).payer,
+      mint,
+      creatorTokenAccount.address,
+      provider.wallet.publicKey,
+      5_000_000
+    );
+
+    await program.methods
+      .createTokenPrizePool([10000])
+      .accounts({
+        creator: provider.wallet.publicKey,
+        game: setup.gamePda,
+        prizePool: setup.prizePoolPda,
+        mint,
+        prizeEscrow: prizeEscrowPda,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    // SOL cannot be added to a token pool
+    try {
+      await program.methods
+        .fundPrizePool(new anchor.BN(1_000_000))
+        .accounts({
+          funder: provider.wallet.publicKey,
+          game: setup.gamePda,
+          prizePool: setup.prizePoolPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+      expect.fail("Expected SOL funding of a token pool to fail");
+    } catch (error) {
+      expect(error.message).to.include("PrizeMintMismatch");
+    }
+
+    await program.methods
+      .fundTokenPrizePool(new anchor.BN(5_000_000))
+      .accounts({
+        funder: provider.wallet.publicKey,
+        game: setup.gamePda,
+        prizePool: setup.prizePoolPda,
+        mint,
+        funderTokenAccount: creatorTokenAccount.address,
+        prizeEscrow: prizeEscrowPda,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .rpc();
+
+    await winGame(setup, winner);
+
+    const winnerTokenAccount = getAssociatedTokenAddressSync(
+      mint,
+      winner.publicKey
+    );
+    await program.methods
+      .claimTokenPrize()
+      .accounts({
+        winner: winner.publicKey,
+        game: setup.gamePda,
+        prizePool: setup.prizePoolPda,
+        mint,
+        prizeEscrow: prizeEscrowPda,
+        winnerTokenAccount,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([winner])
+      .rpc();
+
+    const winnerBalance = await provider.connection.getTokenAccountBalance(
+      winnerTokenAccount
+    );
+    expect(winnerBalance.value.amount).to.equal("5000000");
+
+    const escrowBalance = await provider.connection.getTokenAccountBalance(
+      prizeEscrowPda
+    );
+    expect(escrowBalance.value.amount).to.equal("0");
+
+    await program.methods
+      .closeTokenPrizePool()
+      .accounts({
+        creator: provider.wallet.publicKey,
+        prizePool: setup.prizePoolPda,
+        mint,
+        prizeEscrow: prizeEscrowPda,
+        creatorTokenAccount: creatorTokenAccount.address,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      } as any)
+      .rpc();
+
+    // Both the pool and its escrow are gone, with their rent back with the creator
+    expect(await provider.connection.getAccountInfo(setup.prizePoolPda)).to.be
+      .null;
+    expect(await provider.connection.getAccountInfo(prizeEscrowPda)).to.be.null;
+  });
+});
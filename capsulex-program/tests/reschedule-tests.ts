@@ -0,0 +1,242 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { describe, before, it } from "mocha";
+import { expect } from "chai";
+import {
+  VAULT_SEED,
+  GUESS_SEED,
+  PROGRAM_CONFIG_SEED,
+  CREATOR_EARNINGS_SEED,
+} from "./constants";
+import { airdrop, createCapsule } from "./capsule";
+import { commitGuess } from "./guess";
+
+describe("Reveal Rescheduling", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PROGRAM_CONFIG_SEED)],
+    program.programId
+  );
+
+  const creator = Keypair.generate();
+  const guesser = Keypair.generate();
+
+  const [creatorEarningsPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(CREATOR_EARNINGS_SEED), creator.publicKey.toBuffer()],
+    program.programId
+  );
+
+  function newCapsule(revealIn: number) {
+    return createCapsule(program, {
+      creator,
+      answer: "reschedule secret",
+      revealIn,
+    });
+  }
+
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await airdrop(provider.connection, creator.publicKey, 2_000_000_000);
+    await airdrop(provider.connection, guesser.publicKey, 1_000_000_000);
+  });
+
+  it("Extends the reveal without moving the capsule address", async () => {
+    const { capsulePda, gamePda, revealDate } = await newCapsule(3);
+    const newRevealDate = revealDate.addn(3600);
+
+    await program.methods
+      .rescheduleReveal(newRevealDate)
+      .accounts({
+        creator: creator.publicKey,
+        capsule: capsulePda,
+        game: gamePda,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    const capsule = await program.account.capsule.fetch(capsulePda);
+    expect(capsule.revealDate.toNumber()).to.equal(newRevealDate.toNumber());
+    expect(capsule.capsuleIndex).to.not.be.null;
+
+    // The original date has passed, but the capsule now waits for the new one
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+    try {
+      await program.methods
+        .revealCapsule()
+        .accounts({
+          revealer: creator.publicKey,
+          capsule: capsulePda,
+        } as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("Expected the rescheduled capsule to stay sealed");
+    } catch (error) {
+      expect(error.message).to.include("CapsuleNotReady");
+    }
+  });
+
+  it("Never moves the reveal earlier", async () => {
+    const { capsulePda, gamePda, revealDate } = await newCapsule(3600);
+
+    try {
+      await program.methods
+        .rescheduleReveal(revealDate.subn(60))
+        .accounts({
+          creator: creator.publicKey,
+          capsule: capsulePda,
+          game: gamePda,
+        } as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("Expected shortening the reveal to fail");
+    } catch (error) {
+      expect(error.message).to.include("RevealDateNotExtended");
+    }
+  });
+
+  it("Only lets the creator reschedule", async () => {
+    const { capsulePda, gamePda, revealDate } = await newCapsule(3600);
+
+    try {
+      await program.methods
+        .rescheduleReveal(revealDate.addn(60))
+        .accounts({
+          creator: guesser.publicKey,
+          capsule: capsulePda,
+          game: gamePda,
+        } as any)
+        .signers([guesser])
+        .rpc();
+      expect.fail("Expected a reschedule by another wallet to fail");
+    } catch (error) {
+      expect(error.message).to.include("UnauthorizedCreator");
+    }
+  });
+
+  it("Requires every guesser's consent once guesses exist", async () => {
+    const { capsulePda, gamePda, revealDate } = await newCapsule(3600);
+    const secondGuesser = Keypair.generate();
+    await airdrop(provider.connection, secondGuesser.publicKey, 1_000_000_000);
+
+    const guessPdas: PublicKey[] = [];
+    for (const [index, player] of [guesser, secondGuesser].entries()) {
+      const [guessPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GUESS_SEED),
+          gamePda.toBuffer(),
+          player.publicKey.toBuffer(),
+          Buffer.from([index, 0, 0, 0]),
+        ],
+        program.programId
+      );
+      await program.methods
+        .submitGuess(commitGuess("reschedule guess").commitment, false)
+        .accounts({
+          guesser: player.publicKey,
+          game: gamePda,
+          capsule: capsulePda,
+          guess: guessPda,
+          vault: vaultPda,
+          config: configPda,
+          creatorEarnings: creatorEarningsPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([player])
+        .rpc();
+      guessPdas.push(guessPda);
+    }
+
+    async function consent(player: Keypair, guessPda: PublicKey) {
+      await program.methods
+        .consentReschedule()
+        .accounts({
+          guesser: player.publicKey,
+          capsule: capsulePda,
+          game: gamePda,
+          guess: guessPda,
+        } as any)
+        .signers([player])
+        .rpc();
+    }
+
+    const newRevealDate = revealDate.addn(3600);
+    const accounts = {
+      creator: creator.publicKey,
+      capsule: capsulePda,
+      game: gamePda,
+    };
+
+    // Each guesser consents in their own transaction
+    await consent(guesser, guessPdas[0]);
+    try {
+      await consent(guesser, guessPdas[0]);
+      expect.fail("Expected a second consent for the same guess to fail");
+    } catch (error) {
+      expect(error.message).to.include("RescheduleAlreadyConsented");
+    }
+    try {
+      await consent(guesser, guessPdas[1]);
+      expect.fail("Expected consenting for another guesser's guess to fail");
+    } catch (error) {
+      expect(error.message).to.include("InvalidAccountOwner");
+    }
+
+    try {
+      await program.methods
+        .rescheduleReveal(newRevealDate)
+        .accounts(accounts as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("Expected a reschedule without every consent to fail");
+    } catch (error) {
+      expect(error.message).to.include("MissingGuesserConsent");
+    }
+
+    await consent(secondGuesser, guessPdas[1]);
+    await program.methods
+      .rescheduleReveal(newRevealDate)
+      .accounts(accounts as any)
+      .signers([creator])
+      .rpc();
+
+    const capsule = await program.account.capsule.fetch(capsulePda);
+    expect(capsule.revealDate.toNumber()).to.equal(newRevealDate.toNumber());
+
+    // Consents are used up, so the next reschedule needs fresh ones
+    const game = await program.account.game.fetch(gamePda);
+    expect(game.rescheduleConsents).to.equal(0);
+    try {
+      await program.methods
+        .rescheduleReveal(newRevealDate.addn(3600))
+        .accounts(accounts as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("Expected a reschedule with spent consents to fail");
+    } catch (error) {
+      expect(error.message).to.include("MissingGuesserConsent");
+    }
+    await consent(guesser, guessPdas[0]);
+  });
+});
@@ -0,0 +1,344 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import {
+  createAssociatedTokenAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+  getNonTransferable,
+  getTokenMetadata,
+  transferChecked,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { describe, before, after, it } from "mocha";
+import { expect } from "chai";
+import {
+  VAULT_SEED,
+  GUESS_SEED,
+  LEADERBOARD_SEED,
+  BADGE_MINT_SEED,
+  WINNER_BADGE_SEED,
+  PROGRAM_CONFIG_SEED,
+  COLLECTION_AUTHORITY_SEED,
+} from "./constants";
+import { airdrop, createCapsule } from "./capsule";
+import {
+  ensureOracleRegistered,
+  getUsedNoncePda,
+  loadDevOracleKey,
+  signOracleVerdict,
+} from "./oracle";
+import { commitGuess, revealGuess } from "./guess";
+import { ensureNftCollections, TOKEN_METADATA_PROGRAM_ID } from "./collection";
+
+describe("Soulbound NFTs", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+  const devOracle = loadDevOracleKey();
+  const secretAnswer = "soulbound secret";
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PROGRAM_CONFIG_SEED)],
+    program.programId
+  );
+  const [collectionAuthorityPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(COLLECTION_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const winner = Keypair.generate();
+  const buyer = Keypair.generate();
+
+  let gamePda: PublicKey;
+  let badgeMintPda: PublicKey;
+
+  function getLeaderboardPda(player: PublicKey) {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(LEADERBOARD_SEED), player.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  async function setSoulboundNfts(enabled: boolean) {
+    await program.methods
+      .updateConfig({
+        appAuthority: null,
+        capsuleCreationFee: null,
+        serviceFee: null,
+        badgeMintFee: null,
+        trophyMintFee: null,
+        creatorShareBps: null,
+        refundGracePeriod: null,
+        soulboundNfts: enabled,
+      })
+      .accounts({
+        authority: provider.wallet.publicKey,
+        vault: vaultPda,
+        config: configPda,
+      } as any)
+      .rpc();
+  }
+
+  function winnerBadgePda() {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(WINNER_BADGE_SEED),
+        gamePda.toBuffer(),
+        winner.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    return pda;
+  }
+
+  function soulboundTokenAccount(owner: PublicKey) {
+    return getAssociatedTokenAddressSync(
+      badgeMintPda,
+      owner,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+  }
+
+  // One game with a single verified winner
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await ensureOracleRegistered(program, provider, devOracle);
+    await ensureNftCollections(program, provider);
+    await airdrop(provider.connection, winner.publicKey, 1_000_000_000);
+    await airdrop(provider.connection, buyer.publicKey, 1_000_000_000);
+
+    const capsule = await createCapsule(program, {
+      answer: secretAnswer,
+      revealIn: 3,
+    });
+    const { capsulePda, revealDate } = capsule;
+    gamePda = capsule.gamePda;
+    [badgeMintPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(BADGE_MINT_SEED),
+        gamePda.toBuffer(),
+        winner.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    const [guessPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(GUESS_SEED),
+        gamePda.toBuffer(),
+        winner.publicKey.toBuffer(),
+        Buffer.from([0, 0, 0, 0]),
+      ],
+      program.programId
+    );
+    const committed = commitGuess(secretAnswer);
+    await program.methods
+      .submitGuess(committed.commitment, false)
+      .accounts({
+        guesser: winner.publicKey,
+        game: gamePda,
+        capsule: capsulePda,
+        guess: guessPda,
+        vault: vaultPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([winner])
+      .rpc();
+    await program.methods
+      .initializeLeaderboard(winner.publicKey)
+      .accounts({
+        authority: winner.publicKey,
+        user: winner.publicKey,
+        leaderboard: getLeaderboardPda(winner.publicKey),
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([winner])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+    await program.methods
+      .revealCapsule()
+      .accounts({
+        revealer: provider.wallet.publicKey,
+        capsule: capsulePda,
+      } as any)
+      .rpc();
+
+    await revealGuess(
+      program,
+      committed,
+      {
+        guesser: winner.publicKey,
+        guess: guessPda,
+        game: gamePda,
+        capsule: capsulePda,
+      },
+      winner
+    );
+    const verdict = signOracleVerdict(
+      devOracle,
+      secretAnswer,
+      secretAnswer,
+      true
+    );
+    await program.methods
+      .verifyGuess(
+        secretAnswer,
+        null,
+        true,
+        verdict.timestamp,
+        verdict.nonce,
+        verdict.signature
+      )
+      .accounts({
+        authority: provider.wallet.publicKey,
+        guess: guessPda,
+        game: gamePda,
+        capsule: capsulePda,
+        leaderboard: getLeaderboardPda(winner.publicKey),
+        usedNonce: getUsedNoncePda(program.programId, verdict.nonce),
+      } as any)
+      .preInstructions([verdict.ed25519Ix])
+      .rpc();
+
+    await setSoulboundNfts(true);
+  });
+
+  after(async () => {
+    // Other suites mint legacy badges and trophies
+    await setSoulboundNfts(false);
+  });
+
+  it("Rejects legacy badge mints while soulbound NFTs are enabled", async () => {
+    try {
+      await program.methods
+        .mintWinnerBadge("https://example.com/badge.json")
+        .accounts({
+          authority: winner.publicKey,
+          winner: winner.publicKey,
+          game: gamePda,
+          badgeMint: badgeMintPda,
+          winnerTokenAccount: anchor.utils.token.associatedAddress({
+            mint: badgeMintPda,
+            owner: winner.publicKey,
+          }),
+          winnerBadge: winnerBadgePda(),
+          vault: vaultPda,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        } as any)
+        .signers([winner])
+        .rpc();
+      expect.fail("Expected the legacy badge path to be disabled");
+    } catch (error) {
+      expect(error.message).to.include("SoulboundNftsRequired");
+    }
+  });
+
+  it("Mints a non-transferable Token-2022 badge with on-chain metadata", async () => {
+    await program.methods
+      .mintSoulboundBadge("https://example.com/badge.json")
+      .accounts({
+        authority: winner.publicKey,
+        winner: winner.publicKey,
+        game: gamePda,
+        badgeMint: badgeMintPda,
+        winnerTokenAccount: soulboundTokenAccount(winner.publicKey),
+        winnerBadge: winnerBadgePda(),
+        vault: vaultPda,
+        config: configPda,
+        collectionAuthority: collectionAuthorityPda,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([winner])
+      .rpc();
+
+    const mint = await getMint(
+      provider.connection,
+      badgeMintPda,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(mint.supply.toString()).to.equal("1");
+    expect(mint.mintAuthority).to.be.null;
+    expect(getNonTransferable(mint)).to.not.be.null;
+
+    const metadata = await getTokenMetadata(
+      provider.connection,
+      badgeMintPda,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(metadata.name).to.equal("1st Place");
+    expect(metadata.symbol).to.equal("CXBADGE");
+    expect(metadata.uri).to.equal("https://example.com/badge.json");
+    expect(metadata.updateAuthority.toBase58()).to.equal(
+      collectionAuthorityPda.toBase58()
+    );
+
+    const badge = await program.account.winnerBadge.fetch(winnerBadgePda());
+    expect(badge.placement).to.equal(1);
+    expect(badge.badgeMint.toBase58()).to.equal(badgeMintPda.toBase58());
+  });
+
+  it("Rejects transferring a soulbound badge", async () => {
+    const buyerTokenAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      buyer,
+      badgeMintPda,
+      buyer.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    try {
+      await transferChecked(
+        provider.connection,
+        winner,
+        soulboundTokenAccount(winner.publicKey),
+        badgeMintPda,
+        buyerTokenAccount,
+        winner,
+        1,
+        0,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect.fail("Expected the transfer to be rejected");
+    } catch (error) {
+      expect(error.message).to.not.include("Expected the transfer");
+    }
+
+    const balance = await provider.connection.getTokenAccountBalance(
+      soulboundTokenAccount(winner.publicKey)
+    );
+    expect(balance.value.amount).to.equal("1");
+  });
+});
@@ -0,0 +1,235 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { describe, before, after, it } from "mocha";
+import { expect } from "chai";
+import { VAULT_SEED, TREASURY_SEED } from "./constants";
+import { airdrop, createCapsule } from "./capsule";
+
+describe("Treasury Management", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+  const [treasuryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(TREASURY_SEED)],
+    program.programId
+  );
+
+  const outsider = Keypair.generate();
+  const destination = Keypair.generate();
+  const opsWallet = Keypair.generate();
+  const devWallet = Keypair.generate();
+
+  // Creating a capsule pays the creation fee into the vault
+  async function collectCreationFee() {
+    await createCapsule(program, { answer: "treasury", isGamified: false });
+  }
+
+  async function withdrawableBalance() {
+    const vaultInfo = await provider.connection.getAccountInfo(vaultPda);
+    const rentExemptMinimum =
+      await provider.connection.getMinimumBalanceForRentExemption(
+        vaultInfo.data.length
+      );
+    return vaultInfo.lamports - rentExemptMinimum;
+  }
+
+  function treasuryAccounts(authority: PublicKey) {
+    return {
+      authority,
+      vault: vaultPda,
+      treasury: treasuryPda,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await airdrop(provider.connection, outsider.publicKey, 1_000_000_000);
+    // Recipients start funded so small payouts keep them rent exempt
+    await airdrop(provider.connection, destination.publicKey, 1_000_000_000);
+    await airdrop(provider.connection, opsWallet.publicKey, 1_000_000_000);
+    await airdrop(provider.connection, devWallet.publicKey, 1_000_000_000);
+
+    await collectCreationFee();
+    await collectCreationFee();
+  });
+
+  after(async () => {
+    // Leave split payouts disabled for other suites
+    await program.methods
+      .setTreasuryRecipients([])
+      .accounts(treasuryAccounts(provider.wallet.publicKey) as any)
+      .rpc();
+  });
+
+  it("Rejects withdrawals from a non-authority", async () => {
+    try {
+      await program.methods
+        .withdrawFees(new anchor.BN(1_000))
+        .accounts({
+          ...treasuryAccounts(outsider.publicKey),
+          destination: outsider.publicKey,
+        } as any)
+        .signers([outsider])
+        .rpc();
+      expect.fail("Expected InvalidProgramAuthority");
+    } catch (error) {
+      expect(error.message).to.include("InvalidProgramAuthority");
+    }
+  });
+
+  it("Rejects withdrawals below the rent-exempt minimum", async () => {
+    const available = await withdrawableBalance();
+
+    try {
+      await program.methods
+        .withdrawFees(new anchor.BN(available + 1))
+        .accounts({
+          ...treasuryAccounts(provider.wallet.publicKey),
+          destination: destination.publicKey,
+        } as any)
+        .rpc();
+      expect.fail("Expected InsufficientVaultBalance");
+    } catch (error) {
+      expect(error.message).to.include("InsufficientVaultBalance");
+    }
+  });
+
+  it("Withdraws fees to a destination account", async () => {
+    const amount = 1_000_000;
+    const vaultBefore = await program.account.programVault.fetch(vaultPda);
+    const destinationBefore = await provider.connection.getBalance(
+      destination.publicKey
+    );
+    const availableBefore = await withdrawableBalance();
+
+    await program.methods
+      .withdrawFees(new anchor.BN(amount))
+      .accounts({
+        ...treasuryAccounts(provider.wallet.publicKey),
+        destination: destination.publicKey,
+      } as any)
+      .rpc();
+
+    const destinationAfter = await provider.connection.getBalance(
+      destination.publicKey
+    );
+    expect(destinationAfter - destinationBefore).to.equal(amount);
+    expect(await withdrawableBalance()).to.equal(availableBefore - amount);
+
+    const treasury = await program.account.treasury.fetch(treasuryPda);
+    expect(treasury.totalWithdrawn.toNumber()).to.be.at.least(amount);
+
+    // Payouts count as distributed; fees collected are unchanged
+    const vaultAfter = await program.account.programVault.fetch(vaultPda);
+    expect(
+      vaultAfter.totalRewardsDistributed
+        .sub(vaultBefore.totalRewardsDistributed)
+        .toNumber()
+    ).to.equal(amount);
+    expect(vaultAfter.totalFeesCollected.toNumber()).to.equal(
+      vaultBefore.totalFeesCollected.toNumber()
+    );
+  });
+
+  it("Rejects treasury shares that do not sum to 100%", async () => {
+    try {
+      await program.methods
+        .setTreasuryRecipients([
+          { wallet: opsWallet.publicKey, shareBps: 6000 },
+          { wallet: devWallet.publicKey, shareBps: 3000 },
+        ])
+        .accounts(treasuryAccounts(provider.wallet.publicKey) as any)
+        .rpc();
+      expect.fail("Expected InvalidTreasuryShares");
+    } catch (error) {
+      expect(error.message).to.include("InvalidTreasuryShares");
+    }
+  });
+
+  it("Splits fees between configured treasury recipients", async () => {
+    await program.methods
+      .setTreasuryRecipients([
+        { wallet: opsWallet.publicKey, shareBps: 7000 },
+        { wallet: devWallet.publicKey, shareBps: 3000 },
+      ])
+      .accounts(treasuryAccounts(provider.wallet.publicKey) as any)
+      .rpc();
+
+    const amount = 1_000_001;
+    const opsBefore = await provider.connection.getBalance(opsWallet.publicKey);
+    const devBefore = await provider.connection.getBalance(devWallet.publicKey);
+    const treasuryBefore = await program.account.treasury.fetch(treasuryPda);
+    const vaultBefore = await program.account.programVault.fetch(vaultPda);
+
+    await program.methods
+      .distributeFees(new anchor.BN(amount))
+      .accounts({
+        authority: provider.wallet.publicKey,
+        vault: vaultPda,
+        treasury: treasuryPda,
+      } as any)
+      .remainingAccounts([
+        { pubkey: opsWallet.publicKey, isWritable: true, isSigner: false },
+        { pubkey: devWallet.publicKey, isWritable: true, isSigner: false },
+      ])
+      .rpc();
+
+    const opsAfter = await provider.connection.getBalance(opsWallet.publicKey);
+    const devAfter = await provider.connection.getBalance(devWallet.publicKey);
+    expect(opsAfter - opsBefore).to.equal(700_000);
+    // Last recipient receives the rounding remainder
+    expect(devAfter - devBefore).to.equal(300_001);
+
+    const treasuryAfter = await program.account.treasury.fetch(treasuryPda);
+    expect(
+      treasuryAfter.totalWithdrawn.sub(treasuryBefore.totalWithdrawn).toNumber()
+    ).to.equal(amount);
+    const vaultAfter = await program.account.programVault.fetch(vaultPda);
+    expect(
+      vaultAfter.totalRewardsDistributed
+        .sub(vaultBefore.totalRewardsDistributed)
+        .toNumber()
+    ).to.equal(amount);
+  });
+
+  it("Rejects split payouts to mismatched recipients", async () => {
+    try {
+      await program.methods
+        .distributeFees(new anchor.BN(100_000))
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          treasury: treasuryPda,
+        } as any)
+        .remainingAccounts([
+          { pubkey: devWallet.publicKey, isWritable: true, isSigner: false },
+          { pubkey: opsWallet.publicKey, isWritable: true, isSigner: false },
+        ])
+        .rpc();
+      expect.fail("Expected TreasuryRecipientMismatch");
+    } catch (error) {
+      expect(error.message).to.include("TreasuryRecipientMismatch");
+    }
+  });
+});
@@ -0,0 +1,307 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { describe, before, it } from "mocha";
+import { expect } from "chai";
+import { VAULT_SEED, LEADERBOARD_SEED } from "./constants";
+import { airdrop, createCapsule } from "./capsule";
+import { ensureNftCollections, TOKEN_METADATA_PROGRAM_ID } from "./collection";
+import {
+  getTrophyDefinitionPda,
+  getTrophyMintPda,
+  trophyDefinitionParams,
+} from "./trophy";
+
+describe("Trophy Definitions", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+
+  const firstUser = Keypair.generate();
+  const secondUser = Keypair.generate();
+  const outsider = Keypair.generate();
+
+  // Definitions persist on the validator, so every run uses fresh trophy types
+  const runId = Date.now().toString(36);
+  const limitedTrophy = `limited-${runId}`;
+
+  function getLeaderboardPda(player: PublicKey) {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(LEADERBOARD_SEED), player.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  function createDefinition(
+    trophyType: string,
+    overrides: Record<string, any> = {},
+    authority: Keypair | null = null
+  ) {
+    const signer = authority ? authority.publicKey : provider.wallet.publicKey;
+    const builder = program.methods
+      .createTrophyDefinition(
+        trophyType,
+        trophyDefinitionParams(overrides) as any
+      )
+      .accounts({
+        authority: signer,
+        vault: vaultPda,
+        trophyDefinition: getTrophyDefinitionPda(program, trophyType),
+        systemProgram: SystemProgram.programId,
+      } as any);
+    return authority ? builder.signers([authority]).rpc() : builder.rpc();
+  }
+
+  function updateDefinition(
+    trophyType: string,
+    overrides: Record<string, any>,
+    isActive: boolean
+  ) {
+    return program.methods
+      .updateTrophyDefinition(
+        trophyType,
+        trophyDefinitionParams(overrides) as any,
+        isActive
+      )
+      .accounts({
+        authority: provider.wallet.publicKey,
+        vault: vaultPda,
+        trophyDefinition: getTrophyDefinitionPda(program, trophyType),
+      } as any)
+      .rpc();
+  }
+
+  function mintTrophy(user: Keypair, trophyType: string, season = 0) {
+    const trophyMintPda = getTrophyMintPda(
+      program,
+      user.publicKey,
+      trophyType,
+      season
+    );
+
+    return program.methods
+      .mintTrophyNft(trophyType)
+      .accounts({
+        authority: user.publicKey,
+        user: user.publicKey,
+        leaderboard: getLeaderboardPda(user.publicKey),
+        trophyDefinition: getTrophyDefinitionPda(program, trophyType),
+        trophyMint: trophyMintPda,
+        userTokenAccount: anchor.utils.token.associatedAddress({
+          mint: trophyMintPda,
+          owner: user.publicKey,
+        }),
+        vault: vaultPda,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      } as any)
+      .signers([user])
+      .rpc();
+  }
+
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await ensureNftCollections(program, provider);
+    for (const user of [firstUser, secondUser, outsider]) {
+      await airdrop(provider.connection, user.publicKey, 1_000_000_000);
+    }
+    for (const user of [firstUser, secondUser]) {
+      await program.methods
+        .initializeLeaderboard(user.publicKey)
+        .accounts({
+          authority: user.publicKey,
+          user: user.publicKey,
+          leaderboard: getLeaderboardPda(user.publicKey),
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([user])
+        .rpc();
+    }
+  });
+
+  it("Only the vault authority can create definitions", async () => {
+    try {
+      await createDefinition(`rogue-${runId}`, {}, outsider);
+      expect.fail("Expected an outsider to be rejected");
+    } catch (error) {
+      expect(error.message).to.include("InvalidProgramAuthority");
+    }
+  });
+
+  it("Mints numbered trophies until the supply cap is reached", async () => {
+    await createDefinition(limitedTrophy, {
+      season: 1,
+      maxSupply: 1,
+      metadataUriTemplate: `https://metadata.capsulex.com/trophy/${limitedTrophy}/{serial}`,
+    });
+
+    await mintTrophy(firstUser, limitedTrophy, 1);
+    const definition = await program.account.trophyDefinition.fetch(
+      getTrophyDefinitionPda(program, limitedTrophy)
+    );
+    expect(definition.minted).to.equal(1);
+    expect(definition.season).to.equal(1);
+
+    try {
+      await mintTrophy(secondUser, limitedTrophy, 1);
+      expect.fail("Expected the capped trophy to be sold out");
+    } catch (error) {
+      expect(error.message).to.include("TrophySupplyExhausted");
+    }
+  });
+
+  it("Rejects caps below the number already minted", async () => {
+    try {
+      await updateDefinition(limitedTrophy, { maxSupply: 0 }, true);
+      expect.fail("Expected a cap below the minted count to fail");
+    } catch (error) {
+      expect(error.message).to.include("InvalidTrophyDefinition");
+    }
+  });
+
+  it("Evaluates the definition's leaderboard criteria", async () => {
+    const pointsTrophy = `points-${runId}`;
+    await createDefinition(pointsTrophy, {
+      criteria: {
+        minGamesWon: 0,
+        minGamesPlayed: 0,
+        minCapsulesCreated: 0,
+        minTotalPoints: new anchor.BN(1_000_000),
+      },
+    });
+
+    try {
+      await mintTrophy(firstUser, pointsTrophy);
+      expect.fail("Expected the points requirement to fail");
+    } catch (error) {
+      expect(error.message).to.include("NotEligibleForReward");
+    }
+  });
+
+  it("Awards capsule-count trophies once a creator has made enough capsules", async () => {
+    const creatorTrophy = `creator-${runId}`;
+    await createDefinition(creatorTrophy, {
+      criteria: {
+        minGamesWon: 0,
+        minGamesPlayed: 0,
+        minCapsulesCreated: 2,
+        minTotalPoints: new anchor.BN(0),
+      },
+    });
+
+    // The first capsule also creates the creator's leaderboard entry
+    await createCapsule(program, {
+      creator: outsider,
+      answer: "trophy capsule",
+      isGamified: false,
+    });
+    try {
+      await mintTrophy(outsider, creatorTrophy);
+      expect.fail("Expected one capsule to fall short of the requirement");
+    } catch (error) {
+      expect(error.message).to.include("NotEligibleForReward");
+    }
+
+    await createCapsule(program, {
+      creator: outsider,
+      answer: "trophy capsule",
+      isGamified: false,
+    });
+    const leaderboard = await program.account.leaderboardEntry.fetch(
+      getLeaderboardPda(outsider.publicKey)
+    );
+    expect(leaderboard.capsulesCreated).to.equal(2);
+
+    await mintTrophy(outsider, creatorTrophy);
+    const balance = await provider.connection.getTokenAccountBalance(
+      anchor.utils.token.associatedAddress({
+        mint: getTrophyMintPda(program, outsider.publicKey, creatorTrophy),
+        owner: outsider.publicKey,
+      })
+    );
+    expect(balance.value.amount).to.equal("1");
+  });
+
+  it("Rejects mints outside the season window", async () => {
+    const pastSeasonTrophy = `past-${runId}`;
+    await createDefinition(pastSeasonTrophy, {
+      season: 1,
+      seasonStart: new anchor.BN(1),
+      seasonEnd: new anchor.BN(2),
+    });
+
+    try {
+      await mintTrophy(firstUser, pastSeasonTrophy, 1);
+      expect.fail("Expected the closed season to fail");
+    } catch (error) {
+      expect(error.message).to.include("TrophySeasonNotOpen");
+    }
+  });
+
+  it("Mints the same trophy again in a later season", async () => {
+    const seasonalTrophy = `seasonal-${runId}`;
+    await createDefinition(seasonalTrophy, { season: 1 });
+    await mintTrophy(firstUser, seasonalTrophy, 1);
+
+    await updateDefinition(seasonalTrophy, { season: 2 }, true);
+    await mintTrophy(firstUser, seasonalTrophy, 2);
+
+    const definition = await program.account.trophyDefinition.fetch(
+      getTrophyDefinitionPda(program, seasonalTrophy)
+    );
+    expect(definition.minted).to.equal(2);
+
+    // Each season's trophy is its own mint held by the same winner
+    for (const season of [1, 2]) {
+      const tokenAccount = anchor.utils.token.associatedAddress({
+        mint: getTrophyMintPda(
+          program,
+          firstUser.publicKey,
+          seasonalTrophy,
+          season
+        ),
+        owner: firstUser.publicKey,
+      });
+      const balance = await provider.connection.getTokenAccountBalance(
+        tokenAccount
+      );
+      expect(balance.value.amount).to.equal("1");
+    }
+  });
+
+  it("Rejects mints of retired definitions", async () => {
+    const retiredTrophy = `retired-${runId}`;
+    await createDefinition(retiredTrophy);
+    await updateDefinition(retiredTrophy, {}, false);
+
+    try {
+      await mintTrophy(secondUser, retiredTrophy);
+      expect.fail("Expected the retired trophy to fail");
+    } catch (error) {
+      expect(error.message).to.include("TrophyDefinitionInactive");
+    }
+  });
+});
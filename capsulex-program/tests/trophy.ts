@@ -0,0 +1,115 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  TROPHY_DEFINITION_SEED,
+  TROPHY_MINT_SEED,
+  VAULT_SEED,
+} from "./constants";
+
+export function getTrophyDefinitionPda(
+  program: Program<Capsulex>,
+  trophyType: string
+) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(TROPHY_DEFINITION_SEED), Buffer.from(trophyType)],
+    program.programId
+  )[0];
+}
+
+// One mint per user, definition and season, so a trophy can be earned again in a later season
+export function getTrophyMintPda(
+  program: Program<Capsulex>,
+  user: PublicKey,
+  trophyType: string,
+  season = 0
+) {
+  const seasonBytes = Buffer.alloc(2);
+  seasonBytes.writeUInt16LE(season);
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(TROPHY_MINT_SEED),
+      user.toBuffer(),
+      getTrophyDefinitionPda(program, trophyType).toBuffer(),
+      seasonBytes,
+    ],
+    program.programId
+  )[0];
+}
+
+export function trophyDefinitionParams(overrides: Record<string, any> = {}) {
+  return {
+    criteria: {
+      minGamesWon: 0,
+      minGamesPlayed: 0,
+      minCapsulesCreated: 0,
+      minTotalPoints: new anchor.BN(0),
+    },
+    season: 0,
+    seasonStart: new anchor.BN(0),
+    seasonEnd: null,
+    maxSupply: null,
+    metadataUriTemplate: "https://metadata.capsulex.com/trophy/{serial}",
+    ...overrides,
+  };
+}
+
+// The trophies mint_trophy_nft used to hardcode, created once per validator
+export async function ensureTrophyDefinitions(
+  program: Program<Capsulex>,
+  provider: anchor.AnchorProvider
+) {
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+  const trophies = [
+    {
+      trophyType: "winner",
+      minGamesWon: 1,
+      minGamesPlayed: 0,
+      minCapsulesCreated: 0,
+    },
+    {
+      trophyType: "veteran",
+      minGamesWon: 0,
+      minGamesPlayed: 10,
+      minCapsulesCreated: 0,
+    },
+    {
+      trophyType: "creator",
+      minGamesWon: 0,
+      minGamesPlayed: 0,
+      minCapsulesCreated: 5,
+    },
+    {
+      trophyType: "champion",
+      minGamesWon: 10,
+      minGamesPlayed: 0,
+      minCapsulesCreated: 0,
+    },
+  ];
+
+  for (const { trophyType, ...criteria } of trophies) {
+    try {
+      await program.methods
+        .createTrophyDefinition(
+          trophyType,
+          trophyDefinitionParams({
+            criteria: { ...criteria, minTotalPoints: new anchor.BN(0) },
+            metadataUriTemplate: `https://metadata.capsulex.com/trophy/${trophyType}/{serial}`,
+          }) as any
+        )
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          trophyDefinition: getTrophyDefinitionPda(program, trophyType),
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Definition already created by another test file
+    }
+  }
+}
@@ -3,27 +3,11 @@ import { Program } from "@coral-xyz/anchor";
 import { Capsulex } from "../target/types/capsulex";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
 import { describe, before, it } from "mocha";
-import { VAULT_SEED, CAPSULE_SEED, CAPSULE_MINT_SEED } from "./constants";
+import { VAULT_SEED, CAPSULE_MINT_SEED } from "./constants";
+import { nextCapsulePda } from "./capsule";
 import { expect } from "chai";
 import crypto from "crypto";
 
-// Helper functions
-function getCapsulePda(
-  creator: PublicKey,
-  revealDate: anchor.BN,
-  programId: PublicKey
-) {
-  const [pda] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from(CAPSULE_SEED),
-      creator.toBuffer(),
-      Buffer.from(revealDate.toArray("le", 8)),
-    ],
-    programId
-  );
-  return pda;
-}
-
 function getNftMintPda(capsule: PublicKey, programId: PublicKey) {
   const [pda] = PublicKey.findProgramAddressSync(
     [Buffer.from(CAPSULE_MINT_SEED), capsule.toBuffer()],
@@ -97,11 +81,7 @@ describe("On-Chain Validation Efficiency Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3600);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -153,11 +133,7 @@ describe("On-Chain Validation Efficiency Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3601);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -202,11 +178,7 @@ describe("On-Chain Validation Efficiency Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3602);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -251,11 +223,7 @@ describe("On-Chain Validation Efficiency Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3603);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -299,11 +267,7 @@ describe("On-Chain Validation Efficiency Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3604);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -357,11 +321,7 @@ describe("On-Chain Validation Efficiency Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3605);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -419,11 +379,7 @@ describe("On-Chain Validation Efficiency Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3606);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -480,11 +436,7 @@ describe("On-Chain Validation Efficiency Tests", () => {
       const currentTime = Math.floor(Date.now() / 1000);
       const revealDate = new anchor.BN(currentTime + 3607);
 
-      const capsulePda = getCapsulePda(
-        provider.wallet.publicKey,
-        revealDate,
-        program.programId
-      );
+      const capsulePda = await nextCapsulePda(program, provider.wallet.publicKey);
       const nftMintPda = getNftMintPda(capsulePda, program.programId);
       const gamePda = getGamePda(capsulePda, program.programId);
       const accounts = getDefaultAccounts({
@@ -0,0 +1,381 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Capsulex } from "../target/types/capsulex";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { describe, before, it } from "mocha";
+import { expect } from "chai";
+import {
+  VAULT_SEED,
+  GUESS_SEED,
+  LEADERBOARD_SEED,
+  BADGE_MINT_SEED,
+  WINNER_BADGE_SEED,
+  COLLECTION_AUTHORITY_SEED,
+  PROGRAM_CONFIG_SEED,
+} from "./constants";
+import { airdrop, createCapsule } from "./capsule";
+import {
+  ensureOracleRegistered,
+  getUsedNoncePda,
+  loadDevOracleKey,
+  signOracleVerdict,
+} from "./oracle";
+import { commitGuess, GuessCommitment, revealGuess } from "./guess";
+import {
+  ensureNftCollections,
+  getMetadataPda,
+  metadataUpdateAuthority,
+  TOKEN_METADATA_PROGRAM_ID,
+} from "./collection";
+
+describe("Winner Badges", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.capsulex as Program<Capsulex>;
+  const devOracle = loadDevOracleKey();
+  const secretAnswer = "badge secret";
+
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(VAULT_SEED)],
+    program.programId
+  );
+
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PROGRAM_CONFIG_SEED)],
+    program.programId
+  );
+
+  const firstWinner = Keypair.generate();
+  const secondWinner = Keypair.generate();
+  const loser = Keypair.generate();
+
+  const [collectionAuthorityPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(COLLECTION_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  let capsulePda: PublicKey;
+  let gamePda: PublicKey;
+  let bondPda: PublicKey;
+
+  function getLeaderboardPda(player: PublicKey) {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(LEADERBOARD_SEED), player.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  function badgeAccounts(winner: Keypair) {
+    const [badgeMintPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(BADGE_MINT_SEED),
+        gamePda.toBuffer(),
+        winner.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    const [winnerBadgePda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(WINNER_BADGE_SEED),
+        gamePda.toBuffer(),
+        winner.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    return {
+      authority: winner.publicKey,
+      winner: winner.publicKey,
+      game: gamePda,
+      badgeMint: badgeMintPda,
+      winnerTokenAccount: anchor.utils.token.associatedAddress({
+        mint: badgeMintPda,
+        owner: winner.publicKey,
+      }),
+      winnerBadge: winnerBadgePda,
+      vault: vaultPda,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    };
+  }
+
+  function closeGame() {
+    return program.methods
+      .closeGame()
+      .accounts({
+        creator: provider.wallet.publicKey,
+        capsule: capsulePda,
+        game: gamePda,
+        bond: bondPda,
+      } as any)
+      .rpc();
+  }
+
+  function mintBadge(winner: Keypair) {
+    return program.methods
+      .mintWinnerBadge("https://example.com/badge.json")
+      .accounts(badgeAccounts(winner) as any)
+      .signers([winner])
+      .rpc();
+  }
+
+  // One game, three guessers; the first two are verified as winners in order
+  before(async () => {
+    try {
+      await program.methods
+        .initializeProgram()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Vault already initialized by another test file
+    }
+
+    await ensureOracleRegistered(program, provider, devOracle);
+    await ensureNftCollections(program, provider);
+    for (const player of [firstWinner, secondWinner, loser]) {
+      await airdrop(provider.connection, player.publicKey, 1_000_000_000);
+    }
+
+    const capsule = await createCapsule(program, {
+      answer: secretAnswer,
+      revealIn: 3,
+    });
+    ({ capsulePda, gamePda, bondPda } = capsule);
+
+    const winners = [firstWinner, secondWinner];
+    const guesses: {
+      player: Keypair;
+      guessPda: PublicKey;
+      committed: GuessCommitment;
+    }[] = [];
+    for (const player of [...winners, loser]) {
+      const [guessPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GUESS_SEED),
+          gamePda.toBuffer(),
+          player.publicKey.toBuffer(),
+          Buffer.from([0, 0, 0, 0]),
+        ],
+        program.programId
+      );
+      const committed = commitGuess(secretAnswer);
+      await program.methods
+        .submitGuess(committed.commitment, false)
+        .accounts({
+          guesser: player.publicKey,
+          game: gamePda,
+          capsule: capsulePda,
+          guess: guessPda,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([player])
+        .rpc();
+      await program.methods
+        .initializeLeaderboard(player.publicKey)
+        .accounts({
+          authority: player.publicKey,
+          user: player.publicKey,
+          leaderboard: getLeaderboardPda(player.publicKey),
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([player])
+        .rpc();
+      guesses.push({ player, guessPda, committed });
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+    await program.methods
+      .revealCapsule()
+      .accounts({
+        revealer: provider.wallet.publicKey,
+        capsule: capsulePda,
+      } as any)
+      .rpc();
+
+    for (const { player, guessPda, committed } of guesses) {
+      await revealGuess(
+        program,
+        committed,
+        {
+          guesser: player.publicKey,
+          guess: guessPda,
+          game: gamePda,
+          capsule: capsulePda,
+        },
+        player
+      );
+
+      // Only the first two guessers are judged correct
+      const isCorrect = player !== loser;
+      const verdict = signOracleVerdict(
+        devOracle,
+        secretAnswer,
+        secretAnswer,
+        isCorrect
+      );
+      await program.methods
+        .verifyGuess(
+          secretAnswer,
+          null,
+          isCorrect,
+          verdict.timestamp,
+          verdict.nonce,
+          verdict.signature
+        )
+        .accounts({
+          authority: provider.wallet.publicKey,
+          guess: guessPda,
+          game: gamePda,
+          capsule: capsulePda,
+          leaderboard: getLeaderboardPda(player.publicKey),
+          usedNonce: getUsedNoncePda(program.programId, verdict.nonce),
+        } as any)
+        .preInstructions([verdict.ed25519Ix])
+        .rpc();
+    }
+
+    const game = await program.account.game.fetch(gamePda);
+    expect(game.winners.map((winner) => winner.toBase58())).to.deep.equal(
+      winners.map((winner) => winner.publicKey.toBase58())
+    );
+  });
+
+  // The game is complete and its bond released, so only the missing badges hold it open
+  it("Keeps the game open while a winner has no badge", async () => {
+    const creatorLeaderboardPda = getLeaderboardPda(provider.wallet.publicKey);
+    try {
+      await program.methods
+        .initializeLeaderboard(provider.wallet.publicKey)
+        .accounts({
+          authority: provider.wallet.publicKey,
+          user: provider.wallet.publicKey,
+          leaderboard: creatorLeaderboardPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    } catch (error) {
+      // Creator leaderboard already exists
+    }
+
+    const game = await program.account.game.fetch(gamePda);
+    if (game.isActive) {
+      await program.methods
+        .completeGame()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          game: gamePda,
+          capsule: capsulePda,
+          creatorLeaderboard: creatorLeaderboardPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    }
+
+    await program.methods
+      .releaseBond()
+      .accounts({
+        creator: provider.wallet.publicKey,
+        capsule: capsulePda,
+        game: gamePda,
+        bond: bondPda,
+        config: configPda,
+      } as any)
+      .rpc();
+
+    await mintBadge(firstWinner);
+    try {
+      await closeGame();
+      expect.fail("Expected closing a game with an unbadged winner to fail");
+    } catch (error) {
+      expect(error.message).to.include("WinnerBadgesUnclaimed");
+    }
+  });
+
+  it("Rejects badge mints signed by anyone but the winner, creator or vault authority", async () => {
+    try {
+      await program.methods
+        .mintWinnerBadge("https://example.com/forged.json")
+        .accounts({
+          ...badgeAccounts(secondWinner),
+          authority: loser.publicKey,
+        } as any)
+        .signers([loser])
+        .rpc();
+      expect.fail("Expected a badge minted by a stranger to fail");
+    } catch (error) {
+      expect(error.message).to.include("UnauthorizedBadgeMinter");
+    }
+  });
+
+  it("Badges every winner with their placement", async () => {
+    await mintBadge(secondWinner);
+
+    for (const [index, winner] of [firstWinner, secondWinner].entries()) {
+      const accounts = badgeAccounts(winner);
+      const badge = await program.account.winnerBadge.fetch(
+        accounts.winnerBadge
+      );
+      expect(badge.placement).to.equal(index + 1);
+      expect(badge.badgeType).to.equal(["1st Place", "2nd Place"][index]);
+      expect(badge.winner.toBase58()).to.equal(winner.publicKey.toBase58());
+      expect(badge.badgeMint.toBase58()).to.equal(accounts.badgeMint.toBase58());
+
+      const balance = await provider.connection.getTokenAccountBalance(
+        accounts.winnerTokenAccount
+      );
+      expect(balance.value.amount).to.equal("1");
+
+      // Badges are updated by the program's collection authority
+      const metadata = await provider.connection.getAccountInfo(
+        getMetadataPda(accounts.badgeMint)
+      );
+      expect(metadataUpdateAuthority(metadata.data).toBase58()).to.equal(
+        collectionAuthorityPda.toBase58()
+      );
+    }
+
+    const game = await program.account.game.fetch(gamePda);
+    expect(game.badgedPlacements).to.equal(0b11);
+  });
+
+  it("Rejects a second badge for the same winner", async () => {
+    try {
+      await mintBadge(secondWinner);
+      expect.fail("Expected a duplicate badge to fail");
+    } catch (error) {
+      expect(error.message).to.include("BadgeAlreadyMinted");
+    }
+  });
+
+  it("Rejects badges for players outside the winners list", async () => {
+    try {
+      await mintBadge(loser);
+      expect.fail("Expected a badge for a non-winner to fail");
+    } catch (error) {
+      expect(error.message).to.include("NotEligibleForReward");
+    }
+  });
+
+  it("Closes the game once every winner is badged", async () => {
+    let unbadgedPlacements: number[] | null = null;
+    const listener = program.addEventListener("gameClosed", (event) => {
+      unbadgedPlacements = Array.from(event.unbadgedPlacements);
+    });
+    await closeGame();
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await program.removeEventListener(listener);
+
+    expect(await provider.connection.getAccountInfo(gamePda)).to.be.null;
+    expect(unbadgedPlacements).to.deep.equal([]);
+  });
+});
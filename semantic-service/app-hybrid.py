@@ -36,26 +36,36 @@ except Exception as e:
     print(f"⚠️  OpenAI not available: {e} (falling back to local-only mode)")
 
 # Initialize Oracle signing key
+# The committed oracle_private_key.pem is a public development key: deployments must supply their own
+# through ORACLE_PRIVATE_KEY (PEM contents) or ORACLE_PRIVATE_KEY_PATH, and only register that key on-chain
+DEV_ORACLE_KEY_PATH = 'oracle_private_key.pem'
+oracle_key_path = os.environ.get('ORACLE_PRIVATE_KEY_PATH', DEV_ORACLE_KEY_PATH)
 oracle_private_key = None
 oracle_public_key_bytes = None
 try:
-    # Try to load existing key from file
-    if os.path.exists('oracle_private_key.pem'):
-        with open('oracle_private_key.pem', 'rb') as f:
+    if os.environ.get('ORACLE_PRIVATE_KEY'):
+        oracle_private_key = serialization.load_pem_private_key(
+            os.environ['ORACLE_PRIVATE_KEY'].encode(), password=None
+        )
+        print("✅ Loaded oracle private key from ORACLE_PRIVATE_KEY")
+    elif os.path.exists(oracle_key_path):
+        with open(oracle_key_path, 'rb') as f:
             oracle_private_key = serialization.load_pem_private_key(f.read(), password=None)
-        print("✅ Loaded existing oracle private key")
+        print(f"✅ Loaded existing oracle private key from {oracle_key_path}")
+        if oracle_key_path == DEV_ORACLE_KEY_PATH:
+            print("⚠️  Using the committed development oracle key; never register it on devnet or mainnet")
     else:
         # Generate new key pair
         oracle_private_key = Ed25519PrivateKey.generate()
         
         # Save private key to file
-        with open('oracle_private_key.pem', 'wb') as f:
+        with open(oracle_key_path, 'wb') as f:
             f.write(oracle_private_key.private_bytes(
                 encoding=serialization.Encoding.PEM,
                 format=serialization.PrivateFormat.PKCS8,
                 encryption_algorithm=serialization.NoEncryption()
             ))
-        print("🔑 Generated new oracle private key and saved to oracle_private_key.pem")
+        print(f"🔑 Generated new oracle private key and saved to {oracle_key_path}")
     
     # Get public key bytes for Solana
     oracle_public_key = oracle_private_key.public_key()
@@ -80,7 +90,8 @@ def create_oracle_signature(guess: str, answer: str, is_correct: bool, timestamp
     
     try:
         # Create message to sign (must match Solana program format)
-        message = f"{guess}:{answer}:{is_correct}:{timestamp}:{nonce}"
+        # Booleans are lowercase to match Rust's `bool` Display ("true"/"false")
+        message = f"{guess}:{answer}:{str(is_correct).lower()}:{timestamp}:{nonce}"
         message_bytes = message.encode('utf-8')
         
         # Sign the message